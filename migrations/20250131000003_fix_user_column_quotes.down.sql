@@ -6,13 +6,6 @@
 DROP MATERIALIZED VIEW IF EXISTS active_auto_rebalance_users CASCADE;
 DROP VIEW IF EXISTS user_balances CASCADE;
 
--- Rename columns back to "user" (with quotes)
-ALTER TABLE auto_rebalance_enableds RENAME COLUMN user TO "user";
-ALTER TABLE auto_rebalance_disableds RENAME COLUMN user TO "user";
-ALTER TABLE depositeds RENAME COLUMN user TO "user";
-ALTER TABLE withdrawns RENAME COLUMN user TO "user";
-ALTER TABLE rebalanceds RENAME COLUMN user TO "user";
-
 -- Recreate indexes with quotes
 DROP INDEX IF EXISTS idx_auto_rebalance_enableds_user;
 DROP INDEX IF EXISTS idx_auto_rebalance_disableds_user;
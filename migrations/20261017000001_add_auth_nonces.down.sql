@@ -0,0 +1,4 @@
+-- Rollback: Remove SIWE login nonces
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS auth_nonces;
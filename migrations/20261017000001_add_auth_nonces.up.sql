@@ -0,0 +1,14 @@
+-- Migration: Add SIWE login nonces
+-- Description: Stores single-use nonces issued for Sign-In-With-Ethereum (EIP-4361) wallet login
+-- Date: 2026-10-17
+
+CREATE TABLE IF NOT EXISTS auth_nonces (
+    nonce TEXT PRIMARY KEY,
+    "expiresAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    "consumedAt" TIMESTAMP WITHOUT TIME ZONE,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_auth_nonces_expiresAt ON auth_nonces("expiresAt");
+
+COMMENT ON TABLE auth_nonces IS 'Nonces handed out by GET /auth/nonce; each one can be consumed by exactly one wallet login before it expires';
@@ -0,0 +1,5 @@
+-- Rollback: Remove revocable auth sessions
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS auth_refresh_tokens;
+DROP TABLE IF EXISTS auth_sessions;
@@ -0,0 +1,31 @@
+-- Migration: Add revocable auth sessions
+-- Description: Persists login sessions (referenced by the access token jti) and rotating refresh tokens
+-- Date: 2026-10-17
+
+CREATE TABLE IF NOT EXISTS auth_sessions (
+    id TEXT PRIMARY KEY,
+    "userId" TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    "deviceLabel" TEXT,
+    revoked BOOLEAN NOT NULL DEFAULT false,
+    "revokedAt" TIMESTAMP WITHOUT TIME ZONE,
+    "revokedReason" TEXT,
+    "expiresAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    "lastUsedAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_auth_sessions_userId ON auth_sessions("userId");
+
+CREATE TABLE IF NOT EXISTS auth_refresh_tokens (
+    id TEXT PRIMARY KEY,
+    "sessionId" TEXT NOT NULL REFERENCES auth_sessions(id) ON DELETE CASCADE,
+    "tokenHash" TEXT NOT NULL UNIQUE,
+    "expiresAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    "usedAt" TIMESTAMP WITHOUT TIME ZONE,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_auth_refresh_tokens_sessionId ON auth_refresh_tokens("sessionId");
+
+COMMENT ON TABLE auth_sessions IS 'Wallet login sessions; access tokens carry the session id as jti and are rejected once the session is revoked';
+COMMENT ON TABLE auth_refresh_tokens IS 'Hashed single-use refresh tokens; presenting an already used token revokes the whole session';
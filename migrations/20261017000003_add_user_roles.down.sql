@@ -0,0 +1,4 @@
+-- Rollback: Remove user roles
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS user_roles;
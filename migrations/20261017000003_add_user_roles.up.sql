@@ -0,0 +1,13 @@
+-- Migration: Add user roles
+-- Description: Role assignments (admin, operator, market_curator, analyst) used for per-route permission checks
+-- Date: 2026-10-17
+
+CREATE TABLE IF NOT EXISTS user_roles (
+    "userId" TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role TEXT NOT NULL CHECK (role IN ('admin', 'operator', 'market_curator', 'analyst')),
+    "grantedBy" TEXT,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY ("userId", role)
+);
+
+COMMENT ON TABLE user_roles IS 'Roles attached to users; copied into the access token claims when a session is issued or refreshed';
@@ -0,0 +1,4 @@
+-- Rollback: Remove scoped API keys
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS api_keys;
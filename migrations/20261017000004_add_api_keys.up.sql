@@ -0,0 +1,19 @@
+-- Migration: Add scoped API keys
+-- Description: Bcrypt-hashed integrator API keys with scopes, expiry, last-used tracking and revocation
+-- Date: 2026-10-17
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    "keyPrefix" TEXT NOT NULL UNIQUE,
+    "keyHash" TEXT NOT NULL,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    "expiresAt" TIMESTAMP WITHOUT TIME ZONE,
+    "lastUsedAt" TIMESTAMP WITHOUT TIME ZONE,
+    revoked BOOLEAN NOT NULL DEFAULT false,
+    "revokedAt" TIMESTAMP WITHOUT TIME ZONE,
+    "createdBy" TEXT,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+COMMENT ON TABLE api_keys IS 'Integrator API keys; only the bcrypt hash is stored and keyPrefix is used to look a key up before verifying it';
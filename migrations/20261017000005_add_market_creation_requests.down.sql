@@ -0,0 +1,4 @@
+-- Rollback: Remove market creation requests
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS market_creation_requests;
@@ -0,0 +1,25 @@
+-- Migration: Add market creation requests
+-- Description: Tracks admin market creations by client idempotency key so retries never create a second on-chain market
+-- Date: 2026-10-17
+
+CREATE TABLE IF NOT EXISTS market_creation_requests (
+    "idempotencyKey" TEXT PRIMARY KEY,
+    "requestHash" TEXT NOT NULL,
+    "marketId" TEXT REFERENCES markets_extended(id) ON DELETE SET NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'submitted', 'confirmed', 'failed')),
+    "txHash" TEXT,
+    "blockNumber" BIGINT,
+    error TEXT,
+    "createdBy" TEXT,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_market_creation_requests_marketId ON market_creation_requests("marketId");
+
+CREATE TRIGGER update_market_creation_requests_updated_at
+    BEFORE UPDATE ON market_creation_requests
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE market_creation_requests IS 'One row per POST /markets/create-blockchain idempotency key: pending -> submitted (txHash known) -> confirmed | failed';
@@ -0,0 +1,10 @@
+-- Rollback: Add market lifecycle states
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS market_status_history;
+
+DROP INDEX IF EXISTS idx_markets_extended_startdate;
+
+ALTER TABLE markets_extended DROP CONSTRAINT IF EXISTS markets_extended_status_check;
+
+ALTER TABLE markets_extended DROP COLUMN IF EXISTS "startDate";
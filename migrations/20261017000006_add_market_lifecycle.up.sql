@@ -0,0 +1,29 @@
+-- Migration: Add market lifecycle states
+-- Description: Restricts markets_extended.status to the lifecycle states, adds a scheduled start date and records every status transition
+-- Date: 2026-10-17
+
+ALTER TABLE markets_extended ADD COLUMN IF NOT EXISTS "startDate" TIMESTAMP WITHOUT TIME ZONE;
+
+UPDATE markets_extended
+SET status = 'active'
+WHERE status NOT IN ('draft', 'scheduled', 'active', 'closed', 'pending_resolution', 'resolved', 'cancelled');
+
+ALTER TABLE markets_extended
+    ADD CONSTRAINT markets_extended_status_check
+    CHECK (status IN ('draft', 'scheduled', 'active', 'closed', 'pending_resolution', 'resolved', 'cancelled'));
+
+CREATE INDEX IF NOT EXISTS idx_markets_extended_startdate ON markets_extended("startDate") WHERE status = 'scheduled';
+
+CREATE TABLE IF NOT EXISTS market_status_history (
+    id BIGSERIAL PRIMARY KEY,
+    "marketId" TEXT NOT NULL REFERENCES markets_extended(id) ON DELETE CASCADE,
+    "fromStatus" TEXT NOT NULL,
+    "toStatus" TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    reason TEXT,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_market_status_history_marketId ON market_status_history("marketId", "createdAt");
+
+COMMENT ON TABLE market_status_history IS 'Audit trail of markets_extended.status transitions made by admins and the lifecycle scheduler';
@@ -0,0 +1,6 @@
+-- Rollback: Add optimistic market resolution
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS market_resolution_audit;
+DROP TABLE IF EXISTS market_resolution_disputes;
+DROP TABLE IF EXISTS market_resolution_proposals;
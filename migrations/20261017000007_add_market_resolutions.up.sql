@@ -0,0 +1,56 @@
+-- Migration: Add optimistic market resolution
+-- Description: Outcome proposals with evidence and a challenge window, disputes against them and an audit trail of every resolution step
+-- Date: 2026-10-17
+
+CREATE TABLE IF NOT EXISTS market_resolution_proposals (
+    id TEXT PRIMARY KEY,
+    "marketId" TEXT NOT NULL REFERENCES markets_extended(id) ON DELETE CASCADE,
+    outcome BOOLEAN NOT NULL,
+    evidence TEXT[] NOT NULL DEFAULT '{}',
+    "proposedBy" TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'proposed' CHECK (status IN ('proposed', 'disputed', 'finalized', 'rejected')),
+    "challengeEndsAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    "finalOutcome" BOOLEAN,
+    "decidedBy" TEXT,
+    "decisionReason" TEXT,
+    "decidedAt" TIMESTAMP WITHOUT TIME ZONE,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_market_resolution_proposals_marketId ON market_resolution_proposals("marketId");
+CREATE UNIQUE INDEX idx_market_resolution_proposals_open
+    ON market_resolution_proposals("marketId")
+    WHERE status IN ('proposed', 'disputed');
+
+CREATE TRIGGER update_market_resolution_proposals_updated_at
+    BEFORE UPDATE ON market_resolution_proposals
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE IF NOT EXISTS market_resolution_disputes (
+    id TEXT PRIMARY KEY,
+    "proposalId" TEXT NOT NULL REFERENCES market_resolution_proposals(id) ON DELETE CASCADE,
+    "disputedBy" TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    evidence TEXT[] NOT NULL DEFAULT '{}',
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE ("proposalId", "disputedBy")
+);
+
+CREATE TABLE IF NOT EXISTS market_resolution_audit (
+    id BIGSERIAL PRIMARY KEY,
+    "marketId" TEXT NOT NULL REFERENCES markets_extended(id) ON DELETE CASCADE,
+    "proposalId" TEXT REFERENCES market_resolution_proposals(id) ON DELETE SET NULL,
+    action TEXT NOT NULL CHECK (action IN ('proposed', 'disputed', 'finalized', 'rejected')),
+    actor TEXT NOT NULL,
+    outcome BOOLEAN,
+    note TEXT,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_market_resolution_audit_marketId ON market_resolution_audit("marketId", "createdAt");
+
+COMMENT ON TABLE market_resolution_proposals IS 'Optimistic outcome proposals: proposed -> (disputed) -> finalized | rejected';
+COMMENT ON TABLE market_resolution_disputes IS 'Challenges raised against a proposal before its challenge window ends';
+COMMENT ON TABLE market_resolution_audit IS 'Append-only log of every resolution step per market';
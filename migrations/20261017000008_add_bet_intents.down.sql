@@ -0,0 +1,4 @@
+-- Rollback: Add bet intents
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS bet_intents;
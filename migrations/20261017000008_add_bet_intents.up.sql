@@ -0,0 +1,30 @@
+-- Migration: Add bet intents
+-- Description: Tracks non-custodial bets from unsigned calldata to the user's signed transaction and its BetPlaced event
+-- Date: 2026-10-17
+
+CREATE TABLE IF NOT EXISTS bet_intents (
+    id TEXT PRIMARY KEY,
+    "userId" TEXT NOT NULL REFERENCES users(id),
+    "userAddress" TEXT NOT NULL,
+    "marketId" TEXT NOT NULL REFERENCES markets_extended(id) ON DELETE CASCADE,
+    "blockchainMarketId" BIGINT NOT NULL,
+    position BOOLEAN NOT NULL,
+    amount NUMERIC(78, 18) NOT NULL,
+    status TEXT NOT NULL DEFAULT 'prepared' CHECK (status IN ('prepared', 'submitted', 'confirmed', 'failed', 'expired')),
+    "txHash" TEXT UNIQUE,
+    "betId" TEXT,
+    error TEXT,
+    "expiresAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_bet_intents_userId ON bet_intents("userId", "createdAt");
+CREATE INDEX idx_bet_intents_submitted ON bet_intents(status) WHERE status = 'submitted';
+
+CREATE TRIGGER update_bet_intents_updated_at
+    BEFORE UPDATE ON bet_intents
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE bet_intents IS 'Non-custodial bets: prepared (calldata issued) -> submitted (user tx known) -> confirmed (BetPlaced recorded) | failed | expired';
@@ -0,0 +1,4 @@
+-- Rollback: Add transactions
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS transactions;
@@ -0,0 +1,35 @@
+-- Migration: Add transactions
+-- Description: Persists server-submitted transactions so a background watcher can follow receipts instead of blocking requests
+-- Date: 2026-10-17
+
+CREATE TABLE IF NOT EXISTS transactions (
+    hash TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    "fromAddress" TEXT NOT NULL,
+    "toAddress" TEXT,
+    "marketId" TEXT REFERENCES markets_extended(id) ON DELETE SET NULL,
+    "betId" TEXT REFERENCES bets_extended(id) ON DELETE SET NULL,
+    nonce BIGINT,
+    "gasLimit" NUMERIC(78, 0),
+    "gasPrice" NUMERIC(78, 0),
+    "gasUsed" NUMERIC(78, 0),
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'confirmed', 'failed', 'reorged')),
+    confirmations BIGINT NOT NULL DEFAULT 0,
+    "blockNumber" BIGINT,
+    "blockHash" TEXT,
+    error TEXT,
+    "confirmedAt" TIMESTAMP WITHOUT TIME ZONE,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_transactions_watch ON transactions(status, confirmations) WHERE status <> 'failed';
+CREATE INDEX idx_transactions_betId ON transactions("betId");
+CREATE INDEX idx_transactions_marketId ON transactions("marketId");
+
+CREATE TRIGGER update_transactions_updated_at
+    BEFORE UPDATE ON transactions
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE transactions IS 'Server-submitted transactions: pending -> confirmed | failed, confirmed -> reorged -> pending while within the reorg window';
@@ -0,0 +1,13 @@
+-- Rollback: Add transaction replacements
+-- Date: 2026-10-17
+
+DROP INDEX IF EXISTS idx_transactions_sender_nonce;
+
+DELETE FROM transactions WHERE status = 'replaced';
+
+ALTER TABLE transactions DROP CONSTRAINT IF EXISTS transactions_status_check;
+ALTER TABLE transactions ADD CONSTRAINT transactions_status_check
+    CHECK (status IN ('pending', 'confirmed', 'failed', 'reorged'));
+
+ALTER TABLE transactions DROP COLUMN IF EXISTS "replacedBy";
+ALTER TABLE transactions DROP COLUMN IF EXISTS request;
@@ -0,0 +1,15 @@
+-- Migration: Add transaction replacements
+-- Description: Stores the signed request so stuck transactions can be re-sent with bumped gas under the same nonce
+-- Date: 2026-10-17
+
+ALTER TABLE transactions ADD COLUMN request TEXT;
+ALTER TABLE transactions ADD COLUMN "replacedBy" TEXT REFERENCES transactions(hash) ON DELETE SET NULL;
+
+ALTER TABLE transactions DROP CONSTRAINT IF EXISTS transactions_status_check;
+ALTER TABLE transactions ADD CONSTRAINT transactions_status_check
+    CHECK (status IN ('pending', 'confirmed', 'failed', 'reorged', 'replaced'));
+
+CREATE INDEX idx_transactions_sender_nonce ON transactions("fromAddress", nonce);
+
+COMMENT ON COLUMN transactions.request IS 'JSON-encoded transaction request used to build gas-bumped replacements';
+COMMENT ON COLUMN transactions."replacedBy" IS 'Hash of the replacement sent with the same nonce and higher fees';
@@ -0,0 +1,5 @@
+-- Rollback: Add outbox
+-- Date: 2026-10-17
+
+DROP TRIGGER IF EXISTS update_outbox_updated_at ON outbox;
+DROP TABLE IF EXISTS outbox;
@@ -0,0 +1,36 @@
+-- Migration: Add outbox
+-- Description: Records intended contract calls in the same transaction as the domain change so a worker can execute each exactly once
+-- Date: 2026-10-17
+
+CREATE TABLE IF NOT EXISTS outbox (
+    id TEXT PRIMARY KEY,
+    kind TEXT NOT NULL CHECK (kind IN ('create_market', 'place_bet')),
+    "marketId" TEXT REFERENCES markets_extended(id) ON DELETE CASCADE,
+    "betId" TEXT REFERENCES bets_extended(id) ON DELETE CASCADE,
+    payload TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'processing', 'submitted', 'completed', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    "maxAttempts" INTEGER NOT NULL DEFAULT 5,
+    "nextAttemptAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "lockedAt" TIMESTAMP WITHOUT TIME ZONE,
+    request TEXT,
+    "signedTransaction" TEXT,
+    "txHash" TEXT,
+    "lastError" TEXT,
+    "completedAt" TIMESTAMP WITHOUT TIME ZONE,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_outbox_due ON outbox(status, "nextAttemptAt") WHERE status IN ('pending', 'processing', 'submitted');
+CREATE INDEX idx_outbox_marketId ON outbox("marketId");
+CREATE INDEX idx_outbox_betId ON outbox("betId");
+CREATE UNIQUE INDEX idx_outbox_open_market_creation ON outbox("marketId")
+    WHERE kind = 'create_market' AND status <> 'failed';
+
+CREATE TRIGGER update_outbox_updated_at
+    BEFORE UPDATE ON outbox
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE outbox IS 'Intended contract calls: pending -> processing -> submitted (signed and broadcast) -> completed | failed once retries are exhausted or the transaction reverts';
@@ -0,0 +1,5 @@
+-- Rollback: Add chain reorgs
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS chain_reorgs;
+DROP TABLE IF EXISTS indexed_blocks;
@@ -0,0 +1,28 @@
+-- Migration: Add chain reorgs
+-- Description: Keeps recent indexed block hashes to locate fork points and logs every rollback performed by the indexer
+-- Date: 2026-10-17
+
+CREATE TABLE IF NOT EXISTS indexed_blocks (
+    "blockNumber" BIGINT PRIMARY KEY,
+    "blockHash" TEXT NOT NULL,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS chain_reorgs (
+    id TEXT PRIMARY KEY,
+    "contractAddress" TEXT NOT NULL,
+    "contractName" TEXT NOT NULL,
+    "detectedAtBlock" BIGINT NOT NULL,
+    "forkBlock" BIGINT NOT NULL,
+    depth BIGINT NOT NULL,
+    "expectedHash" TEXT NOT NULL,
+    "canonicalHash" TEXT,
+    "eventsRolledBack" BIGINT NOT NULL DEFAULT 0,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_chain_reorgs_createdAt ON chain_reorgs("createdAt" DESC);
+CREATE INDEX idx_chain_reorgs_contractAddress ON chain_reorgs("contractAddress");
+
+COMMENT ON TABLE indexed_blocks IS 'Hashes of recently indexed blocks, compared against the canonical chain to find where a reorg forked';
+COMMENT ON TABLE chain_reorgs IS 'Reorgs detected by the indexer: events above forkBlock were rolled back and re-ingested';
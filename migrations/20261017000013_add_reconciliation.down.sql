@@ -0,0 +1,5 @@
+-- Rollback: Add reconciliation
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS reconciliation_drifts;
+DROP TABLE IF EXISTS reconciliation_runs;
@@ -0,0 +1,34 @@
+-- Migration: Add reconciliation
+-- Description: Stores each database-vs-chain reconciliation run and the per-field drift it found
+-- Date: 2026-10-17
+
+CREATE TABLE IF NOT EXISTS reconciliation_runs (
+    id TEXT PRIMARY KEY,
+    status TEXT NOT NULL DEFAULT 'running' CHECK (status IN ('running', 'completed', 'failed')),
+    "marketsChecked" INTEGER NOT NULL DEFAULT 0,
+    "marketsDrifted" INTEGER NOT NULL DEFAULT 0,
+    "marketsUnreadable" INTEGER NOT NULL DEFAULT 0,
+    "fieldsHealed" INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    "startedAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "completedAt" TIMESTAMP WITHOUT TIME ZONE
+);
+
+CREATE TABLE IF NOT EXISTS reconciliation_drifts (
+    id BIGSERIAL PRIMARY KEY,
+    "runId" TEXT NOT NULL REFERENCES reconciliation_runs(id) ON DELETE CASCADE,
+    "marketId" TEXT NOT NULL REFERENCES markets_extended(id) ON DELETE CASCADE,
+    "blockchainMarketId" BIGINT NOT NULL,
+    field TEXT NOT NULL,
+    "databaseValue" TEXT,
+    "chainValue" TEXT,
+    healed BOOLEAN NOT NULL DEFAULT false,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_reconciliation_runs_startedAt ON reconciliation_runs("startedAt" DESC);
+CREATE INDEX idx_reconciliation_drifts_runId ON reconciliation_drifts("runId");
+CREATE INDEX idx_reconciliation_drifts_marketId ON reconciliation_drifts("marketId");
+
+COMMENT ON TABLE reconciliation_runs IS 'Periodic comparison of markets_extended against on-chain market state';
+COMMENT ON TABLE reconciliation_drifts IS 'Fields that differed from the chain in a run; healed fields were overwritten with the on-chain value';
@@ -0,0 +1,12 @@
+-- Rollback: Add event dead letters
+-- Date: 2026-10-17
+
+DROP INDEX IF EXISTS idx_blockchain_events_status;
+DROP INDEX IF EXISTS idx_blockchain_events_due;
+
+ALTER TABLE blockchain_events
+    DROP COLUMN IF EXISTS "processedAt",
+    DROP COLUMN IF EXISTS "nextRetryAt",
+    DROP COLUMN IF EXISTS "lastError",
+    DROP COLUMN IF EXISTS attempts,
+    DROP COLUMN IF EXISTS status;
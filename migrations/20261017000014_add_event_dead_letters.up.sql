@@ -0,0 +1,44 @@
+-- Migration: Add event dead letters
+-- Description: Tracks processing of every ingested event in blockchain_events with retries and a dead-letter state
+-- Date: 2026-10-17
+
+ALTER TABLE blockchain_events
+    ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'processed', 'dead_letter', 'discarded')),
+    ADD COLUMN IF NOT EXISTS attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS "lastError" TEXT,
+    ADD COLUMN IF NOT EXISTS "nextRetryAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD COLUMN IF NOT EXISTS "processedAt" TIMESTAMP WITHOUT TIME ZONE;
+
+CREATE INDEX IF NOT EXISTS idx_blockchain_events_due ON blockchain_events("eventType", "nextRetryAt")
+    WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_blockchain_events_status ON blockchain_events(status, "updatedAt");
+
+-- Track events indexed before this migration
+INSERT INTO blockchain_events (
+    id, "eventType", "blockchainId", "blockNumber", "blockTimestamp", "transactionHash",
+    processed, status, "processedAt"
+)
+SELECT bp.id, 'BetPlaced', bp.id, bp.block_number::bigint, bp.block_timestamp::bigint,
+       bp.transaction_hash, be.id IS NOT NULL,
+       CASE WHEN be.id IS NOT NULL THEN 'processed' ELSE 'pending' END,
+       CASE WHEN be.id IS NOT NULL THEN NOW() END
+FROM bet_placeds bp
+LEFT JOIN bets_extended be ON be.id = bp.id
+ON CONFLICT ("eventType", "blockchainId") DO NOTHING;
+
+INSERT INTO blockchain_events (
+    id, "eventType", "blockchainId", "blockNumber", "blockTimestamp", "transactionHash",
+    processed, status, "processedAt"
+)
+SELECT mr.id, 'MarketResolved', mr.id, mr.block_number::bigint, mr.block_timestamp::bigint,
+       mr.transaction_hash, resolved, CASE WHEN resolved THEN 'processed' ELSE 'pending' END,
+       CASE WHEN resolved THEN NOW() END
+FROM (
+    SELECT mr.*, COALESCE(me.status = 'resolved' AND me.result = mr.outcome, false) as resolved
+    FROM market_resolveds mr
+    LEFT JOIN markets_extended me ON me."blockchainMarketId" = mr.market_id
+) mr
+ON CONFLICT ("eventType", "blockchainId") DO NOTHING;
+
+COMMENT ON TABLE blockchain_events IS 'Processing state of ingested events: pending -> processed, or dead_letter once attempts are exhausted (an admin can retry or discard it)';
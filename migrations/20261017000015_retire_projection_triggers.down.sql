@@ -0,0 +1,274 @@
+-- Rollback: Retire projection triggers
+-- Date: 2026-10-17
+
+CREATE OR REPLACE FUNCTION sync_market_created()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO markets_extended (
+        id,
+        "blockchainMarketId",
+        question,
+        "endDate",
+        "vaultAddress",
+        status,
+        "createdAt",
+        "updatedAt"
+    ) VALUES (
+        gen_random_uuid()::text,
+        NEW.market_id,
+        NEW.question,
+        to_timestamp(NEW.end_time),
+        NEW.vault_address,
+        'active',
+        to_timestamp(NEW.block_timestamp),
+        NOW()
+    )
+    ON CONFLICT ("blockchainMarketId") DO UPDATE SET
+        "vaultAddress" = EXCLUDED."vaultAddress",
+        "endDate" = EXCLUDED."endDate",
+        "updatedAt" = NOW();
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION sync_bet_placed()
+RETURNS TRIGGER AS $$
+DECLARE
+    user_id_var text;
+    market_id_var text;
+BEGIN
+    -- Get user_id from users table by address
+    SELECT id INTO user_id_var FROM users WHERE address = NEW.user LIMIT 1;
+
+    -- If user doesn't exist, create it
+    IF user_id_var IS NULL THEN
+        INSERT INTO users (id, address, "createdAt", "updatedAt")
+        VALUES (gen_random_uuid()::text, NEW.user, NOW(), NOW())
+        RETURNING id INTO user_id_var;
+    END IF;
+
+    -- Get market_id from markets_extended by blockchainMarketId
+    SELECT id INTO market_id_var FROM markets_extended WHERE "blockchainMarketId" = NEW.market_id LIMIT 1;
+
+    -- Insert into bets_extended with shares (no bet_id needed anymore)
+    -- Use a combination of user, market, and block as unique identifier
+    INSERT INTO bets_extended (
+        id,
+        "blockchainBetId",
+        "userId",
+        "marketId",
+        position,
+        amount,
+        shares,
+        odds,
+        status,
+        "createdAt",
+        "updatedAt"
+    ) VALUES (
+        NEW.id,
+        NULL, -- No bet_id in new contract
+        user_id_var,
+        market_id_var,
+        NEW.position,
+        NEW.amount,
+        NEW.shares,
+        1.0, -- Default odds, will be calculated by trigger
+        'active',
+        to_timestamp(NEW.block_timestamp),
+        NOW()
+    )
+    ON CONFLICT (id) DO NOTHING;
+
+    -- Update market shares and pool sizes
+    IF NEW.position THEN
+        UPDATE markets_extended
+        SET "totalYesShares" = "totalYesShares" + COALESCE(NEW.shares, 0),
+            "yesPoolSize" = "yesPoolSize" + NEW.amount,
+            "totalPoolSize" = "totalPoolSize" + NEW.amount,
+            "countYes" = "countYes" + 1,
+            volume = volume + NEW.amount,
+            "updatedAt" = NOW()
+        WHERE "blockchainMarketId" = NEW.market_id;
+    ELSE
+        UPDATE markets_extended
+        SET "totalNoShares" = "totalNoShares" + COALESCE(NEW.shares, 0),
+            "noPoolSize" = "noPoolSize" + NEW.amount,
+            "totalPoolSize" = "totalPoolSize" + NEW.amount,
+            "countNo" = "countNo" + 1,
+            volume = volume + NEW.amount,
+            "updatedAt" = NOW()
+        WHERE "blockchainMarketId" = NEW.market_id;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION sync_market_resolved()
+RETURNS TRIGGER AS $$
+BEGIN
+    -- Update market status and result
+    UPDATE markets_extended
+    SET
+        status = 'resolved',
+        result = NEW.outcome,
+        "resolutionDate" = to_timestamp(NEW.block_timestamp),
+        "updatedAt" = NOW()
+    WHERE "blockchainMarketId" = NEW.market_id;
+
+    -- Update bets for this market
+    -- Mark winning bets as 'won' and losing bets as 'lost'
+    UPDATE bets_extended
+    SET
+        status = CASE
+            WHEN position = NEW.outcome THEN 'won'
+            ELSE 'lost'
+        END,
+        "updatedAt" = NOW()
+    WHERE "marketId" IN (
+        SELECT id FROM markets_extended WHERE "blockchainMarketId" = NEW.market_id
+    ) AND status = 'active';
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION sync_winnings_claimed()
+RETURNS TRIGGER AS $$
+DECLARE
+    bet_records RECORD;
+BEGIN
+    -- New contract uses market_id instead of bet_id
+    -- Find all bets by user address for this market and mark them as claimed
+    FOR bet_records IN
+        SELECT be.id
+        FROM bets_extended be
+        JOIN users u ON be."userId" = u.id
+        JOIN markets_extended me ON be."marketId" = me.id
+        WHERE u.address = NEW.user
+        AND me."blockchainMarketId" = NEW.market_id
+        AND be.status IN ('active', 'won')
+    LOOP
+        UPDATE bets_extended
+        SET
+            payout = NEW.winning_amount,
+            status = 'claimed',
+            "updatedAt" = NOW()
+        WHERE id = bet_records.id;
+    END LOOP;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION update_market_from_bet()
+RETURNS TRIGGER AS $$
+BEGIN
+    -- Handle UPDATE
+    IF (TG_OP = 'UPDATE') THEN
+        -- Remove old bet amounts
+        IF OLD.position = TRUE THEN
+            UPDATE markets_extended
+            SET
+                "yesPoolSize" = "yesPoolSize" - OLD.amount,
+                "totalPoolSize" = "totalPoolSize" - OLD.amount,
+                "countYes" = "countYes" - 1,
+                "updatedAt" = NOW()
+            WHERE id = OLD."marketId";
+        ELSE
+            UPDATE markets_extended
+            SET
+                "noPoolSize" = "noPoolSize" - OLD.amount,
+                "totalPoolSize" = "totalPoolSize" - OLD.amount,
+                "countNo" = "countNo" - 1,
+                "updatedAt" = NOW()
+            WHERE id = OLD."marketId";
+        END IF;
+
+        -- Add new bet amounts
+        IF NEW.position = TRUE THEN
+            UPDATE markets_extended
+            SET
+                "yesPoolSize" = "yesPoolSize" + NEW.amount,
+                "totalPoolSize" = "totalPoolSize" + NEW.amount,
+                "countYes" = "countYes" + 1,
+                "updatedAt" = NOW()
+            WHERE id = NEW."marketId";
+        ELSE
+            UPDATE markets_extended
+            SET
+                "noPoolSize" = "noPoolSize" + NEW.amount,
+                "totalPoolSize" = "totalPoolSize" + NEW.amount,
+                "countNo" = "countNo" + 1,
+                "updatedAt" = NOW()
+            WHERE id = NEW."marketId";
+        END IF;
+        RETURN NEW;
+    END IF;
+
+    -- Handle DELETE
+    IF (TG_OP = 'DELETE') THEN
+        IF OLD.position = TRUE THEN
+            UPDATE markets_extended
+            SET
+                "yesPoolSize" = "yesPoolSize" - OLD.amount,
+                "totalPoolSize" = "totalPoolSize" - OLD.amount,
+                "countYes" = "countYes" - 1,
+                "updatedAt" = NOW()
+            WHERE id = OLD."marketId";
+        ELSE
+            UPDATE markets_extended
+            SET
+                "noPoolSize" = "noPoolSize" - OLD.amount,
+                "totalPoolSize" = "totalPoolSize" - OLD.amount,
+                "countNo" = "countNo" - 1,
+                "updatedAt" = NOW()
+            WHERE id = OLD."marketId";
+        END IF;
+        RETURN OLD;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION update_market_volume()
+RETURNS TRIGGER AS $$
+BEGIN
+    -- Automatically calculate volume as sum of yes and no pool sizes
+    NEW.volume = NEW."yesPoolSize" + NEW."noPoolSize";
+    NEW."totalPoolSize" = NEW."yesPoolSize" + NEW."noPoolSize";
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER sync_market_created_trigger
+    AFTER INSERT ON market_createds
+    FOR EACH ROW
+    EXECUTE FUNCTION sync_market_created();
+
+CREATE TRIGGER sync_bet_placed_trigger
+    AFTER INSERT ON bet_placeds
+    FOR EACH ROW
+    EXECUTE FUNCTION sync_bet_placed();
+
+CREATE TRIGGER sync_market_resolved_trigger
+    AFTER INSERT ON market_resolveds
+    FOR EACH ROW
+    EXECUTE FUNCTION sync_market_resolved();
+
+CREATE TRIGGER sync_winnings_claimed_trigger
+    AFTER INSERT ON winnings_claimeds
+    FOR EACH ROW
+    EXECUTE FUNCTION sync_winnings_claimed();
+
+CREATE TRIGGER trigger_update_market_from_bet
+    AFTER UPDATE OR DELETE ON bets_extended
+    FOR EACH ROW
+    EXECUTE FUNCTION update_market_from_bet();
+
+CREATE TRIGGER trigger_update_market_volume
+    BEFORE INSERT OR UPDATE OF "yesPoolSize", "noPoolSize" ON markets_extended
+    FOR EACH ROW
+    EXECUTE FUNCTION update_market_volume();
@@ -0,0 +1,46 @@
+-- Migration: Retire projection triggers
+-- Description: Market pools, counts, shares, probability and bet statuses are now maintained by the Rust projection engine
+-- Date: 2026-10-17
+
+DROP TRIGGER IF EXISTS sync_market_created_trigger ON market_createds;
+DROP TRIGGER IF EXISTS sync_bet_placed_trigger ON bet_placeds;
+DROP TRIGGER IF EXISTS sync_market_resolved_trigger ON market_resolveds;
+DROP TRIGGER IF EXISTS sync_winnings_claimed_trigger ON winnings_claimeds;
+DROP TRIGGER IF EXISTS trigger_update_market_from_bet ON bets_extended;
+DROP TRIGGER IF EXISTS trigger_update_market_volume ON markets_extended;
+
+DROP FUNCTION IF EXISTS sync_market_created();
+DROP FUNCTION IF EXISTS sync_bet_placed();
+DROP FUNCTION IF EXISTS sync_market_resolved();
+DROP FUNCTION IF EXISTS sync_winnings_claimed();
+DROP FUNCTION IF EXISTS update_market_from_bet();
+DROP FUNCTION IF EXISTS update_market_volume();
+
+-- Recompute aggregates once with the engine's rules so existing markets start consistent
+UPDATE markets_extended me
+SET "yesPoolSize" = agg.yes_pool,
+    "noPoolSize" = agg.no_pool,
+    "totalPoolSize" = agg.yes_pool + agg.no_pool,
+    volume = agg.yes_pool + agg.no_pool,
+    "countYes" = agg.count_yes,
+    "countNo" = agg.count_no,
+    "totalYesShares" = agg.yes_shares,
+    "totalNoShares" = agg.no_shares,
+    probability = CASE
+        WHEN agg.yes_pool + agg.no_pool > 0 THEN ROUND(agg.yes_pool * 100 / (agg.yes_pool + agg.no_pool))::integer
+        ELSE 50
+    END
+FROM (
+    SELECT "marketId",
+           COALESCE(SUM(amount) FILTER (WHERE position), 0) as yes_pool,
+           COALESCE(SUM(amount) FILTER (WHERE NOT position), 0) as no_pool,
+           COUNT(*) FILTER (WHERE position)::integer as count_yes,
+           COUNT(*) FILTER (WHERE NOT position)::integer as count_no,
+           COALESCE(SUM(COALESCE(shares, 0)) FILTER (WHERE position), 0) as yes_shares,
+           COALESCE(SUM(COALESCE(shares, 0)) FILTER (WHERE NOT position), 0) as no_shares
+    FROM bets_extended
+    WHERE status IN ('active', 'won', 'lost', 'claimed')
+      AND position IS NOT NULL AND amount IS NOT NULL
+    GROUP BY "marketId"
+) agg
+WHERE me.id = agg."marketId";
@@ -0,0 +1,9 @@
+-- Rollback: Add rebalance decisions
+-- Date: 2026-10-17
+
+DROP TABLE IF EXISTS rebalance_decisions;
+
+DELETE FROM outbox WHERE kind = 'rebalance';
+ALTER TABLE outbox DROP CONSTRAINT IF EXISTS outbox_kind_check;
+ALTER TABLE outbox ADD CONSTRAINT outbox_kind_check
+    CHECK (kind IN ('create_market', 'place_bet'));
@@ -0,0 +1,31 @@
+-- Migration: Add rebalance decisions
+-- Description: Logs every rebalancer operator decision and allows rebalance calls in the outbox
+-- Date: 2026-10-17
+
+ALTER TABLE outbox DROP CONSTRAINT IF EXISTS outbox_kind_check;
+ALTER TABLE outbox ADD CONSTRAINT outbox_kind_check
+    CHECK (kind IN ('create_market', 'place_bet', 'rebalance'));
+
+CREATE TABLE IF NOT EXISTS rebalance_decisions (
+    id TEXT PRIMARY KEY,
+    "user" TEXT NOT NULL,
+    "riskProfile" INTEGER NOT NULL,
+    "currentProtocol" TEXT,
+    "currentApy" NUMERIC,
+    "targetProtocol" TEXT,
+    "targetApy" NUMERIC,
+    balance NUMERIC NOT NULL DEFAULT 0,
+    "expectedGain" NUMERIC NOT NULL DEFAULT 0,
+    "gasCost" NUMERIC NOT NULL DEFAULT 0,
+    "netGain" NUMERIC NOT NULL DEFAULT 0,
+    action TEXT NOT NULL CHECK (action IN ('submitted', 'dry_run', 'skipped')),
+    reason TEXT NOT NULL,
+    "outboxId" TEXT REFERENCES outbox(id) ON DELETE SET NULL,
+    "createdAt" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_rebalance_decisions_user ON rebalance_decisions("user", "createdAt" DESC);
+CREATE INDEX idx_rebalance_decisions_action ON rebalance_decisions(action, "createdAt" DESC);
+CREATE INDEX idx_rebalance_decisions_outboxId ON rebalance_decisions("outboxId");
+
+COMMENT ON TABLE rebalance_decisions IS 'Rebalancer operator decisions per auto-rebalance user: submitted (queued in the outbox), dry_run (would have been submitted) or skipped, with the APYs and gains behind them';
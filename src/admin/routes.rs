@@ -1,22 +1,94 @@
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
     middleware,
     response::Json,
-    routing::{get, post},
-    Router,
+    routing::{delete, get, post},
+    Extension, Router,
 };
+use serde::Deserialize;
 use serde_json::json;
 use sqlx::Row;
 
-use crate::{db::Database, error::AppError, middleware::auth::require_api_key};
+use crate::{
+    db::Database,
+    error::AppError,
+    middleware::{require_jwt_or_api_key, require_permission},
+    models::CreateApiKeyRequest,
+    services::{
+        blockchain_event::EVENT_STATUSES, rebalancer::DECISION_ACTIONS, ApiKeyService,
+        BlockchainEventService, BlockchainSyncService, IndexerService, OutboxService,
+        RebalancerService, ReplayService, RpcPool, UserService,
+    },
+    utils::{ApiKeyScope, Claims, Permission, Role},
+};
 
-pub fn create_admin_router() -> Router<(Database, crate::config::Config)> {
-    Router::new()
+pub fn create_admin_router(
+    state: (Database, crate::config::Config),
+) -> Router<(Database, crate::config::Config)> {
+    let stats_routes = Router::new()
         .route("/stats", get(get_admin_stats))
         .route("/users", get(list_all_users))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::ViewAdminStats, req, next)
+        }));
+
+    let sync_routes = Router::new()
         .route("/sync/trigger", post(trigger_admin_sync))
         .route("/sync/blockchain", post(trigger_blockchain_sync))
-        .route_layer(middleware::from_fn(require_api_key))
+        .route("/replay/events", post(replay_events))
+        .route("/replay/rebuild", post(rebuild_projections))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::TriggerSync, req, next)
+        }));
+
+    let sync_status_routes = Router::new()
+        .route("/reconciliation", get(get_reconciliation))
+        .route("/rpc", get(get_rpc_status))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::ViewSyncStatus, req, next)
+        }));
+
+    let role_routes = Router::new()
+        .route(
+            "/users/:address/roles",
+            get(get_user_roles).post(grant_user_role),
+        )
+        .route("/users/:address/roles/:role", delete(revoke_user_role))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::ManageRoles, req, next)
+        }));
+
+    let api_key_routes = Router::new()
+        .route("/api-keys", get(list_api_keys).post(create_api_key))
+        .route("/api-keys/:id", delete(revoke_api_key))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::ManageApiKeys, req, next)
+        }));
+
+    let outbox_routes = Router::new()
+        .route("/outbox", get(list_outbox_entries))
+        .route("/outbox/:id", get(get_outbox_entry))
+        .route("/outbox/:id/retry", post(retry_outbox_entry))
+        .route("/indexer/reorgs", get(list_chain_reorgs))
+        .route("/events", get(list_blockchain_events))
+        .route("/events/:id/retry", post(retry_blockchain_event))
+        .route("/events/:id/discard", post(discard_blockchain_event))
+        .route("/rebalancer/decisions", get(list_rebalance_decisions))
+        .route("/rebalancer/run", post(run_rebalancer))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::TriggerSync, req, next)
+        }));
+
+    stats_routes
+        .merge(sync_routes)
+        .merge(outbox_routes)
+        .merge(sync_status_routes)
+        .merge(role_routes)
+        .merge(api_key_routes)
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            require_jwt_or_api_key,
+        ))
 }
 
 async fn get_admin_stats(
@@ -76,10 +148,10 @@ async fn list_all_users(
 }
 
 async fn trigger_admin_sync(
-    State((db, _)): State<(Database, crate::config::Config)>,
+    State((db, config)): State<(Database, crate::config::Config)>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let sync_service = crate::services::SyncService::new(db);
-    sync_service.full_sync().await?;
+    sync_service.full_sync(&config).await?;
 
     Ok(Json(json!({
         "message": "Admin sync triggered successfully"
@@ -90,12 +162,416 @@ async fn trigger_blockchain_sync(
     State((db, config)): State<(Database, crate::config::Config)>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let sync_service = crate::services::SyncService::new(db);
-    let synced_count = sync_service
-        .sync_from_blockchain(&config.whizy_prediction_market_addr, &config.base_rpc_url)
-        .await?;
+    let synced_count = sync_service.sync_from_blockchain(&config).await?;
 
     Ok(Json(json!({
         "message": format!("Blockchain sync completed - {} markets synced", synced_count),
         "synced_count": synced_count
     })))
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReplayEventsRequest {
+    from_block: u64,
+    to_block: u64,
+    #[serde(default)]
+    contracts: Vec<String>,
+    #[serde(default)]
+    events: Vec<String>,
+}
+
+async fn replay_events(
+    State((db, config)): State<(Database, crate::config::Config)>,
+    Json(payload): Json<ReplayEventsRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let summary = ReplayService::new(db)
+        .replay_events(
+            &config,
+            &payload.contracts,
+            &payload.events,
+            payload.from_block,
+            payload.to_block,
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "message": format!(
+            "Replayed {} events from blocks {}..{}",
+            summary.events, payload.from_block, payload.to_block
+        ),
+        "data": summary
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RebuildQuery {
+    dry_run: Option<bool>,
+}
+
+async fn rebuild_projections(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Query(query): Query<RebuildQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let report = ReplayService::new(db)
+        .rebuild(query.dry_run.unwrap_or(true))
+        .await?;
+
+    Ok(Json(json!({ "data": report })))
+}
+
+#[derive(Debug, Deserialize)]
+struct OutboxQuery {
+    status: Option<String>,
+    limit: Option<i64>,
+}
+
+async fn list_outbox_entries(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Query(query): Query<OutboxQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    if let Some(status) = query.status.as_deref() {
+        if !["pending", "processing", "submitted", "completed", "failed"].contains(&status) {
+            return Err(AppError::BadRequest(format!(
+                "Unknown outbox status: {}",
+                status
+            )));
+        }
+    }
+
+    let outbox_service = OutboxService::new(db);
+    let entries = outbox_service
+        .list_entries(query.status.as_deref(), query.limit.unwrap_or(100))
+        .await?;
+
+    Ok(Json(json!({
+        "data": entries
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+struct ReorgQuery {
+    limit: Option<i64>,
+}
+
+async fn list_chain_reorgs(
+    State((db, config)): State<(Database, crate::config::Config)>,
+    Query(query): Query<ReorgQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let indexer_service = IndexerService::new(db, &config)?;
+    let stats = indexer_service.reorg_stats().await?;
+    let reorgs = indexer_service
+        .list_reorgs(query.limit.unwrap_or(50))
+        .await?;
+
+    Ok(Json(json!({
+        "data": {
+            "stats": stats,
+            "reorgs": reorgs
+        }
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+struct ReconciliationQuery {
+    limit: Option<i64>,
+}
+
+async fn get_reconciliation(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Query(query): Query<ReconciliationQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let sync_service = BlockchainSyncService::new(db);
+    let runs = sync_service
+        .list_reconciliation_runs(query.limit.unwrap_or(10))
+        .await?;
+    let drifts = match runs.first() {
+        Some(run) => sync_service.list_reconciliation_drifts(&run.id).await?,
+        None => Vec::new(),
+    };
+
+    Ok(Json(json!({
+        "data": {
+            "run": runs.first(),
+            "drifts": drifts,
+            "recentRuns": runs
+        }
+    })))
+}
+
+async fn get_rpc_status(
+    State((_, config)): State<(Database, crate::config::Config)>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let pool = RpcPool::shared(&config)?;
+
+    Ok(Json(json!({
+        "data": {
+            "endpoints": pool.statuses()
+        }
+    })))
+}
+
+async fn get_outbox_entry(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let outbox_service = OutboxService::new(db);
+    let entry = outbox_service.get_entry(&id).await?;
+
+    Ok(Json(json!({
+        "data": entry
+    })))
+}
+
+async fn retry_outbox_entry(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let outbox_service = OutboxService::new(db);
+    let entry = outbox_service.retry_entry(&id).await?;
+
+    Ok(Json(json!({
+        "message": "Outbox entry requeued",
+        "data": entry
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockchainEventQuery {
+    status: Option<String>,
+    event_type: Option<String>,
+    limit: Option<i64>,
+}
+
+async fn list_blockchain_events(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Query(query): Query<BlockchainEventQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let status = query.status.as_deref().unwrap_or("dead_letter");
+    if !EVENT_STATUSES.contains(&status) {
+        return Err(AppError::BadRequest(format!(
+            "Unknown event status: {}",
+            status
+        )));
+    }
+
+    let events = BlockchainEventService::new(db)
+        .list_events(
+            Some(status),
+            query.event_type.as_deref(),
+            query.limit.unwrap_or(100),
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "data": events
+    })))
+}
+
+async fn retry_blockchain_event(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let event = BlockchainEventService::new(db).retry_event(&id).await?;
+
+    Ok(Json(json!({
+        "message": "Event requeued",
+        "data": event
+    })))
+}
+
+async fn discard_blockchain_event(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let event = BlockchainEventService::new(db).discard_event(&id).await?;
+
+    Ok(Json(json!({
+        "message": "Event discarded",
+        "data": event
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+struct RebalanceDecisionQuery {
+    user: Option<String>,
+    action: Option<String>,
+    limit: Option<i64>,
+}
+
+async fn list_rebalance_decisions(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Query(query): Query<RebalanceDecisionQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    if let Some(action) = query.action.as_deref() {
+        if !DECISION_ACTIONS.contains(&action) {
+            return Err(AppError::BadRequest(format!(
+                "Unknown rebalance action: {}",
+                action
+            )));
+        }
+    }
+
+    let decisions = RebalancerService::new(db)
+        .list_decisions(
+            query.user.as_deref(),
+            query.action.as_deref(),
+            query.limit.unwrap_or(100),
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "data": decisions
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RebalancerRunQuery {
+    dry_run: Option<bool>,
+}
+
+async fn run_rebalancer(
+    State((db, config)): State<(Database, crate::config::Config)>,
+    Query(query): Query<RebalancerRunQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let summary = RebalancerService::new(db)
+        .run(&config, query.dry_run.unwrap_or(true))
+        .await?;
+
+    Ok(Json(json!({
+        "message": format!(
+            "Evaluated {} users: {} submitted, {} dry-run, {} skipped, {} failed",
+            summary.evaluated,
+            summary.submitted,
+            summary.dry_run_rebalances,
+            summary.skipped,
+            summary.failed
+        ),
+        "data": summary
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+struct GrantRoleRequest {
+    role: String,
+}
+
+async fn get_user_roles(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Path(address): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let user_service = UserService::new(db);
+    let user = user_service.get_user_by_address(&address).await?;
+    let roles = user_service.get_user_roles(&user.id).await?;
+
+    Ok(Json(json!({
+        "data": {
+            "userId": user.id,
+            "address": user.address,
+            "roles": roles
+        }
+    })))
+}
+
+async fn grant_user_role(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    Path(address): Path<String>,
+    Json(payload): Json<GrantRoleRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let role: Role = payload.role.parse()?;
+    let user_service = UserService::new(db);
+    let user = user_service.get_user_by_address(&address).await?;
+
+    user_service
+        .grant_role(&user.id, role, Some(&claims.address))
+        .await?;
+    let roles = user_service.get_user_roles(&user.id).await?;
+
+    Ok(Json(json!({
+        "message": format!("Granted role {} to {}", role, user.address),
+        "data": {
+            "userId": user.id,
+            "address": user.address,
+            "roles": roles
+        }
+    })))
+}
+
+async fn revoke_user_role(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Path((address, role)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let role: Role = role.parse()?;
+    let user_service = UserService::new(db);
+    let user = user_service.get_user_by_address(&address).await?;
+
+    user_service.revoke_role(&user.id, role).await?;
+    let roles = user_service.get_user_roles(&user.id).await?;
+
+    Ok(Json(json!({
+        "message": format!("Revoked role {} from {}", role, user.address),
+        "data": {
+            "userId": user.id,
+            "address": user.address,
+            "roles": roles
+        }
+    })))
+}
+
+async fn list_api_keys(
+    State((db, _)): State<(Database, crate::config::Config)>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let api_key_service = ApiKeyService::new(db);
+    let keys = api_key_service.list_keys().await?;
+
+    Ok(Json(json!({
+        "data": keys
+    })))
+}
+
+async fn create_api_key(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let scopes = payload
+        .scopes
+        .iter()
+        .map(|scope| scope.parse::<ApiKeyScope>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let api_key_service = ApiKeyService::new(db);
+    let (api_key, raw_key) = api_key_service
+        .create_key(
+            &payload.name,
+            &scopes,
+            payload.expires_at,
+            Some(&claims.address),
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "message": "API key created; store the key now, it cannot be shown again",
+        "data": {
+            "key": raw_key,
+            "apiKey": api_key
+        }
+    })))
+}
+
+async fn revoke_api_key(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let api_key_service = ApiKeyService::new(db);
+    let api_key = api_key_service.revoke_key(&id).await?;
+
+    Ok(Json(json!({
+        "message": "API key revoked successfully",
+        "data": api_key
+    })))
+}
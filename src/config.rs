@@ -9,10 +9,35 @@ pub struct Config {
     pub port: u16,
     pub log_level: String,
     pub cors_origin: String,
-    pub api_key: String,
-    pub jwt_secret: String,
+    pub jwt_keys_dir: Option<String>,
+    pub jwt_active_kid: Option<String>,
+    pub jwt_allow_ephemeral_key: bool,
+    pub admin_addresses: Vec<String>,
+    pub siwe_domain: String,
+    pub siwe_nonce_ttl_secs: u64,
+    pub access_token_ttl_secs: u64,
+    pub refresh_token_ttl_secs: u64,
+    pub resolution_challenge_window_secs: u64,
+    pub bet_quote_ttl_secs: u64,
     pub base_rpc_url: String,
+    pub ws_rpc_url: Option<String>,
+    pub rpc_fallback_urls: Vec<String>,
+    pub rpc_max_retries: u32,
+    pub rpc_retry_backoff_ms: u64,
+    pub rpc_rate_limit_per_sec: u32,
+    pub rpc_timeout_secs: u64,
+    pub rpc_failure_threshold: u32,
+    pub rpc_cooldown_secs: u64,
     pub base_chain_id: u64,
+    pub explorer_url: String,
+    pub private_key: Option<String>,
+    pub tx_stuck_after_secs: u64,
+    pub tx_gas_bump_percent: u64,
+    pub tx_max_gas_bumps: u64,
+    pub outbox_max_attempts: i32,
+    pub outbox_retry_backoff_secs: u64,
+    pub event_max_attempts: i32,
+    pub event_retry_backoff_secs: u64,
     pub whizy_prediction_market_addr: String,
     pub protocol_selector_addr: String,
     pub usdc_address: String,
@@ -23,6 +48,19 @@ pub struct Config {
     pub aave_fork_address: String,
     pub compound_fork_address: String,
     pub morpho_fork_address: String,
+    pub rebalancer_delegation_addr: Option<String>,
+    pub rebalancer_enabled: bool,
+    pub rebalancer_dry_run: bool,
+    pub rebalancer_min_net_gain: f64,
+    pub rebalancer_gain_horizon_days: u64,
+    pub rebalancer_gas_limit: u64,
+    pub rebalancer_native_price: f64,
+    pub indexer_start_block: u64,
+    pub indexer_batch_size: u64,
+    pub indexer_confirmations: u64,
+    pub indexer_reorg_depth: u64,
+    pub multicall_address: String,
+    pub multicall_batch_size: usize,
     pub run_seeds: bool,
 }
 
@@ -45,18 +83,138 @@ impl Config {
 
         let cors_origin = env::var("CORS_ORIGIN").unwrap_or_else(|_| "*".to_string());
 
-        let api_key = env::var("API_KEY").unwrap_or_else(|_| "dev-api-key".to_string());
+        let jwt_keys_dir = env::var("JWT_KEYS_DIR").ok().filter(|dir| !dir.is_empty());
 
-        let jwt_secret = env::var("JWT_SECRET").unwrap_or_else(|_| "dev-jwt-secret".to_string());
+        let jwt_active_kid = env::var("JWT_ACTIVE_KID").ok().filter(|kid| !kid.is_empty());
+
+        let jwt_allow_ephemeral_key = env::var("JWT_ALLOW_EPHEMERAL_KEY")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false);
+
+        let admin_addresses = env::var("ADMIN_ADDRESSES")
+            .unwrap_or_default()
+            .split(',')
+            .map(|address| address.trim().to_lowercase())
+            .filter(|address| !address.is_empty())
+            .collect::<Vec<_>>();
+
+        let siwe_domain = env::var("SIWE_DOMAIN").unwrap_or_else(|_| "localhost:3000".to_string());
+
+        let siwe_nonce_ttl_secs = env::var("SIWE_NONCE_TTL_SECS")
+            .unwrap_or_else(|_| "600".to_string())
+            .parse::<u64>()
+            .unwrap_or(600);
+
+        let access_token_ttl_secs = env::var("ACCESS_TOKEN_TTL_SECS")
+            .unwrap_or_else(|_| "900".to_string())
+            .parse::<u64>()
+            .unwrap_or(900);
+
+        let refresh_token_ttl_secs = env::var("REFRESH_TOKEN_TTL_SECS")
+            .unwrap_or_else(|_| "2592000".to_string())
+            .parse::<u64>()
+            .unwrap_or(2592000);
+
+        let resolution_challenge_window_secs = env::var("RESOLUTION_CHALLENGE_WINDOW_SECS")
+            .unwrap_or_else(|_| "86400".to_string())
+            .parse::<u64>()
+            .unwrap_or(86400);
+
+        let bet_quote_ttl_secs = env::var("BET_QUOTE_TTL_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse::<u64>()
+            .unwrap_or(30);
 
         let base_rpc_url =
-            env::var("HEDERA_RPC_URL").unwrap_or_else(|_| "https://hashscan.io/testnet".to_string());
+            env::var("HEDERA_RPC_URL").unwrap_or_else(|_| "https://testnet.hashio.io/api".to_string());
+
+        let ws_rpc_url = env::var("HEDERA_WS_RPC_URL").ok().filter(|url| !url.is_empty());
+
+        let rpc_fallback_urls = env::var("HEDERA_RPC_FALLBACK_URLS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .collect();
+
+        let rpc_max_retries = env::var("RPC_MAX_RETRIES")
+            .unwrap_or_else(|_| "2".to_string())
+            .parse::<u32>()
+            .unwrap_or(2);
+
+        let rpc_retry_backoff_ms = env::var("RPC_RETRY_BACKOFF_MS")
+            .unwrap_or_else(|_| "250".to_string())
+            .parse::<u64>()
+            .unwrap_or(250);
+
+        let rpc_rate_limit_per_sec = env::var("RPC_RATE_LIMIT_PER_SEC")
+            .unwrap_or_else(|_| "20".to_string())
+            .parse::<u32>()
+            .unwrap_or(20);
+
+        let rpc_timeout_secs = env::var("RPC_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "15".to_string())
+            .parse::<u64>()
+            .unwrap_or(15);
+
+        let rpc_failure_threshold = env::var("RPC_FAILURE_THRESHOLD")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse::<u32>()
+            .unwrap_or(3);
+
+        let rpc_cooldown_secs = env::var("RPC_COOLDOWN_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse::<u64>()
+            .unwrap_or(30);
 
         let base_chain_id = env::var("HEDERA_CHAIN_ID")
             .unwrap_or_else(|_| "296".to_string())
             .parse::<u64>()
             .unwrap_or(296);
 
+        let explorer_url = env::var("HEDERA_EXPLORER")
+            .unwrap_or_else(|_| "https://hashscan.io/testnet".to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        let private_key = env::var("PRIVATE_KEY").ok().filter(|key| !key.is_empty());
+
+        let tx_stuck_after_secs = env::var("TX_STUCK_AFTER_SECS")
+            .unwrap_or_else(|_| "180".to_string())
+            .parse::<u64>()
+            .unwrap_or(180);
+
+        let tx_gas_bump_percent = env::var("TX_GAS_BUMP_PERCENT")
+            .unwrap_or_else(|_| "20".to_string())
+            .parse::<u64>()
+            .unwrap_or(20);
+
+        let tx_max_gas_bumps = env::var("TX_MAX_GAS_BUMPS")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse::<u64>()
+            .unwrap_or(3);
+
+        let outbox_max_attempts = env::var("OUTBOX_MAX_ATTEMPTS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse::<i32>()
+            .unwrap_or(5);
+
+        let outbox_retry_backoff_secs = env::var("OUTBOX_RETRY_BACKOFF_SECS")
+            .unwrap_or_else(|_| "15".to_string())
+            .parse::<u64>()
+            .unwrap_or(15);
+
+        let event_max_attempts = env::var("EVENT_MAX_ATTEMPTS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse::<i32>()
+            .unwrap_or(5);
+
+        let event_retry_backoff_secs = env::var("EVENT_RETRY_BACKOFF_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse::<u64>()
+            .unwrap_or(30);
+
         let whizy_prediction_market_addr = env::var("WHIZY_PREDICTION_MARKET_ADDR")
             .unwrap_or_else(|_| "0x2695CB6da12c6e3C34afd05982607CFd22d40415".to_string());
 
@@ -87,6 +245,71 @@ impl Config {
         let morpho_fork_address = env::var("MORPHO_FORK_ADDRESS")
             .unwrap_or_else(|_| "0x2D35B90e7E1e03a4D6ED369AeeB3D2BcF3DFb312".to_string());
 
+        let rebalancer_delegation_addr = env::var("REBALANCER_DELEGATION_ADDR")
+            .ok()
+            .filter(|address| !address.is_empty());
+
+        let rebalancer_enabled = env::var("REBALANCER_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false);
+
+        let rebalancer_dry_run = env::var("REBALANCER_DRY_RUN")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse()
+            .unwrap_or(true);
+
+        let rebalancer_min_net_gain = env::var("REBALANCER_MIN_NET_GAIN")
+            .unwrap_or_else(|_| "1".to_string())
+            .parse::<f64>()
+            .unwrap_or(1.0);
+
+        let rebalancer_gain_horizon_days = env::var("REBALANCER_GAIN_HORIZON_DAYS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse::<u64>()
+            .unwrap_or(30)
+            .max(1);
+
+        let rebalancer_gas_limit = env::var("REBALANCER_GAS_LIMIT")
+            .unwrap_or_else(|_| "500000".to_string())
+            .parse::<u64>()
+            .unwrap_or(500_000);
+
+        let rebalancer_native_price = env::var("REBALANCER_NATIVE_PRICE")
+            .unwrap_or_else(|_| "0.05".to_string())
+            .parse::<f64>()
+            .unwrap_or(0.05);
+
+        let indexer_start_block = env::var("INDEXER_START_BLOCK")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse::<u64>()
+            .unwrap_or(0);
+
+        let indexer_batch_size = env::var("INDEXER_BATCH_SIZE")
+            .unwrap_or_else(|_| "2000".to_string())
+            .parse::<u64>()
+            .unwrap_or(2000)
+            .max(1);
+
+        let indexer_confirmations = env::var("INDEXER_CONFIRMATIONS")
+            .unwrap_or_else(|_| "1".to_string())
+            .parse::<u64>()
+            .unwrap_or(1);
+
+        let indexer_reorg_depth = env::var("INDEXER_REORG_DEPTH")
+            .unwrap_or_else(|_| "64".to_string())
+            .parse::<u64>()
+            .unwrap_or(64);
+
+        let multicall_address = env::var("MULTICALL_ADDRESS")
+            .unwrap_or_else(|_| "0xcA11bde05977b3631167028862bE2a173976CA11".to_string());
+
+        let multicall_batch_size = env::var("MULTICALL_BATCH_SIZE")
+            .unwrap_or_else(|_| "100".to_string())
+            .parse::<usize>()
+            .unwrap_or(100)
+            .max(1);
+
         let run_seeds = env::var("RUN_SEEDS")
             .unwrap_or_else(|_| "false".to_string())
             .parse()
@@ -99,10 +322,35 @@ impl Config {
             port,
             log_level,
             cors_origin,
-            api_key,
-            jwt_secret,
+            jwt_keys_dir,
+            jwt_active_kid,
+            jwt_allow_ephemeral_key,
+            admin_addresses,
+            siwe_domain,
+            siwe_nonce_ttl_secs,
+            access_token_ttl_secs,
+            refresh_token_ttl_secs,
+            resolution_challenge_window_secs,
+            bet_quote_ttl_secs,
             base_rpc_url,
+            ws_rpc_url,
+            rpc_fallback_urls,
+            rpc_max_retries,
+            rpc_retry_backoff_ms,
+            rpc_rate_limit_per_sec,
+            rpc_timeout_secs,
+            rpc_failure_threshold,
+            rpc_cooldown_secs,
             base_chain_id,
+            explorer_url,
+            private_key,
+            tx_stuck_after_secs,
+            tx_gas_bump_percent,
+            tx_max_gas_bumps,
+            outbox_max_attempts,
+            outbox_retry_backoff_secs,
+            event_max_attempts,
+            event_retry_backoff_secs,
             whizy_prediction_market_addr,
             protocol_selector_addr,
             usdc_address,
@@ -113,7 +361,28 @@ impl Config {
             aave_fork_address,
             compound_fork_address,
             morpho_fork_address,
+            rebalancer_delegation_addr,
+            rebalancer_enabled,
+            rebalancer_dry_run,
+            rebalancer_min_net_gain,
+            rebalancer_gain_horizon_days,
+            rebalancer_gas_limit,
+            rebalancer_native_price,
+            indexer_start_block,
+            indexer_batch_size,
+            indexer_confirmations,
+            indexer_reorg_depth,
+            multicall_address,
+            multicall_batch_size,
             run_seeds,
         })
     }
+
+    pub fn explorer_transaction_url(&self, tx_hash: &str) -> String {
+        format!("{}/transaction/{}", self.explorer_url, tx_hash)
+    }
+
+    pub fn explorer_contract_url(&self, address: &str) -> String {
+        format!("{}/contract/{}", self.explorer_url, address)
+    }
 }
@@ -5,7 +5,9 @@ use tower_http::cors::{Any, CorsLayer};
 use tracing::{error, info};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use whizy_base_server::{config::Config, db::Database, routes, seed, services::*};
+use whizy_base_server::{
+    config::Config, db::Database, routes, seed, services::*, utils::JwtService,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -32,6 +34,13 @@ async fn main() -> Result<()> {
     );
     info!("   ProtocolSelector: {}", config.protocol_selector_addr);
 
+    JwtService::init(
+        config.jwt_keys_dir.as_deref(),
+        config.jwt_active_kid.as_deref(),
+        config.jwt_allow_ephemeral_key,
+    )?;
+    SignerService::init(&config)?;
+
     info!("📦 Connecting to database...");
     let db = Database::new(&config.database_url).await?;
     info!("✅ Connected to database");
@@ -45,21 +54,12 @@ async fn main() -> Result<()> {
         seed::run_all_seeds(db.pool()).await?;
         info!("✅ Seeds completed");
 
-        if let Ok(private_key) = std::env::var("PRIVATE_KEY") {
+        if SignerService::shared().is_ok() {
             info!("🔗 Auto-syncing markets to blockchain...");
             let blockchain_sync = BlockchainSyncService::new(db.clone());
 
-            match blockchain_sync
-                .sync_markets_to_blockchain(
-                    &config.whizy_prediction_market_addr,
-                    &config.base_rpc_url,
-                    &private_key,
-                    &config.usdc_address,
-                    config.base_chain_id,
-                )
-                .await
-            {
-                Ok(count) => info!("✅ Successfully synced {} markets to blockchain", count),
+            match blockchain_sync.sync_markets_to_blockchain(&config).await {
+                Ok(count) => info!("✅ Linked or queued {} markets for blockchain sync", count),
                 Err(e) => error!(
                     "⚠️  Failed to sync markets to blockchain: {}. Continuing...",
                     e
@@ -81,6 +81,7 @@ async fn main() -> Result<()> {
 
     let app = Router::new()
         .nest("/api", routes::create_routes(db.clone(), config.clone()))
+        .nest("/.well-known", routes::create_well_known_router())
         .layer(cors)
         .layer(tower_http::trace::TraceLayer::new_for_http());
 
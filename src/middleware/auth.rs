@@ -1,17 +1,60 @@
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     http::StatusCode,
     middleware::Next,
     response::{IntoResponse, Response},
 };
 
-pub async fn require_api_key(req: Request, next: Next) -> Result<Response, Response> {
-    let api_key_header = req.headers().get("X-API-Key").and_then(|h| h.to_str().ok());
+use crate::{config::Config, db::Database, middleware::jwt::require_jwt, services::ApiKeyService};
 
-    let expected_key = std::env::var("API_KEY").unwrap_or_else(|_| "dev-api-key".to_string());
+const API_KEY_HEADER: &str = "X-API-Key";
 
-    match api_key_header {
-        Some(key) if key == expected_key => Ok(next.run(req).await),
-        _ => Err((StatusCode::UNAUTHORIZED, "Invalid or missing API key").into_response()),
+pub async fn require_api_key(
+    State((db, _)): State<(Database, Config)>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, Response> {
+    let api_key_header = req
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .map(|h| h.to_string());
+
+    let Some(raw_key) = api_key_header else {
+        return Err((StatusCode::UNAUTHORIZED, "Invalid or missing API key").into_response());
+    };
+
+    let api_key_service = ApiKeyService::new(db);
+
+    match api_key_service.authenticate(&raw_key).await {
+        Ok(api_key) => {
+            req.extensions_mut().insert(api_key);
+            Ok(next.run(req).await)
+        }
+        Err(e) => Err(e.into_response()),
+    }
+}
+
+pub async fn optional_api_key(
+    State(state): State<(Database, Config)>,
+    req: Request,
+    next: Next,
+) -> Result<Response, Response> {
+    if !req.headers().contains_key(API_KEY_HEADER) {
+        return Ok(next.run(req).await);
+    }
+
+    require_api_key(State(state), req, next).await
+}
+
+pub async fn require_jwt_or_api_key(
+    State(state): State<(Database, Config)>,
+    req: Request,
+    next: Next,
+) -> Result<Response, Response> {
+    if req.headers().contains_key(API_KEY_HEADER) {
+        require_api_key(State(state), req, next).await
+    } else {
+        require_jwt(State(state), req, next).await
     }
 }
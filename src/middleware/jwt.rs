@@ -1,13 +1,17 @@
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     http::StatusCode,
     middleware::Next,
     response::{IntoResponse, Response},
 };
 
-use crate::utils::JwtService;
+use crate::{config::Config, db::Database, services::SessionService, utils::JwtService};
 
-pub async fn require_jwt(mut req: Request, next: Next) -> Result<Response, Response> {
+pub async fn require_jwt(
+    State((db, _)): State<(Database, Config)>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, Response> {
     let auth_header = req
         .headers()
         .get("Authorization")
@@ -24,13 +28,30 @@ pub async fn require_jwt(mut req: Request, next: Next) -> Result<Response, Respo
         }
     };
 
-    let jwt_service = JwtService::new();
+    let jwt_service = JwtService::new().map_err(|e| e.into_response())?;
 
-    match jwt_service.verify_token(token) {
-        Ok(claims) => {
+    let claims = match jwt_service.verify_token(token) {
+        Ok(claims) => claims,
+        Err(_) => {
+            return Err((StatusCode::UNAUTHORIZED, "Invalid or expired token").into_response())
+        }
+    };
+
+    let session_service = SessionService::new(db);
+
+    match session_service
+        .touch_session(&claims.jti, &claims.sub)
+        .await
+    {
+        Ok(true) => {
             req.extensions_mut().insert(claims);
             Ok(next.run(req).await)
         }
-        Err(_) => Err((StatusCode::UNAUTHORIZED, "Invalid or expired token").into_response()),
+        Ok(false) => Err((
+            StatusCode::UNAUTHORIZED,
+            "Session has been revoked or expired",
+        )
+            .into_response()),
+        Err(e) => Err(e.into_response()),
     }
 }
@@ -1,5 +1,7 @@
 pub mod auth;
 pub mod jwt;
+pub mod permission;
 
-pub use auth::require_api_key;
+pub use auth::{optional_api_key, require_api_key, require_jwt_or_api_key};
 pub use jwt::require_jwt;
+pub use permission::{require_permission, restrict_api_key};
@@ -0,0 +1,60 @@
+use axum::{
+    extract::Request,
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    services::ApiKeyContext,
+    utils::{
+        rbac::{roles_grant, scopes_grant},
+        Claims, Permission,
+    },
+};
+
+pub async fn require_permission(
+    permission: Permission,
+    req: Request,
+    next: Next,
+) -> Result<Response, Response> {
+    let granted_by_role = req
+        .extensions()
+        .get::<Claims>()
+        .map(|claims| roles_grant(&claims.roles, permission))
+        .unwrap_or(false);
+
+    let granted_by_scope = req
+        .extensions()
+        .get::<ApiKeyContext>()
+        .map(|api_key| scopes_grant(&api_key.scopes, permission))
+        .unwrap_or(false);
+
+    if !granted_by_role && !granted_by_scope {
+        return Err(missing_permission(permission));
+    }
+
+    Ok(next.run(req).await)
+}
+
+pub async fn restrict_api_key(
+    permission: Permission,
+    req: Request,
+    next: Next,
+) -> Result<Response, Response> {
+    if let Some(api_key) = req.extensions().get::<ApiKeyContext>() {
+        if !scopes_grant(&api_key.scopes, permission) {
+            return Err(missing_permission(permission));
+        }
+    }
+
+    Ok(next.run(req).await)
+}
+
+fn missing_permission(permission: Permission) -> Response {
+    (
+        StatusCode::FORBIDDEN,
+        format!("Missing required permission: {}", permission.as_str()),
+    )
+        .into_response()
+}
@@ -5,15 +5,37 @@ use std::str::FromStr;
 use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
 #[derive(Default)]
 pub enum MarketStatus {
+    Draft,
+    Scheduled,
     Active,
+    Closed,
+    PendingResolution,
     Resolved,
+    Cancelled,
     #[default]
     All,
 }
 
+impl MarketStatus {
+    pub fn state(&self) -> Option<crate::utils::MarketState> {
+        use crate::utils::MarketState;
+
+        match self {
+            MarketStatus::Draft => Some(MarketState::Draft),
+            MarketStatus::Scheduled => Some(MarketState::Scheduled),
+            MarketStatus::Active => Some(MarketState::Active),
+            MarketStatus::Closed => Some(MarketState::Closed),
+            MarketStatus::PendingResolution => Some(MarketState::PendingResolution),
+            MarketStatus::Resolved => Some(MarketState::Resolved),
+            MarketStatus::Cancelled => Some(MarketState::Cancelled),
+            MarketStatus::All => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[derive(Default)]
@@ -181,7 +203,7 @@ impl MarketExtended {
         let now = chrono::Utc::now().naive_utc();
         let end_date = self.end_date;
 
-        if end_date > now && self.total_pool_size > BigDecimal::from(0) {
+        if end_date > now && self.total_pool_size > 0 {
             let duration = end_date.signed_duration_since(now);
             let days_remaining = duration.num_days().max(0) as f64;
 
@@ -401,8 +423,11 @@ pub struct PaginationMeta {
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct WalletConnectRequest {
-    pub address: String,
+    pub message: String,
+    pub signature: String,
+    pub device_label: Option<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -412,9 +437,401 @@ pub struct WalletConnectResponse {
 }
 
 #[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct WalletConnectData {
     pub user: User,
     pub token: String,
+    pub refresh_token: String,
+    pub expires_in: u64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub id: String,
+    #[sqlx(rename = "deviceLabel")]
+    pub device_label: Option<String>,
+    pub revoked: bool,
+    #[sqlx(rename = "revokedAt")]
+    pub revoked_at: Option<NaiveDateTime>,
+    #[sqlx(rename = "expiresAt")]
+    pub expires_at: NaiveDateTime,
+    #[sqlx(rename = "lastUsedAt")]
+    pub last_used_at: NaiveDateTime,
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKey {
+    pub id: String,
+    pub name: String,
+    #[sqlx(rename = "keyPrefix")]
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    #[sqlx(rename = "expiresAt")]
+    pub expires_at: Option<NaiveDateTime>,
+    #[sqlx(rename = "lastUsedAt")]
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked: bool,
+    #[sqlx(rename = "revokedAt")]
+    pub revoked_at: Option<NaiveDateTime>,
+    #[sqlx(rename = "createdBy")]
+    pub created_by: Option<String>,
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BetIntent {
+    pub id: String,
+    #[sqlx(rename = "userAddress")]
+    pub user_address: String,
+    #[sqlx(rename = "marketId")]
+    pub market_id: String,
+    #[sqlx(rename = "blockchainMarketId")]
+    pub blockchain_market_id: i64,
+    pub position: bool,
+    pub amount: BigDecimal,
+    pub status: String,
+    #[sqlx(rename = "txHash")]
+    pub tx_hash: Option<String>,
+    #[sqlx(rename = "betId")]
+    pub bet_id: Option<String>,
+    pub error: Option<String>,
+    #[sqlx(rename = "expiresAt")]
+    pub expires_at: NaiveDateTime,
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+    #[sqlx(rename = "updatedAt")]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainTransaction {
+    pub hash: String,
+    pub kind: String,
+    #[sqlx(rename = "fromAddress")]
+    pub from_address: String,
+    #[sqlx(rename = "toAddress")]
+    pub to_address: Option<String>,
+    #[sqlx(rename = "marketId")]
+    pub market_id: Option<String>,
+    #[sqlx(rename = "betId")]
+    pub bet_id: Option<String>,
+    pub nonce: Option<i64>,
+    #[sqlx(rename = "gasLimit")]
+    pub gas_limit: Option<BigDecimal>,
+    #[sqlx(rename = "gasPrice")]
+    pub gas_price: Option<BigDecimal>,
+    #[sqlx(rename = "gasUsed")]
+    pub gas_used: Option<BigDecimal>,
+    pub status: String,
+    pub confirmations: i64,
+    #[sqlx(rename = "blockNumber")]
+    pub block_number: Option<i64>,
+    #[sqlx(rename = "blockHash")]
+    pub block_hash: Option<String>,
+    pub error: Option<String>,
+    #[sqlx(rename = "replacedBy")]
+    pub replaced_by: Option<String>,
+    #[sqlx(rename = "confirmedAt")]
+    pub confirmed_at: Option<NaiveDateTime>,
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+    #[sqlx(rename = "updatedAt")]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxEntry {
+    pub id: String,
+    pub kind: String,
+    #[sqlx(rename = "marketId")]
+    pub market_id: Option<String>,
+    #[sqlx(rename = "betId")]
+    pub bet_id: Option<String>,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    #[sqlx(rename = "maxAttempts")]
+    pub max_attempts: i32,
+    #[sqlx(rename = "nextAttemptAt")]
+    pub next_attempt_at: NaiveDateTime,
+    #[sqlx(rename = "txHash")]
+    pub tx_hash: Option<String>,
+    #[sqlx(rename = "lastError")]
+    pub last_error: Option<String>,
+    #[sqlx(rename = "completedAt")]
+    pub completed_at: Option<NaiveDateTime>,
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+    #[sqlx(rename = "updatedAt")]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainReorg {
+    pub id: String,
+    #[sqlx(rename = "contractAddress")]
+    pub contract_address: String,
+    #[sqlx(rename = "contractName")]
+    pub contract_name: String,
+    #[sqlx(rename = "detectedAtBlock")]
+    pub detected_at_block: i64,
+    #[sqlx(rename = "forkBlock")]
+    pub fork_block: i64,
+    pub depth: i64,
+    #[sqlx(rename = "expectedHash")]
+    pub expected_hash: String,
+    #[sqlx(rename = "canonicalHash")]
+    pub canonical_hash: Option<String>,
+    #[sqlx(rename = "eventsRolledBack")]
+    pub events_rolled_back: i64,
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationRun {
+    pub id: String,
+    pub status: String,
+    #[sqlx(rename = "marketsChecked")]
+    pub markets_checked: i32,
+    #[sqlx(rename = "marketsDrifted")]
+    pub markets_drifted: i32,
+    #[sqlx(rename = "marketsUnreadable")]
+    pub markets_unreadable: i32,
+    #[sqlx(rename = "fieldsHealed")]
+    pub fields_healed: i32,
+    pub error: Option<String>,
+    #[sqlx(rename = "startedAt")]
+    pub started_at: NaiveDateTime,
+    #[sqlx(rename = "completedAt")]
+    pub completed_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationDrift {
+    pub id: i64,
+    #[sqlx(rename = "marketId")]
+    pub market_id: String,
+    #[sqlx(rename = "blockchainMarketId")]
+    pub blockchain_market_id: i64,
+    pub field: String,
+    #[sqlx(rename = "databaseValue")]
+    pub database_value: Option<String>,
+    #[sqlx(rename = "chainValue")]
+    pub chain_value: Option<String>,
+    pub healed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockchainEvent {
+    pub id: String,
+    #[sqlx(rename = "eventType")]
+    pub event_type: String,
+    #[sqlx(rename = "blockchainId")]
+    pub blockchain_id: String,
+    #[sqlx(rename = "blockNumber")]
+    pub block_number: i64,
+    #[sqlx(rename = "transactionHash")]
+    pub transaction_hash: String,
+    pub status: String,
+    pub attempts: i32,
+    #[sqlx(rename = "lastError")]
+    pub last_error: Option<String>,
+    #[sqlx(rename = "nextRetryAt")]
+    pub next_retry_at: NaiveDateTime,
+    #[sqlx(rename = "processedAt")]
+    pub processed_at: Option<NaiveDateTime>,
+    pub data: Option<String>,
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+    #[sqlx(rename = "updatedAt")]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoRebalanceStatus {
+    pub user: String,
+    pub is_enabled: bool,
+    pub risk_profile: Option<i32>,
+    pub enabled_at: Option<NaiveDateTime>,
+    pub disabled_at: Option<NaiveDateTime>,
+    pub balance: BigDecimal,
+    pub rebalance_count: i64,
+    pub total_rebalanced: BigDecimal,
+    pub last_rebalanced_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceRecord {
+    pub id: String,
+    pub user: String,
+    pub operator: String,
+    pub amount: BigDecimal,
+    pub block_number: i64,
+    pub transaction_hash: String,
+    pub rebalanced_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskProfileUsers {
+    pub risk_profile: i32,
+    pub users: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoRebalanceStats {
+    pub enabled_users: i64,
+    pub disabled_users: i64,
+    #[sqlx(skip)]
+    pub users_by_risk_profile: Vec<RiskProfileUsers>,
+    pub total_deposited: BigDecimal,
+    pub total_rebalances: i64,
+    pub total_rebalanced: BigDecimal,
+    pub rebalances_24h: i64,
+    pub rebalanced_users: i64,
+    pub active_operators: i64,
+    pub last_rebalanced_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceDecision {
+    pub id: String,
+    pub user: String,
+    #[sqlx(rename = "riskProfile")]
+    pub risk_profile: i32,
+    #[sqlx(rename = "currentProtocol")]
+    pub current_protocol: Option<String>,
+    #[sqlx(rename = "currentApy")]
+    pub current_apy: Option<BigDecimal>,
+    #[sqlx(rename = "targetProtocol")]
+    pub target_protocol: Option<String>,
+    #[sqlx(rename = "targetApy")]
+    pub target_apy: Option<BigDecimal>,
+    pub balance: BigDecimal,
+    #[sqlx(rename = "expectedGain")]
+    pub expected_gain: BigDecimal,
+    #[sqlx(rename = "gasCost")]
+    pub gas_cost: BigDecimal,
+    #[sqlx(rename = "netGain")]
+    pub net_gain: BigDecimal,
+    pub action: String,
+    pub reason: String,
+    #[sqlx(rename = "outboxId")]
+    pub outbox_id: Option<String>,
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionProposal {
+    pub id: String,
+    #[sqlx(rename = "marketId")]
+    pub market_id: String,
+    pub outcome: bool,
+    pub evidence: Vec<String>,
+    #[sqlx(rename = "proposedBy")]
+    pub proposed_by: String,
+    pub status: String,
+    #[sqlx(rename = "challengeEndsAt")]
+    pub challenge_ends_at: NaiveDateTime,
+    #[sqlx(rename = "finalOutcome")]
+    pub final_outcome: Option<bool>,
+    #[sqlx(rename = "decidedBy")]
+    pub decided_by: Option<String>,
+    #[sqlx(rename = "decisionReason")]
+    pub decision_reason: Option<String>,
+    #[sqlx(rename = "decidedAt")]
+    pub decided_at: Option<NaiveDateTime>,
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionDispute {
+    pub id: String,
+    #[sqlx(rename = "proposalId")]
+    pub proposal_id: String,
+    #[sqlx(rename = "disputedBy")]
+    pub disputed_by: String,
+    pub reason: String,
+    pub evidence: Vec<String>,
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionAuditEntry {
+    pub id: i64,
+    #[sqlx(rename = "proposalId")]
+    pub proposal_id: Option<String>,
+    pub action: String,
+    pub actor: String,
+    pub outcome: Option<bool>,
+    pub note: Option<String>,
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposeResolutionRequest {
+    pub outcome: bool,
+    pub evidence: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DisputeResolutionRequest {
+    pub reason: String,
+    #[serde(default)]
+    pub evidence: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalizeResolutionRequest {
+    pub outcome: Option<bool>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectResolutionRequest {
+    pub reason: String,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -1,8 +1,8 @@
 use axum::{
-    extract::State,
+    extract::{Path, State},
     middleware,
     response::Json,
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
     Extension, Router,
 };
 use serde_json::json;
@@ -12,46 +12,105 @@ use crate::{
     error::AppError,
     middleware::jwt::require_jwt,
     models::{
-        UpdateProfileData, UpdateProfileRequest, UpdateProfileResponse, WalletConnectData,
-        WalletConnectRequest, WalletConnectResponse,
+        RefreshTokenRequest, UpdateProfileData, UpdateProfileRequest, UpdateProfileResponse,
+        WalletConnectData, WalletConnectRequest, WalletConnectResponse,
+    },
+    services::{AuthService, SessionService, UserService},
+    utils::{
+        jwt::{Claims, JwtService},
+        Role, SiweMessage,
     },
-    services::UserService,
-    utils::jwt::{Claims, JwtService},
 };
 
-pub fn create_auth_router() -> Router<(Database, crate::config::Config)> {
-    let public_routes = Router::new().route("/wallet", post(connect_wallet));
+pub fn create_auth_router(
+    state: (Database, crate::config::Config),
+) -> Router<(Database, crate::config::Config)> {
+    let public_routes = Router::new()
+        .route("/nonce", get(get_nonce))
+        .route("/wallet", post(connect_wallet))
+        .route("/refresh", post(refresh_token));
 
     let protected_routes = Router::new()
         .route("/me", get(get_current_user))
         .route("/profile", put(update_profile))
-        .route("/refresh", post(refresh_token))
         .route("/logout", post(logout))
-        .route_layer(middleware::from_fn(require_jwt));
+        .route("/sessions", get(list_sessions))
+        .route("/sessions/:id", delete(revoke_session))
+        .route_layer(middleware::from_fn_with_state(state, require_jwt));
 
     public_routes.merge(protected_routes)
 }
 
+async fn get_nonce(
+    State((db, config)): State<(Database, crate::config::Config)>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let auth_service = AuthService::new(db);
+    let issued = auth_service.issue_nonce(config.siwe_nonce_ttl_secs).await?;
+
+    Ok(Json(json!({
+        "data": {
+            "nonce": issued.nonce,
+            "expiresAt": issued.expires_at,
+            "domain": config.siwe_domain,
+            "chainId": config.base_chain_id
+        }
+    })))
+}
+
 async fn connect_wallet(
-    State((db, _)): State<(Database, crate::config::Config)>,
+    State((db, config)): State<(Database, crate::config::Config)>,
     Json(payload): Json<WalletConnectRequest>,
 ) -> Result<Json<WalletConnectResponse>, AppError> {
+    let auth_service = AuthService::new(db.clone());
     let user_service = UserService::new(db.clone());
-    let jwt_service = JwtService::new();
+    let session_service = SessionService::new(db.clone());
+    let jwt_service = JwtService::new()?;
+
+    let message: SiweMessage = payload.message.parse()?;
+    message.validate(
+        &config.siwe_domain,
+        config.base_chain_id,
+        chrono::Utc::now(),
+    )?;
+    message.verify_signature(&payload.message, &payload.signature)?;
+    auth_service.consume_nonce(&message.nonce).await?;
+
+    let address = ethers::utils::to_checksum(&message.address, None);
+    let user = user_service.upsert_user(&address).await?;
 
-    if payload.address.is_empty() {
-        return Err(AppError::BadRequest("Address cannot be empty".to_string()));
+    if config.admin_addresses.contains(&address.to_lowercase()) {
+        user_service
+            .grant_role(&user.id, Role::Admin, Some("ADMIN_ADDRESSES"))
+            .await?;
     }
+    let roles = user_service.get_user_roles(&user.id).await?;
 
-    let user = user_service.upsert_user(&payload.address).await?;
+    let session = session_service
+        .create_session(
+            &user.id,
+            payload.device_label,
+            config.refresh_token_ttl_secs,
+        )
+        .await?;
 
     let token = jwt_service
-        .generate_token(user.id.clone(), user.address.clone())
+        .generate_token(
+            user.id.clone(),
+            user.address.clone(),
+            session.session_id,
+            roles,
+            config.access_token_ttl_secs,
+        )
         .map_err(|e| AppError::Internal(format!("Failed to generate token: {}", e)))?;
 
     Ok(Json(WalletConnectResponse {
         message: "Successfully connected wallet".to_string(),
-        data: WalletConnectData { user, token },
+        data: WalletConnectData {
+            user,
+            token,
+            refresh_token: session.refresh_token,
+            expires_in: config.access_token_ttl_secs,
+        },
     }))
 }
 
@@ -123,6 +182,7 @@ async fn get_current_user(
             "address": user.address,
             "username": user.username,
             "avatarUrl": user.avatar_url,
+            "roles": claims.roles,
             "createdAt": user.created_at,
             "updatedAt": user.updated_at,
             "bets": bets_json,
@@ -149,28 +209,91 @@ async fn update_profile(
 }
 
 async fn refresh_token(
-    State((db, _)): State<(Database, crate::config::Config)>,
-    Extension(claims): Extension<Claims>,
+    State((db, config)): State<(Database, crate::config::Config)>,
+    Json(payload): Json<RefreshTokenRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    let session_service = SessionService::new(db.clone());
     let user_service = UserService::new(db);
-    let jwt_service = JwtService::new();
+    let jwt_service = JwtService::new()?;
 
-    let user = user_service.get_user_by_id(&claims.sub).await?;
+    let session = session_service
+        .rotate_refresh_token(&payload.refresh_token, config.refresh_token_ttl_secs)
+        .await?;
+    let roles = user_service.get_user_roles(&session.user_id).await?;
 
     let token = jwt_service
-        .generate_token(user.id.clone(), user.address.clone())
+        .generate_token(
+            session.user_id,
+            session.address,
+            session.session_id,
+            roles,
+            config.access_token_ttl_secs,
+        )
         .map_err(|e| AppError::Internal(format!("Failed to generate token: {}", e)))?;
 
     Ok(Json(json!({
         "message": "Token refreshed successfully",
         "data": {
-            "token": token
+            "token": token,
+            "refreshToken": session.refresh_token,
+            "expiresIn": config.access_token_ttl_secs
         }
     })))
 }
 
-async fn logout() -> Result<Json<serde_json::Value>, AppError> {
+async fn logout(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let session_service = SessionService::new(db);
+
+    session_service
+        .revoke_session(&claims.sub, &claims.jti, "logout")
+        .await?;
+
     Ok(Json(json!({
         "message": "Successfully logged out"
     })))
 }
+
+async fn list_sessions(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let session_service = SessionService::new(db);
+
+    let sessions = session_service.list_sessions(&claims.sub).await?;
+
+    let sessions_json: Vec<serde_json::Value> = sessions
+        .into_iter()
+        .map(|session| {
+            let current = session.id == claims.jti;
+            let mut value = json!(session);
+            value["current"] = json!(current);
+            value
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "data": sessions_json
+    })))
+}
+
+async fn revoke_session(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    Path(session_id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let session_service = SessionService::new(db);
+
+    session_service
+        .revoke_session(&claims.sub, &session_id, "revoked_by_user")
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Session revoked successfully",
+        "data": {
+            "id": session_id
+        }
+    })))
+}
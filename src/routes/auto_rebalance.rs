@@ -0,0 +1,74 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{db::Database, error::AppError, services::AutoRebalanceService};
+
+pub fn create_auto_rebalance_router() -> Router<(Database, crate::config::Config)> {
+    Router::new()
+        .route("/stats", get(get_auto_rebalance_stats))
+        .route("/users/:address", get(get_user_status))
+        .route("/users/:address/history", get(get_user_history))
+}
+
+#[derive(Debug, Deserialize)]
+struct RebalanceHistoryParams {
+    #[serde(default = "default_history_limit")]
+    limit: i64,
+    #[serde(default)]
+    offset: i64,
+}
+
+fn default_history_limit() -> i64 {
+    50
+}
+
+async fn get_user_status(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Path(address): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let auto_rebalance_service = AutoRebalanceService::new(db);
+    let status = auto_rebalance_service.get_user_status(&address).await?;
+
+    Ok(Json(json!({
+        "data": status
+    })))
+}
+
+async fn get_user_history(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Path(address): Path<String>,
+    Query(params): Query<RebalanceHistoryParams>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let auto_rebalance_service = AutoRebalanceService::new(db);
+    let (records, total) = auto_rebalance_service
+        .get_user_history(&address, params.limit, params.offset)
+        .await?;
+    let has_more = params.offset + (records.len() as i64) < total;
+
+    Ok(Json(json!({
+        "data": records,
+        "meta": {
+            "total": total,
+            "limit": params.limit,
+            "offset": params.offset,
+            "hasMore": has_more
+        }
+    })))
+}
+
+async fn get_auto_rebalance_stats(
+    State((db, _)): State<(Database, crate::config::Config)>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let auto_rebalance_service = AutoRebalanceService::new(db);
+    let stats = auto_rebalance_service.get_stats().await?;
+
+    Ok(Json(json!({
+        "data": stats
+    })))
+}
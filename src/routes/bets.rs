@@ -1,21 +1,51 @@
 use axum::{
     extract::{Path, Query, State},
+    middleware,
     response::Json,
-    routing::get,
-    Router,
+    routing::{get, post},
+    Extension, Router,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::sync::Arc;
 
-use crate::{db::Database, error::AppError, models::*, services::BetService};
+use crate::{
+    db::Database,
+    error::AppError,
+    middleware::{require_jwt, require_jwt_or_api_key, require_permission},
+    models::*,
+    services::{BetService, BettingService},
+    utils::{jwt::Claims, Permission},
+};
+
+pub fn create_bets_router(
+    state: (Database, crate::config::Config),
+) -> Router<(Database, crate::config::Config)> {
+    let place_bet_routes = Router::new()
+        .route("/", post(place_bet))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::PlaceBets, req, next)
+        }))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_jwt_or_api_key,
+        ));
+
+    let wallet_bet_routes = Router::new()
+        .route("/prepare", post(prepare_bet))
+        .route("/submit", post(submit_bet))
+        .route("/intents/:id", get(get_bet_intent))
+        .route_layer(middleware::from_fn_with_state(state, require_jwt));
 
-pub fn create_bets_router() -> Router<(Database, crate::config::Config)> {
     Router::new()
-        .route("/", get(get_bets).post(place_bet))
+        .route("/", get(get_bets))
+        .route("/quote", post(quote_bet))
         .route("/stats/summary", get(get_bet_stats))
         .route("/:id", get(get_bet_by_id))
         .route("/user/:address", get(get_user_bets))
         .route("/market/:market_id", get(get_market_bets))
+        .merge(place_bet_routes)
+        .merge(wallet_bet_routes)
 }
 
 async fn get_bets(
@@ -92,6 +122,33 @@ pub struct PlaceBetRequest {
     pub position: bool,
     pub amount: String,
     pub user_address: String,
+    pub quote_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BetQuoteRequest {
+    pub market_identifier: String,
+    pub position: bool,
+    pub amount: String,
+    pub user_address: Option<String>,
+    pub slippage_bps: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrepareBetRequest {
+    pub market_identifier: String,
+    pub position: bool,
+    pub amount: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitBetRequest {
+    pub intent_id: String,
+    pub tx_hash: Option<String>,
+    pub signed_transaction: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -101,7 +158,7 @@ pub struct BetStatsQueryParams {
 }
 
 async fn place_bet(
-    State((db, _config)): State<(Database, crate::config::Config)>,
+    State((db, config)): State<(Database, crate::config::Config)>,
     Json(payload): Json<PlaceBetRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let betting_service = crate::services::BettingService::new(db);
@@ -111,13 +168,14 @@ async fn place_bet(
         user_address: payload.user_address,
         position: payload.position,
         amount: payload.amount,
+        quote_id: payload.quote_id,
     };
 
-    let result = betting_service.place_bet(params).await?;
+    let result = betting_service.place_bet(&config, params).await?;
 
     Ok(Json(json!({
         "success": true,
-        "message": "Bet placed successfully",
+        "message": "Bet queued; it becomes active once its transaction confirms",
         "data": {
             "betId": result.bet_id,
             "blockchainBetId": result.blockchain_bet_id,
@@ -125,13 +183,115 @@ async fn place_bet(
             "blockchainMarketId": result.blockchain_market_id,
             "position": result.position,
             "amount": result.amount,
-            "txHash": result.tx_hash,
             "userAddress": result.user_address,
-            "status": "active"
+            "status": "pending"
+        }
+    })))
+}
+
+async fn quote_bet(
+    State((db, config)): State<(Database, crate::config::Config)>,
+    Json(payload): Json<BetQuoteRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let betting_service = BettingService::new(db);
+    let quote = betting_service
+        .quote_bet(
+            &config,
+            &payload.market_identifier,
+            payload.position,
+            &payload.amount,
+            payload.user_address.as_deref(),
+            payload.slippage_bps,
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "data": {
+            "quoteId": quote.quote_id,
+            "marketId": quote.market_id,
+            "position": quote.position,
+            "amount": quote.amount.to_string(),
+            "odds": quote.quote.odds,
+            "minOdds": quote.min_odds,
+            "slippageBps": quote.slippage_bps,
+            "impliedProbability": quote.quote.implied_probability,
+            "shares": quote.quote.shares.to_string(),
+            "potentialPayout": quote.quote.potential_payout.to_string(),
+            "currentYield": quote.current_yield.to_string(),
+            "projectedYield": quote.projected_yield.to_string(),
+            "yieldSource": quote.yield_source,
+            "currentPosition": quote.current_position,
+            "expiresAt": quote.expires_at
+        }
+    })))
+}
+
+async fn prepare_bet(
+    State((db, config)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<PrepareBetRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let betting_service = BettingService::new(db);
+    let prepared = betting_service
+        .prepare_bet(
+            &config,
+            &claims.sub,
+            &claims.address,
+            &payload.market_identifier,
+            payload.position,
+            &payload.amount,
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Sign and send the transactions in order, then submit the placeBet transaction",
+        "data": {
+            "intent": prepared.intent,
+            "tokenAddress": prepared.token_address,
+            "allowance": prepared.allowance.to_string(),
+            "balance": prepared.balance.to_string(),
+            "transactions": prepared.transactions
         }
     })))
 }
 
+async fn submit_bet(
+    State((db, config)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<SubmitBetRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let betting_service = Arc::new(BettingService::new(db));
+    let intent = betting_service
+        .submit_bet(
+            &config,
+            &claims.address,
+            &payload.intent_id,
+            payload.tx_hash.as_deref(),
+            payload.signed_transaction.as_deref(),
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Bet transaction submitted",
+        "data": intent
+    })))
+}
+
+async fn get_bet_intent(
+    State((db, config)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let betting_service = BettingService::new(db);
+    let intent = betting_service
+        .refresh_bet_intent(&config, &claims.address, &id)
+        .await?;
+
+    Ok(Json(json!({
+        "data": intent
+    })))
+}
+
 async fn get_bet_stats(
     State((db, _)): State<(Database, crate::config::Config)>,
     Query(params): Query<BetStatsQueryParams>,
@@ -1,8 +1,10 @@
 use axum::{
     extract::{Path, Query, State},
+    http::HeaderMap,
+    middleware,
     response::Json,
-    routing::{get, post, put},
-    Router,
+    routing::{get, patch, post, put},
+    Extension, Router,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -10,19 +12,35 @@ use serde_json::json;
 use crate::{
     db::Database,
     error::AppError,
+    middleware::{optional_api_key, require_jwt, require_permission, restrict_api_key},
     models::*,
-    services::{MarketService, StatsService},
+    services::{MarketCreationService, MarketService, StatsService},
+    utils::{jwt::Claims, MarketState, Permission},
 };
 
-pub fn create_markets_router() -> Router<(Database, crate::config::Config)> {
+pub fn create_markets_router(
+    state: (Database, crate::config::Config),
+) -> Router<(Database, crate::config::Config)> {
+    let curator_routes = Router::new()
+        .route("/create-blockchain", post(create_blockchain_market))
+        .route("/:id/image", put(update_market_image))
+        .route("/:id/status", patch(update_market_status))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::ManageMarkets, req, next)
+        }))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_jwt));
+
     Router::new()
         .route("/", get(get_markets))
-        .route("/create-blockchain", post(create_blockchain_market))
         .route("/trending", get(get_trending_markets))
         .route("/:id", get(get_market_by_id))
         .route("/:id/stats", get(get_market_stats))
         .route("/:id/bets", get(get_market_bets))
-        .route("/:id/image", put(update_market_image))
+        .route_layer(middleware::from_fn(|req, next| {
+            restrict_api_key(Permission::ReadMarkets, req, next)
+        }))
+        .route_layer(middleware::from_fn_with_state(state, optional_api_key))
+        .merge(curator_routes)
 }
 
 async fn get_markets(
@@ -104,24 +122,49 @@ pub struct BlockchainMarketRequest {
 }
 
 async fn create_blockchain_market(
-    State((_db, config)): State<(Database, crate::config::Config)>,
+    State((db, config)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    headers: HeaderMap,
     Json(payload): Json<BlockchainMarketRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    let idempotency_key = headers
+        .get("idempotency-key")
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| AppError::BadRequest("Idempotency-Key header is required".to_string()))?;
+
+    let creation_service = MarketCreationService::new(db);
+    let created = creation_service
+        .create_market(
+            &config,
+            idempotency_key,
+            &payload.question,
+            &payload.description,
+            payload.duration,
+            payload.image_url.as_deref(),
+            &claims.sub,
+        )
+        .await?;
+
     Ok(Json(json!({
         "success": true,
-        "message": "Market created successfully",
+        "message": if created.replayed {
+            "Market already requested for this Idempotency-Key"
+        } else {
+            "Market queued for on-chain creation"
+        },
         "data": {
-            "databaseId": "temp-db-id",
-            "adjTicker": "TEMP",
-            "marketId": "temp-market-id",
-            "blockchainMarketId": 0,
+            "databaseId": created.id,
+            "adjTicker": created.adj_ticker,
+            "marketId": created.market_id,
+            "blockchainMarketId": created.blockchain_market_id,
             "question": payload.question,
             "description": payload.description,
             "duration": payload.duration,
-            "endTime": chrono::Utc::now().timestamp() + payload.duration,
+            "endTime": created.end_time,
+            "status": created.status,
             "blockchain": {
-                "txHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
-                "blockNumber": 0,
+                "txHash": created.tx_hash,
+                "blockNumber": created.block_number,
                 "chainId": config.base_chain_id,
                 "contracts": {
                     "whizyMarket": config.whizy_prediction_market_addr.clone(),
@@ -130,8 +173,11 @@ async fn create_blockchain_market(
                 }
             },
             "explorer": {
-                "transaction": format!("hashscan.io/testnet/transaction/0x0000000000000000000000000000000000000000000000000000000000000000"),
-                "market": format!("hashscan.io/testnet/account/{}", config.whizy_prediction_market_addr)
+                "transaction": created
+                    .tx_hash
+                    .as_deref()
+                    .map(|hash| config.explorer_transaction_url(hash)),
+                "market": config.explorer_contract_url(&config.whizy_prediction_market_addr)
             }
         }
     })))
@@ -182,3 +228,35 @@ async fn update_market_image(
         }
     })))
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMarketStatusRequest {
+    pub status: MarketState,
+    pub reason: Option<String>,
+    pub start_date: Option<chrono::NaiveDateTime>,
+}
+
+async fn update_market_status(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+    Json(payload): Json<UpdateMarketStatusRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let market_service = MarketService::new(db);
+
+    let market = market_service
+        .transition_market(
+            &id,
+            payload.status,
+            &claims.address,
+            payload.reason.as_deref(),
+            payload.start_date,
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "message": format!("Market moved to {}", payload.status),
+        "data": market
+    })))
+}
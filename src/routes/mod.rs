@@ -9,23 +9,31 @@ use std::sync::Arc;
 use crate::{config::Config, db::Database, error::AppError, models::*, services::*};
 
 mod auth;
+mod auto_rebalance;
 mod bets;
 mod blockchain;
 mod charts;
 mod markets;
 mod prices;
 mod protocols;
+mod resolutions;
 mod sync;
+mod transactions;
+mod well_known;
 mod yields;
 
 pub use auth::create_auth_router;
+pub use auto_rebalance::create_auto_rebalance_router;
 pub use bets::create_bets_router;
 pub use blockchain::create_blockchain_router;
 pub use charts::create_charts_router;
 pub use markets::create_markets_router;
 pub use prices::create_prices_router;
 pub use protocols::create_protocols_router;
+pub use resolutions::create_resolutions_router;
 pub use sync::create_sync_router;
+pub use transactions::create_transactions_router;
+pub use well_known::create_well_known_router;
 pub use yields::create_yields_router;
 
 #[derive(Clone)]
@@ -57,15 +65,19 @@ pub fn create_routes(db: Database, config: Config) -> Router {
         .route("/health", get(health_check))
         .nest(
             "/auth",
-            create_auth_router().with_state(shared_state.clone()),
+            create_auth_router(shared_state.clone()).with_state(shared_state.clone()),
         )
         .nest(
             "/markets",
-            create_markets_router().with_state(shared_state.clone()),
+            create_markets_router(shared_state.clone()).with_state(shared_state.clone()),
+        )
+        .nest(
+            "/resolutions",
+            create_resolutions_router(shared_state.clone()).with_state(shared_state.clone()),
         )
         .nest(
             "/bets",
-            create_bets_router().with_state(shared_state.clone()),
+            create_bets_router(shared_state.clone()).with_state(shared_state.clone()),
         )
         .nest(
             "/charts",
@@ -73,15 +85,15 @@ pub fn create_routes(db: Database, config: Config) -> Router {
         )
         .nest(
             "/protocols",
-            create_protocols_router().with_state(shared_state.clone()),
+            create_protocols_router(shared_state.clone()).with_state(shared_state.clone()),
         )
         .nest(
             "/sync",
-            create_sync_router().with_state(shared_state.clone()),
+            create_sync_router(shared_state.clone()).with_state(shared_state.clone()),
         )
         .nest(
             "/yields",
-            create_yields_router().with_state(shared_state.clone()),
+            create_yields_router(shared_state.clone()).with_state(shared_state.clone()),
         )
         .nest(
             "/prices",
@@ -91,9 +103,18 @@ pub fn create_routes(db: Database, config: Config) -> Router {
             "/blockchain",
             create_blockchain_router().with_state(shared_state.clone()),
         )
+        .nest(
+            "/transactions",
+            create_transactions_router().with_state(shared_state.clone()),
+        )
+        .nest(
+            "/auto-rebalance",
+            create_auto_rebalance_router().with_state(shared_state.clone()),
+        )
         .nest(
             "/admin",
-            crate::admin::create_admin_router().with_state(shared_state.clone()),
+            crate::admin::create_admin_router(shared_state.clone())
+                .with_state(shared_state.clone()),
         )
         .route("/users/:address", get(get_user))
         .route("/users/:address/bets", get(get_user_bets))
@@ -1,18 +1,35 @@
 use axum::{
     extract::{Path, State},
+    middleware,
     response::Json,
     routing::{get, post},
     Router,
 };
 use serde::Serialize;
 
-use crate::{db::Database, error::AppError, models::Protocol, services::ProtocolService};
+use crate::{
+    db::Database,
+    error::AppError,
+    middleware::{require_jwt, require_permission},
+    models::Protocol,
+    services::ProtocolService,
+    utils::Permission,
+};
+
+pub fn create_protocols_router(
+    state: (Database, crate::config::Config),
+) -> Router<(Database, crate::config::Config)> {
+    let refresh_routes = Router::new()
+        .route("/refresh-apy", post(refresh_protocol_apys))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::UpdateYields, req, next)
+        }))
+        .route_layer(middleware::from_fn_with_state(state, require_jwt));
 
-pub fn create_protocols_router() -> Router<(Database, crate::config::Config)> {
     Router::new()
         .route("/", get(get_protocols))
         .route("/:address", get(get_protocol))
-        .route("/refresh-apy", post(refresh_protocol_apys))
+        .merge(refresh_routes)
 }
 
 async fn get_protocols(
@@ -45,7 +62,7 @@ async fn refresh_protocol_apys(
     let protocol_service = ProtocolService::new(db);
 
     match protocol_service
-        .update_all_apys_from_blockchain(&config.base_rpc_url)
+        .update_all_apys_from_blockchain(&config)
         .await
     {
         Ok(count) => Ok(Json(RefreshApyResponse {
@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Path, State},
+    middleware,
+    response::Json,
+    routing::{get, post},
+    Extension, Router,
+};
+use serde_json::json;
+
+use crate::{
+    db::Database,
+    error::AppError,
+    middleware::{require_jwt, require_permission},
+    models::{
+        DisputeResolutionRequest, FinalizeResolutionRequest, ProposeResolutionRequest,
+        RejectResolutionRequest,
+    },
+    services::ResolutionService,
+    utils::{jwt::Claims, Permission},
+};
+
+pub fn create_resolutions_router(
+    state: (Database, crate::config::Config),
+) -> Router<(Database, crate::config::Config)> {
+    let curator_routes = Router::new()
+        .route("/market/:id/propose", post(propose_resolution))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::ManageMarkets, req, next)
+        }));
+
+    let admin_routes = Router::new()
+        .route("/:id/finalize", post(finalize_resolution))
+        .route("/:id/reject", post(reject_resolution))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::ResolveMarkets, req, next)
+        }));
+
+    let protected_routes = Router::new()
+        .route("/:id/dispute", post(dispute_resolution))
+        .merge(curator_routes)
+        .merge(admin_routes)
+        .route_layer(middleware::from_fn_with_state(state, require_jwt));
+
+    Router::new()
+        .route("/market/:id", get(get_market_resolution))
+        .merge(protected_routes)
+}
+
+async fn get_market_resolution(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let resolution_service = ResolutionService::new(db);
+    let resolution = resolution_service.get_market_resolution(&id).await?;
+
+    Ok(Json(json!({
+        "data": {
+            "proposals": resolution.proposals,
+            "disputes": resolution.disputes,
+            "audit": resolution.audit
+        }
+    })))
+}
+
+async fn propose_resolution(
+    State((db, config)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+    Json(payload): Json<ProposeResolutionRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let resolution_service = ResolutionService::new(db);
+    let proposal = resolution_service
+        .propose(
+            &id,
+            payload.outcome,
+            &payload.evidence,
+            &claims.address,
+            config.resolution_challenge_window_secs,
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Outcome proposed",
+        "data": proposal
+    })))
+}
+
+async fn dispute_resolution(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+    Json(payload): Json<DisputeResolutionRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let resolution_service = ResolutionService::new(db);
+    let dispute = resolution_service
+        .dispute(&id, &claims.address, &payload.reason, &payload.evidence)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Proposal disputed",
+        "data": dispute
+    })))
+}
+
+async fn finalize_resolution(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+    Json(payload): Json<FinalizeResolutionRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let resolution_service = ResolutionService::new(db);
+    let (proposal, settled_bets) = resolution_service
+        .finalize(
+            &id,
+            &claims.address,
+            payload.outcome,
+            payload.reason.as_deref(),
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Market resolved",
+        "data": {
+            "proposal": proposal,
+            "settledBets": settled_bets
+        }
+    })))
+}
+
+async fn reject_resolution(
+    State((db, _)): State<(Database, crate::config::Config)>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<String>,
+    Json(payload): Json<RejectResolutionRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let resolution_service = ResolutionService::new(db);
+    let proposal = resolution_service
+        .reject(&id, &claims.address, &payload.reason)
+        .await?;
+
+    Ok(Json(json!({
+        "message": "Proposal rejected; market is closed again",
+        "data": proposal
+    })))
+}
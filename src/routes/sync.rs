@@ -1,19 +1,45 @@
 use axum::{
     extract::{Path, State},
+    middleware,
     response::Json,
     routing::{get, post},
     Router,
 };
 use serde_json::json;
 
-use crate::{db::Database, error::AppError, models::SyncStatusResponse, services::SyncService};
+use crate::{
+    db::Database,
+    error::AppError,
+    middleware::{require_jwt_or_api_key, require_permission},
+    models::SyncStatusResponse,
+    services::SyncService,
+    utils::Permission,
+};
 
-pub fn create_sync_router() -> Router<(Database, crate::config::Config)> {
-    Router::new()
+pub fn create_sync_router(
+    state: (Database, crate::config::Config),
+) -> Router<(Database, crate::config::Config)> {
+    let status_routes = Router::new()
         .route("/status", get(sync_status))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::ViewSyncStatus, req, next)
+        }));
+
+    let trigger_routes = Router::new()
         .route("/full", post(trigger_full_sync))
+        .route("/incremental", post(trigger_incremental_sync))
         .route("/blockchain", post(sync_from_blockchain))
         .route("/market/:id", post(sync_specific_market))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::TriggerSync, req, next)
+        }));
+
+    status_routes
+        .merge(trigger_routes)
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            require_jwt_or_api_key,
+        ))
 }
 
 async fn sync_status(
@@ -25,23 +51,40 @@ async fn sync_status(
 }
 
 async fn trigger_full_sync(
-    State((db, _)): State<(Database, crate::config::Config)>,
+    State((db, config)): State<(Database, crate::config::Config)>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let sync_service = SyncService::new(db);
-    sync_service.full_sync().await?;
+    sync_service.full_sync(&config).await?;
     Ok(Json(json!({
         "status": "success",
         "message": "Full sync completed"
     })))
 }
 
+async fn trigger_incremental_sync(
+    State((db, config)): State<(Database, crate::config::Config)>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let sync_service = SyncService::new(db);
+    let summary = sync_service.incremental_sync(&config).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "message": format!(
+            "Indexed {} events over {} blocks",
+            summary.events, summary.blocks
+        ),
+        "blocks": summary.blocks,
+        "events": summary.events,
+        "reorgs": summary.reorgs,
+        "bets_synced": summary.bets,
+        "resolutions_synced": summary.resolutions
+    })))
+}
+
 async fn sync_from_blockchain(
     State((db, config)): State<(Database, crate::config::Config)>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let sync_service = SyncService::new(db);
-    let synced_count = sync_service
-        .sync_from_blockchain(&config.whizy_prediction_market_addr, &config.base_rpc_url)
-        .await?;
+    let synced_count = sync_service.sync_from_blockchain(&config).await?;
     Ok(Json(json!({
         "status": "success",
         "message": format!("Synced {} markets from blockchain", synced_count),
@@ -50,13 +93,15 @@ async fn sync_from_blockchain(
 }
 
 async fn sync_specific_market(
-    State((db, _)): State<(Database, crate::config::Config)>,
+    State((db, config)): State<(Database, crate::config::Config)>,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let sync_service = SyncService::new(db);
-    sync_service.sync_market_by_id(&id).await?;
+    let (market_id, reconciliation) = sync_service.sync_market_by_id(&config, &id).await?;
     Ok(Json(json!({
         "status": "success",
-        "message": format!("Market {} synced", id)
+        "message": format!("Market {} synced", id),
+        "market_id": market_id,
+        "reconciliation": reconciliation
     })))
 }
@@ -0,0 +1,27 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use serde_json::json;
+
+use crate::{db::Database, error::AppError, services::TransactionService};
+
+pub fn create_transactions_router() -> Router<(Database, crate::config::Config)> {
+    Router::new().route("/:hash", get(get_transaction))
+}
+
+async fn get_transaction(
+    State((db, config)): State<(Database, crate::config::Config)>,
+    Path(hash): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let transaction_service = TransactionService::new(db);
+    let transaction = transaction_service.get_transaction(&hash).await?;
+    let explorer_url = config.explorer_transaction_url(&transaction.hash);
+
+    Ok(Json(json!({
+        "data": transaction,
+        "explorerUrl": explorer_url
+    })))
+}
@@ -0,0 +1,12 @@
+use axum::{response::Json, routing::get, Router};
+use jsonwebtoken::jwk::JwkSet;
+
+use crate::{error::AppError, utils::JwtService};
+
+pub fn create_well_known_router() -> Router {
+    Router::new().route("/jwks.json", get(get_jwks))
+}
+
+async fn get_jwks() -> Result<Json<JwkSet>, AppError> {
+    Ok(Json(JwtService::new()?.jwks()))
+}
@@ -1,5 +1,6 @@
 use axum::{
     extract::{Path, Query, State},
+    middleware,
     response::Json,
     routing::{get, post},
     Router,
@@ -7,14 +8,30 @@ use axum::{
 use serde::Deserialize;
 use serde_json::json;
 
-use crate::{db::Database, error::AppError, services::BlockchainYieldService};
+use crate::{
+    db::Database,
+    error::AppError,
+    middleware::{require_jwt, require_permission},
+    services::BlockchainYieldService,
+    utils::Permission,
+};
+
+pub fn create_yields_router(
+    state: (Database, crate::config::Config),
+) -> Router<(Database, crate::config::Config)> {
+    let update_routes = Router::new()
+        .route("/update", post(update_yields))
+        .route("/blockchain/sync/:market_id", post(sync_market_yield))
+        .route("/blockchain/sync-all", post(sync_all_market_yields))
+        .route_layer(middleware::from_fn(|req, next| {
+            require_permission(Permission::UpdateYields, req, next)
+        }))
+        .route_layer(middleware::from_fn_with_state(state, require_jwt));
 
-pub fn create_yields_router() -> Router<(Database, crate::config::Config)> {
     Router::new()
         .route("/", get(get_yields))
         .route("/summary", get(get_yield_summary))
         .route("/protocols", get(get_yield_protocols))
-        .route("/update", post(update_yields))
         .route("/apy/current", get(get_current_apy))
         .route("/contract/test", get(test_contract_connectivity))
         .route("/contract/apy", get(get_contract_apy))
@@ -23,8 +40,7 @@ pub fn create_yields_router() -> Router<(Database, crate::config::Config)> {
             get(get_market_yield_from_blockchain),
         )
         .route("/blockchain/user", get(get_user_yield_from_blockchain))
-        .route("/blockchain/sync/:market_id", post(sync_market_yield))
-        .route("/blockchain/sync-all", post(sync_all_market_yields))
+        .merge(update_routes)
 }
 
 async fn get_yields(
@@ -199,11 +215,7 @@ async fn get_market_yield_from_blockchain(
     State((db, config)): State<(Database, crate::config::Config)>,
     Path(market_id): Path<u64>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    let yield_service = BlockchainYieldService::new(
-        db,
-        config.base_rpc_url.clone(),
-        config.whizy_prediction_market_addr.clone(),
-    );
+    let yield_service = BlockchainYieldService::new(db, &config);
 
     let yield_info = yield_service.get_market_current_yield(market_id).await?;
 
@@ -218,11 +230,7 @@ async fn get_user_yield_from_blockchain(
     State((db, config)): State<(Database, crate::config::Config)>,
     Query(params): Query<UserYieldQuery>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    let yield_service = BlockchainYieldService::new(
-        db,
-        config.base_rpc_url.clone(),
-        config.whizy_prediction_market_addr.clone(),
-    );
+    let yield_service = BlockchainYieldService::new(db, &config);
 
     let user_yield = yield_service
         .get_user_current_yield(params.market_id, &params.user_address)
@@ -239,11 +247,7 @@ async fn sync_market_yield(
     State((db, config)): State<(Database, crate::config::Config)>,
     Path(market_id): Path<u64>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    let yield_service = BlockchainYieldService::new(
-        db,
-        config.base_rpc_url.clone(),
-        config.whizy_prediction_market_addr.clone(),
-    );
+    let yield_service = BlockchainYieldService::new(db, &config);
 
     yield_service.sync_market_yield_to_db(market_id).await?;
 
@@ -259,11 +263,7 @@ async fn sync_market_yield(
 async fn sync_all_market_yields(
     State((db, config)): State<(Database, crate::config::Config)>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    let yield_service = BlockchainYieldService::new(
-        db,
-        config.base_rpc_url.clone(),
-        config.whizy_prediction_market_addr.clone(),
-    );
+    let yield_service = BlockchainYieldService::new(db, &config);
 
     let synced_count = yield_service.sync_all_active_markets_yields().await?;
 
@@ -1,3 +1,7 @@
+use crate::{
+    config::Config,
+    services::rpc::{self, RpcProvider},
+};
 use anyhow::Result;
 use ethers::prelude::*;
 use sqlx::PgPool;
@@ -13,7 +17,7 @@ abigen!(
     ]"#,
 );
 
-async fn fetch_protocol_apy(provider: &Provider<Http>, adapter_address: &str) -> Result<String> {
+async fn fetch_protocol_apy(provider: &RpcProvider, adapter_address: &str) -> Result<String> {
     let address: Address = adapter_address.parse()?;
     let contract = IYieldProtocol::new(address, Arc::new(provider.clone()));
 
@@ -62,12 +66,11 @@ pub async fn seed_protocols(pool: &PgPool) -> Result<()> {
         ),
     ];
 
-    let rpc_url =
-        std::env::var("HEDERA_RPC_URL").unwrap_or_else(|_| "https://hashscan.io/testnet".to_string());
+    let config = Config::from_env()?;
 
-    info!("Connecting to HEDERA Testnet RPC: {}", rpc_url);
+    info!("Connecting to HEDERA Testnet RPC: {}", config.base_rpc_url);
 
-    let provider_result = Provider::<Http>::try_from(&rpc_url);
+    let provider_result = rpc::provider(&config);
 
     for (
         name,
@@ -0,0 +1,179 @@
+use chrono::{NaiveDateTime, Utc};
+use rand::{distributions::Alphanumeric, Rng};
+
+use crate::{
+    db::Database,
+    error::{AppError, Result},
+    models::ApiKey,
+    utils::ApiKeyScope,
+};
+
+const KEY_PREFIX: &str = "whz";
+const KEY_ID_LENGTH: usize = 8;
+const KEY_SECRET_LENGTH: usize = 32;
+
+const API_KEY_COLUMNS: &str = r#"id, name, "keyPrefix", scopes, "expiresAt", "lastUsedAt", revoked, "revokedAt", "createdBy", "createdAt""#;
+
+#[derive(Debug, Clone)]
+pub struct ApiKeyContext {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+pub struct ApiKeyService {
+    db: Database,
+}
+
+impl ApiKeyService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn create_key(
+        &self,
+        name: &str,
+        scopes: &[ApiKeyScope],
+        expires_at: Option<NaiveDateTime>,
+        created_by: Option<&str>,
+    ) -> Result<(ApiKey, String)> {
+        if name.trim().is_empty() {
+            return Err(AppError::BadRequest("API key name is required".to_string()));
+        }
+        if scopes.is_empty() {
+            return Err(AppError::BadRequest(
+                "API key needs at least one scope".to_string(),
+            ));
+        }
+        if expires_at.is_some_and(|expires_at| expires_at <= Utc::now().naive_utc()) {
+            return Err(AppError::BadRequest(
+                "API key expiry must be in the future".to_string(),
+            ));
+        }
+
+        let key_prefix = format!("{}_{}", KEY_PREFIX, random_string(KEY_ID_LENGTH));
+        let raw_key = format!("{}_{}", key_prefix, random_string(KEY_SECRET_LENGTH));
+
+        let to_hash = raw_key.clone();
+        let key_hash =
+            tokio::task::spawn_blocking(move || bcrypt::hash(to_hash, bcrypt::DEFAULT_COST))
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to hash API key: {}", e)))?
+                .map_err(|e| AppError::Internal(format!("Failed to hash API key: {}", e)))?;
+
+        let scopes: Vec<String> = scopes.iter().map(|s| s.as_str().to_string()).collect();
+
+        let api_key = sqlx::query_as::<_, ApiKey>(&format!(
+            r#"
+            INSERT INTO api_keys (id, name, "keyPrefix", "keyHash", scopes, "expiresAt", "createdBy")
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {}
+            "#,
+            API_KEY_COLUMNS
+        ))
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(name.trim())
+        .bind(&key_prefix)
+        .bind(key_hash)
+        .bind(&scopes)
+        .bind(expires_at)
+        .bind(created_by)
+        .fetch_one(self.db.pool())
+        .await?;
+
+        Ok((api_key, raw_key))
+    }
+
+    pub async fn list_keys(&self) -> Result<Vec<ApiKey>> {
+        let keys = sqlx::query_as::<_, ApiKey>(&format!(
+            r#"SELECT {} FROM api_keys ORDER BY "createdAt" DESC"#,
+            API_KEY_COLUMNS
+        ))
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(keys)
+    }
+
+    pub async fn revoke_key(&self, id: &str) -> Result<ApiKey> {
+        sqlx::query_as::<_, ApiKey>(&format!(
+            r#"
+            UPDATE api_keys
+            SET revoked = true, "revokedAt" = COALESCE("revokedAt", NOW())
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            API_KEY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("API key {} not found", id)))
+    }
+
+    pub async fn authenticate(&self, raw_key: &str) -> Result<ApiKeyContext> {
+        let invalid = || AppError::Unauthorized("Invalid or missing API key".to_string());
+
+        let key_prefix = raw_key
+            .rsplit_once('_')
+            .map(|(prefix, _)| prefix)
+            .ok_or_else(invalid)?;
+
+        let row = sqlx::query!(
+            r#"
+            SELECT id, name, "keyHash" as key_hash, scopes,
+                   revoked OR COALESCE("expiresAt" <= NOW(), false) as "inactive!"
+            FROM api_keys
+            WHERE "keyPrefix" = $1
+            "#,
+            key_prefix
+        )
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(invalid)?;
+
+        let candidate = raw_key.to_string();
+        let key_hash = row.key_hash.clone();
+        let matches = tokio::task::spawn_blocking(move || bcrypt::verify(candidate, &key_hash))
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to verify API key: {}", e)))?
+            .unwrap_or(false);
+
+        if !matches {
+            return Err(invalid());
+        }
+
+        if row.inactive {
+            return Err(AppError::Unauthorized(
+                "API key has been revoked or has expired".to_string(),
+            ));
+        }
+
+        sqlx::query!(
+            r#"UPDATE api_keys SET "lastUsedAt" = NOW() WHERE id = $1"#,
+            row.id
+        )
+        .execute(self.db.pool())
+        .await?;
+
+        let scopes = row
+            .scopes
+            .iter()
+            .filter_map(|scope| scope.parse::<ApiKeyScope>().ok())
+            .collect();
+
+        Ok(ApiKeyContext {
+            id: row.id,
+            name: row.name,
+            scopes,
+        })
+    }
+}
+
+fn random_string(length: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
@@ -0,0 +1,75 @@
+use chrono::NaiveDateTime;
+use rand::{distributions::Alphanumeric, Rng};
+
+use crate::{
+    db::Database,
+    error::{AppError, Result},
+};
+
+const NONCE_LENGTH: usize = 17;
+
+pub struct IssuedNonce {
+    pub nonce: String,
+    pub expires_at: NaiveDateTime,
+}
+
+pub struct AuthService {
+    db: Database,
+}
+
+impl AuthService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn issue_nonce(&self, ttl_secs: u64) -> Result<IssuedNonce> {
+        sqlx::query!(r#"DELETE FROM auth_nonces WHERE "expiresAt" < NOW() - INTERVAL '1 day'"#)
+            .execute(self.db.pool())
+            .await?;
+
+        let nonce: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(NONCE_LENGTH)
+            .map(char::from)
+            .collect();
+
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO auth_nonces (nonce, "expiresAt")
+            VALUES ($1, NOW() + make_interval(secs => $2))
+            RETURNING "expiresAt"
+            "#,
+            nonce,
+            ttl_secs as f64
+        )
+        .fetch_one(self.db.pool())
+        .await?;
+
+        Ok(IssuedNonce {
+            nonce,
+            expires_at: row.expiresAt,
+        })
+    }
+
+    pub async fn consume_nonce(&self, nonce: &str) -> Result<()> {
+        let consumed = sqlx::query!(
+            r#"
+            UPDATE auth_nonces
+            SET "consumedAt" = NOW()
+            WHERE nonce = $1 AND "consumedAt" IS NULL AND "expiresAt" > NOW()
+            RETURNING nonce
+            "#,
+            nonce
+        )
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        if consumed.is_none() {
+            return Err(AppError::Unauthorized(
+                "Nonce is unknown, expired or already used".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use ethers::types::Address;
+
+use crate::{
+    db::Database,
+    error::{AppError, Result},
+    models::{AutoRebalanceStats, AutoRebalanceStatus, RebalanceRecord, RiskProfileUsers},
+};
+
+pub struct AutoRebalanceService {
+    db: Database,
+}
+
+impl AutoRebalanceService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn get_user_status(&self, address: &str) -> Result<AutoRebalanceStatus> {
+        let user = checksum_address(address)?;
+
+        let status = sqlx::query_as::<_, AutoRebalanceStatus>(
+            r#"
+            SELECT
+                $1::text as "user",
+                COALESCE(a.is_enabled, false) as is_enabled,
+                a.risk_profile,
+                to_timestamp(a.enabled_at)::timestamp as enabled_at,
+                to_timestamp(a.disabled_at)::timestamp as disabled_at,
+                (SELECT COALESCE(SUM(amount), 0) FROM depositeds WHERE "user" = $1)
+                    - (SELECT COALESCE(SUM(amount), 0) FROM withdrawns WHERE "user" = $1) as balance,
+                r.rebalance_count,
+                r.total_rebalanced,
+                r.last_rebalanced_at
+            FROM (
+                SELECT COUNT(*) as rebalance_count,
+                       COALESCE(SUM(amount), 0) as total_rebalanced,
+                       to_timestamp(MAX(block_timestamp))::timestamp as last_rebalanced_at
+                FROM rebalanceds
+                WHERE "user" = $1
+            ) r
+            LEFT JOIN active_auto_rebalance_users a ON a."user" = $1
+            "#,
+        )
+        .bind(&user)
+        .fetch_one(self.db.pool())
+        .await?;
+
+        Ok(status)
+    }
+
+    pub async fn get_user_history(
+        &self,
+        address: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<RebalanceRecord>, i64)> {
+        let user = checksum_address(address)?;
+
+        let records = sqlx::query_as::<_, RebalanceRecord>(
+            r#"
+            SELECT id, "user", operator, amount, block_number::bigint as block_number,
+                   transaction_hash, to_timestamp(block_timestamp)::timestamp as rebalanced_at
+            FROM rebalanceds
+            WHERE "user" = $1
+            ORDER BY block_number DESC, id DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(&user)
+        .bind(limit.clamp(1, 500))
+        .bind(offset.max(0))
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let total: i64 =
+            sqlx::query_scalar(r#"SELECT COUNT(*) FROM rebalanceds WHERE "user" = $1"#)
+                .bind(&user)
+                .fetch_one(self.db.pool())
+                .await?;
+
+        Ok((records, total))
+    }
+
+    pub async fn get_stats(&self) -> Result<AutoRebalanceStats> {
+        let mut stats = sqlx::query_as::<_, AutoRebalanceStats>(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM active_auto_rebalance_users WHERE is_enabled) as enabled_users,
+                (SELECT COUNT(*) FROM active_auto_rebalance_users WHERE NOT is_enabled) as disabled_users,
+                (SELECT COALESCE(SUM(amount), 0) FROM depositeds)
+                    - (SELECT COALESCE(SUM(amount), 0) FROM withdrawns) as total_deposited,
+                (SELECT COUNT(*) FROM rebalanceds) as total_rebalances,
+                (SELECT COALESCE(SUM(amount), 0) FROM rebalanceds) as total_rebalanced,
+                (SELECT COUNT(*) FROM rebalanceds
+                 WHERE block_timestamp >= EXTRACT(EPOCH FROM NOW()) - 86400) as rebalances_24h,
+                (SELECT COUNT(DISTINCT "user") FROM rebalanceds) as rebalanced_users,
+                (SELECT COUNT(DISTINCT a.operator) FROM operator_addeds a
+                 WHERE NOT EXISTS (
+                     SELECT 1 FROM operator_removeds r
+                     WHERE r.operator = a.operator AND r.block_number > a.block_number
+                 )) as active_operators,
+                (SELECT to_timestamp(MAX(block_timestamp))::timestamp FROM rebalanceds) as last_rebalanced_at
+            "#,
+        )
+        .fetch_one(self.db.pool())
+        .await?;
+
+        stats.users_by_risk_profile = sqlx::query_as::<_, RiskProfileUsers>(
+            r#"
+            SELECT risk_profile, COUNT(*) as users
+            FROM active_auto_rebalance_users
+            WHERE is_enabled
+            GROUP BY risk_profile
+            ORDER BY risk_profile
+            "#,
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(stats)
+    }
+
+    pub async fn refresh_active_users(&self) -> Result<()> {
+        sqlx::query("SELECT refresh_active_auto_rebalance_users()")
+            .execute(self.db.pool())
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn checksum_address(address: &str) -> Result<String> {
+    let address = address
+        .parse::<Address>()
+        .map_err(|_| AppError::BadRequest(format!("Invalid address: {}", address)))?;
+
+    Ok(ethers::utils::to_checksum(&address, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_addresses_match_indexed_checksum_form() {
+        assert_eq!(
+            checksum_address("0x3182d2160e054dc25b291d04530f514020684315").unwrap(),
+            "0x3182d2160E054DC25b291d04530F514020684315"
+        );
+        assert!(checksum_address("0x1234").is_err());
+    }
+}
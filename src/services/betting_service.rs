@@ -1,14 +1,39 @@
 use crate::{
-    constants::{parse_usdc_amount, raw_to_bigdecimal, raw_to_usdc, validate_bet_amount},
+    config::Config,
+    constants::{
+        bigdecimal_to_raw, parse_usdc_amount, raw_to_bigdecimal, raw_to_usdc, validate_bet_amount,
+    },
     db::Database,
     error::AppError,
+    models::BetIntent,
+    services::{
+        blockchain_event::EventRetryPolicy,
+        blockchain_yield::PotentialPayout,
+        indexer::{write_events, IndexedContractKind},
+        outbox::{enqueue, ContractCall},
+        rpc,
+        transaction::record_transaction,
+        BlockchainYieldService, SignerService,
+    },
+    utils::{
+        min_odds, project_yield, quote_bet, BetQuote, BetQuoteClaims, JwtService, MarketState,
+        BET_QUOTE_TOKEN_TYPE, DEFAULT_SLIPPAGE_BPS, MAX_SLIPPAGE_BPS,
+    },
+};
+use ethers::{
+    abi::{AbiDecode, AbiEncode},
+    prelude::*,
+    types::transaction::eip2718::TypedTransaction,
 };
-use ethers::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tracing::{error, info};
+use std::{collections::HashMap, sync::Arc};
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
+const BET_INTENT_TTL_SECS: f64 = 900.0;
+
+const BET_INTENT_COLUMNS: &str = r#"id, "userAddress", "marketId", "blockchainMarketId", position, amount, status, "txHash", "betId", error, "expiresAt", "createdAt", "updatedAt""#;
+
 abigen!(
     WhizyPredictionMarket,
     r#"[
@@ -33,20 +58,54 @@ pub struct PlaceBetParams {
     pub user_address: String,
     pub position: bool,
     pub amount: String,
+    pub quote_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PlaceBetResult {
     pub bet_id: String,
-    pub blockchain_bet_id: u64,
+    pub blockchain_bet_id: Option<u64>,
     pub market_id: String,
     pub blockchain_market_id: u64,
     pub position: bool,
     pub amount: String,
-    pub tx_hash: String,
     pub user_address: String,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedTransaction {
+    pub kind: &'static str,
+    pub from: Address,
+    pub to: Address,
+    pub data: Bytes,
+    pub value: U256,
+    pub chain_id: u64,
+}
+
+pub struct SignedBetQuote {
+    pub quote_id: String,
+    pub market_id: String,
+    pub position: bool,
+    pub amount: u64,
+    pub quote: BetQuote,
+    pub min_odds: f64,
+    pub slippage_bps: u32,
+    pub current_yield: u64,
+    pub projected_yield: u64,
+    pub yield_source: &'static str,
+    pub current_position: Option<PotentialPayout>,
+    pub expires_at: i64,
+}
+
+pub struct PreparedBet {
+    pub intent: BetIntent,
+    pub token_address: Address,
+    pub allowance: U256,
+    pub balance: U256,
+    pub transactions: Vec<UnsignedTransaction>,
+}
+
 pub struct BettingService {
     db: Database,
 }
@@ -56,41 +115,34 @@ impl BettingService {
         Self { db }
     }
 
-    pub async fn place_bet(&self, params: PlaceBetParams) -> Result<PlaceBetResult, AppError> {
+    pub async fn place_bet(
+        &self,
+        config: &Config,
+        params: PlaceBetParams,
+    ) -> Result<PlaceBetResult, AppError> {
         info!("Placing bet on market: {}", params.market_identifier);
 
         let market = self
             .get_market_by_identifier(&params.market_identifier)
             .await?;
 
-        if market.blockchain_market_id.is_none() {
-            return Err(AppError::BadRequest(
-                "Market is not on blockchain yet".to_string(),
-            ));
-        }
-
-        let blockchain_market_id = market.blockchain_market_id.unwrap() as u64;
-
-        if market.status != "active" {
-            return Err(AppError::BadRequest("Market is not active".to_string()));
-        }
+        let blockchain_market_id = self.ensure_market_open(&market)?;
 
         let amount_raw = parse_usdc_amount(&params.amount)?;
 
         validate_bet_amount(amount_raw)?;
 
+        if let Some(quote_id) = params.quote_id.as_deref() {
+            self.enforce_quote(quote_id, &market.id, params.position, amount_raw)
+                .await?;
+        }
+
         info!(
             "Placing bet: {} USDC ({} base units)",
             raw_to_usdc(amount_raw),
             amount_raw
         );
 
-        let tx_hash = self
-            .submit_bet_transaction(blockchain_market_id, params.position, amount_raw)
-            .await?;
-
-        info!("Bet transaction submitted: {}", tx_hash);
-
         let odds = self
             .calculate_bet_odds(&market.id, params.position, amount_raw)
             .await?;
@@ -100,13 +152,15 @@ impl BettingService {
 
         let bet_id = Uuid::new_v4().to_string();
 
+        let mut tx = self.db.pool().begin().await?;
+
         sqlx::query!(
             r#"
             INSERT INTO bets_extended (
                 id, "userId", "marketId", position, amount,
                 odds, status, "createdAt", "updatedAt"
             )
-            VALUES ($1, $2, $3, $4, $5, $6, 'active', NOW(), NOW())
+            VALUES ($1, $2, $3, $4, $5, $6, 'pending', NOW(), NOW())
             "#,
             bet_id,
             params.user_address,
@@ -115,35 +169,628 @@ impl BettingService {
             raw_to_bigdecimal(amount_raw),
             odds_decimal,
         )
-        .execute(self.db.pool())
+        .execute(&mut *tx)
         .await
         .map_err(|e| AppError::Internal(format!("Failed to insert bet: {}", e)))?;
 
-        self.update_market_pools(&market.id, params.position, amount_raw)
-            .await?;
+        enqueue(
+            &mut tx,
+            &ContractCall::PlaceBet {
+                blockchain_market_id,
+                position: params.position,
+                amount: amount_raw,
+            },
+            Some(&market.id),
+            Some(&bet_id),
+            config.outbox_max_attempts,
+        )
+        .await?;
 
-        info!(
-            "Bet {} created successfully for market {}",
-            bet_id, market.id
-        );
+        tx.commit().await?;
+
+        info!("Bet {} queued for market {}", bet_id, market.id);
 
         Ok(PlaceBetResult {
             bet_id,
-            blockchain_bet_id: 0,
+            blockchain_bet_id: None,
             market_id: market.id,
             blockchain_market_id,
             position: params.position,
             amount: params.amount,
-            tx_hash,
             user_address: params.user_address,
         })
     }
 
+    pub async fn quote_bet(
+        &self,
+        config: &Config,
+        market_identifier: &str,
+        position: bool,
+        amount: &str,
+        user_address: Option<&str>,
+        slippage_bps: Option<u32>,
+    ) -> Result<SignedBetQuote, AppError> {
+        let market = self.get_market_by_identifier(market_identifier).await?;
+        let blockchain_market_id = self.ensure_market_open(&market)?;
+
+        let amount_raw = parse_usdc_amount(amount)?;
+        validate_bet_amount(amount_raw)?;
+
+        let slippage_bps = slippage_bps.unwrap_or(DEFAULT_SLIPPAGE_BPS);
+        if slippage_bps > MAX_SLIPPAGE_BPS {
+            return Err(AppError::BadRequest(format!(
+                "Slippage cannot exceed {} basis points",
+                MAX_SLIPPAGE_BPS
+            )));
+        }
+
+        let pools = sqlx::query!(
+            r#"
+            SELECT "yesPoolSize" as yes_pool, "noPoolSize" as no_pool,
+                   "totalPoolSize" as total_pool, "currentYield" as current_yield,
+                   "totalYesShares" + "totalNoShares" as "total_shares!",
+                   "createdAt" as created_at
+            FROM markets_extended
+            WHERE id = $1
+            "#,
+            market.id
+        )
+        .fetch_one(self.db.pool())
+        .await?;
+
+        let yield_service = BlockchainYieldService::new(self.db.clone(), config);
+
+        let (current_yield, vault_assets, vault_shares, yield_source) = match yield_service
+            .get_market_current_yield(blockchain_market_id)
+            .await
+        {
+            Ok(info) => (
+                info.current_yield_earned.parse::<u64>().unwrap_or(0),
+                info.total_pool_size.parse::<u64>().unwrap_or(0),
+                info.total_shares.parse::<u64>().unwrap_or(0),
+                "onchain",
+            ),
+            Err(e) => {
+                warn!(
+                    "⚠️ Using stored yield for quote on market {}: {}",
+                    market.id, e
+                );
+                let current_yield = bigdecimal_to_raw(&pools.current_yield);
+                (
+                    current_yield,
+                    bigdecimal_to_raw(&pools.total_pool).saturating_add(current_yield),
+                    bigdecimal_to_raw(&pools.total_shares),
+                    "database",
+                )
+            }
+        };
+
+        let now = chrono::Utc::now();
+        let projected_yield = project_yield(
+            current_yield,
+            (now.naive_utc() - pools.created_at).num_seconds(),
+            (market.end_date - now.naive_utc()).num_seconds(),
+        );
+
+        let quote = quote_bet(
+            bigdecimal_to_raw(&pools.yes_pool),
+            bigdecimal_to_raw(&pools.no_pool),
+            position,
+            amount_raw,
+            projected_yield,
+            vault_assets,
+            vault_shares,
+        );
+
+        let current_position = match user_address {
+            Some(user_address) => match yield_service
+                .get_potential_payout(blockchain_market_id, user_address)
+                .await
+            {
+                Ok(payout) => Some(payout),
+                Err(e) => {
+                    warn!(
+                        "⚠️ Could not read potential payout for {} on market {}: {}",
+                        user_address, market.id, e
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let expires_at = now.timestamp() + config.bet_quote_ttl_secs as i64;
+        let claims = BetQuoteClaims {
+            typ: BET_QUOTE_TOKEN_TYPE.to_string(),
+            qid: Uuid::new_v4().to_string(),
+            market_id: market.id.clone(),
+            position,
+            amount: amount_raw,
+            odds: quote.odds,
+            min_odds: min_odds(quote.odds, slippage_bps),
+            exp: expires_at,
+            iat: now.timestamp(),
+        };
+        let quote_id = JwtService::new()?.sign(&claims)?;
+
+        Ok(SignedBetQuote {
+            quote_id,
+            market_id: market.id,
+            position,
+            amount: amount_raw,
+            quote,
+            min_odds: claims.min_odds,
+            slippage_bps,
+            current_yield,
+            projected_yield,
+            yield_source,
+            current_position,
+            expires_at,
+        })
+    }
+
+    async fn enforce_quote(
+        &self,
+        quote_id: &str,
+        market_id: &str,
+        position: bool,
+        amount_raw: u64,
+    ) -> Result<(), AppError> {
+        let claims = JwtService::new()?
+            .verify::<BetQuoteClaims>(quote_id)
+            .ok()
+            .filter(|claims| claims.exp > chrono::Utc::now().timestamp())
+            .ok_or_else(|| {
+                AppError::BadRequest("Bet quote is invalid or has expired".to_string())
+            })?;
+
+        claims.ensure_matches(market_id, position, amount_raw)?;
+
+        let odds = self
+            .calculate_bet_odds(market_id, position, amount_raw)
+            .await?;
+        claims.ensure_within_slippage(odds)
+    }
+
+    pub async fn prepare_bet(
+        &self,
+        config: &Config,
+        user_id: &str,
+        user_address: &str,
+        market_identifier: &str,
+        position: bool,
+        amount: &str,
+    ) -> Result<PreparedBet, AppError> {
+        let market = self.get_market_by_identifier(market_identifier).await?;
+        let blockchain_market_id = self.ensure_market_open(&market)?;
+
+        let amount_raw = parse_usdc_amount(amount)?;
+        validate_bet_amount(amount_raw)?;
+
+        let user: Address = user_address
+            .parse()
+            .map_err(|_| AppError::BadRequest("Invalid user address".to_string()))?;
+        let contract_address = parse_address(&config.whizy_prediction_market_addr)?;
+
+        let provider = Arc::new(rpc::provider(config)?);
+        let contract = WhizyPredictionMarket::new(contract_address, provider.clone());
+
+        let market_data = contract
+            .markets(U256::from(blockchain_market_id))
+            .call()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to get market data: {}", e)))?;
+        let token_address = market_data.3;
+
+        let token = IERC20::new(token_address, provider);
+        let allowance = token
+            .allowance(user, contract_address)
+            .call()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to check allowance: {}", e)))?;
+        let balance = token
+            .balance_of(user)
+            .call()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to check balance: {}", e)))?;
+
+        if balance < U256::from(amount_raw) {
+            return Err(AppError::BadRequest(format!(
+                "Insufficient USDC balance: have {}, need {}",
+                u64::try_from(balance).map(raw_to_usdc).unwrap_or_default(),
+                raw_to_usdc(amount_raw)
+            )));
+        }
+
+        let mut transactions = Vec::new();
+        if allowance < U256::from(amount_raw) {
+            transactions.push(UnsignedTransaction {
+                kind: "approve",
+                from: user,
+                to: token_address,
+                data: ApproveCall {
+                    spender: contract_address,
+                    amount: U256::from(amount_raw),
+                }
+                .encode()
+                .into(),
+                value: U256::zero(),
+                chain_id: config.base_chain_id,
+            });
+        }
+        transactions.push(UnsignedTransaction {
+            kind: "placeBet",
+            from: user,
+            to: contract_address,
+            data: PlaceBetCall {
+                market_id: U256::from(blockchain_market_id),
+                is_yes: position,
+                amount: U256::from(amount_raw),
+            }
+            .encode()
+            .into(),
+            value: U256::zero(),
+            chain_id: config.base_chain_id,
+        });
+
+        let intent = sqlx::query_as::<_, BetIntent>(&format!(
+            r#"
+            INSERT INTO bet_intents (
+                id, "userId", "userAddress", "marketId", "blockchainMarketId", position, amount,
+                "expiresAt"
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, NOW() + make_interval(secs => $8))
+            RETURNING {}
+            "#,
+            BET_INTENT_COLUMNS
+        ))
+        .bind(Uuid::new_v4().to_string())
+        .bind(user_id)
+        .bind(user_address)
+        .bind(&market.id)
+        .bind(blockchain_market_id as i64)
+        .bind(position)
+        .bind(raw_to_bigdecimal(amount_raw))
+        .bind(BET_INTENT_TTL_SECS)
+        .fetch_one(self.db.pool())
+        .await?;
+
+        info!(
+            "Prepared bet intent {} for {} on market {}",
+            intent.id, user_address, market.id
+        );
+
+        Ok(PreparedBet {
+            intent,
+            token_address,
+            allowance,
+            balance,
+            transactions,
+        })
+    }
+
+    pub async fn submit_bet(
+        self: Arc<Self>,
+        config: &Config,
+        user_address: &str,
+        intent_id: &str,
+        tx_hash: Option<&str>,
+        signed_transaction: Option<&str>,
+    ) -> Result<BetIntent, AppError> {
+        let intent = self.get_bet_intent(user_address, intent_id).await?;
+
+        if intent.status != "prepared" {
+            return Err(AppError::BadRequest(format!(
+                "Bet intent {} is already {}",
+                intent.id, intent.status
+            )));
+        }
+        if intent.expires_at <= chrono::Utc::now().naive_utc() {
+            self.update_intent_status(&intent.id, "expired", None)
+                .await?;
+            return Err(AppError::BadRequest(
+                "Bet intent has expired; prepare the bet again".to_string(),
+            ));
+        }
+
+        let provider = rpc::provider(config)?;
+        let contract_address = parse_address(&config.whizy_prediction_market_addr)?;
+
+        let raw_transaction = signed_transaction
+            .map(|raw| {
+                raw.parse::<Bytes>()
+                    .map_err(|_| AppError::BadRequest("Invalid signed transaction hex".to_string()))
+            })
+            .transpose()?;
+
+        let (hash, from, to, input) = match (&raw_transaction, tx_hash) {
+            (Some(bytes), _) => decode_signed_transaction(bytes)?,
+            (None, Some(hash)) => {
+                let hash: H256 = hash
+                    .parse()
+                    .map_err(|_| AppError::BadRequest("Invalid transaction hash".to_string()))?;
+                let tx = provider
+                    .get_transaction(hash)
+                    .await
+                    .map_err(|e| AppError::Internal(format!("Failed to fetch transaction: {}", e)))?
+                    .ok_or_else(|| {
+                        AppError::NotFound(format!(
+                            "Transaction {:?} is not known to the node yet",
+                            hash
+                        ))
+                    })?;
+                (tx.hash, tx.from, tx.to, tx.input)
+            }
+            (None, None) => {
+                return Err(AppError::BadRequest(
+                    "Provide either txHash or signedTransaction".to_string(),
+                ))
+            }
+        };
+
+        if from != parse_address(&intent.user_address)? {
+            return Err(AppError::BadRequest(
+                "Transaction was not sent by the bet intent's wallet".to_string(),
+            ));
+        }
+        if to != Some(contract_address) {
+            return Err(AppError::BadRequest(
+                "Transaction is not addressed to the prediction market contract".to_string(),
+            ));
+        }
+
+        validate_place_bet(
+            &input,
+            intent.blockchain_market_id,
+            intent.position,
+            bigdecimal_to_raw(&intent.amount),
+        )?;
+
+        let hash_hex = format!("{:?}", hash);
+        let claimed = sqlx::query!(
+            r#"
+            UPDATE bet_intents
+            SET status = 'submitted', "txHash" = $2
+            WHERE id = $1 AND status = 'prepared'
+            RETURNING id
+            "#,
+            intent.id,
+            hash_hex
+        )
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        if claimed.is_none() {
+            return Err(AppError::BadRequest(
+                "Bet intent was submitted concurrently".to_string(),
+            ));
+        }
+
+        if let Some(bytes) = raw_transaction {
+            if let Err(e) = provider.send_raw_transaction(bytes).await {
+                let reason = format!("Failed to broadcast transaction: {}", e);
+                self.update_intent_status(&intent.id, "failed", Some(&reason))
+                    .await?;
+                return Err(AppError::BadRequest(reason));
+            }
+            info!(
+                "📡 Broadcast bet transaction {} for intent {}",
+                hash_hex, intent.id
+            );
+        }
+
+        let service = Arc::clone(&self);
+        let tracking_config = config.clone();
+        let tracking_intent = intent.id.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service
+                .track_bet_intent(&tracking_config, &tracking_intent, hash)
+                .await
+            {
+                warn!("⚠️ Tracking bet intent {} failed: {}", tracking_intent, e);
+            }
+        });
+
+        self.get_bet_intent(user_address, intent_id).await
+    }
+
+    pub async fn get_bet_intent(
+        &self,
+        user_address: &str,
+        intent_id: &str,
+    ) -> Result<BetIntent, AppError> {
+        sqlx::query_as::<_, BetIntent>(&format!(
+            r#"SELECT {} FROM bet_intents WHERE id = $1 AND LOWER("userAddress") = LOWER($2)"#,
+            BET_INTENT_COLUMNS
+        ))
+        .bind(intent_id)
+        .bind(user_address)
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Bet intent {} not found", intent_id)))
+    }
+
+    pub async fn refresh_bet_intent(
+        &self,
+        config: &Config,
+        user_address: &str,
+        intent_id: &str,
+    ) -> Result<BetIntent, AppError> {
+        let intent = self.get_bet_intent(user_address, intent_id).await?;
+
+        if intent.status == "submitted" {
+            if let Some(hash) = intent
+                .tx_hash
+                .as_deref()
+                .and_then(|h| h.parse::<H256>().ok())
+            {
+                let provider = rpc::provider(config)?;
+                let receipt = provider
+                    .get_transaction_receipt(hash)
+                    .await
+                    .map_err(|e| AppError::Internal(format!("Failed to fetch receipt: {}", e)))?;
+                if let Some(receipt) = receipt {
+                    self.record_bet_receipt(config, &intent, &receipt).await?;
+                    return self.get_bet_intent(user_address, intent_id).await;
+                }
+            }
+        }
+
+        Ok(intent)
+    }
+
+    async fn track_bet_intent(
+        &self,
+        config: &Config,
+        intent_id: &str,
+        tx_hash: H256,
+    ) -> Result<(), AppError> {
+        let provider = rpc::provider(config)?;
+
+        let receipt = PendingTransaction::new(tx_hash, &provider)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch receipt: {}", e)))?;
+
+        let intent = sqlx::query_as::<_, BetIntent>(&format!(
+            r#"SELECT {} FROM bet_intents WHERE id = $1"#,
+            BET_INTENT_COLUMNS
+        ))
+        .bind(intent_id)
+        .fetch_one(self.db.pool())
+        .await?;
+
+        match receipt {
+            Some(receipt) => self.record_bet_receipt(config, &intent, &receipt).await,
+            None => {
+                self.update_intent_status(intent_id, "failed", Some("Transaction was dropped"))
+                    .await
+            }
+        }
+    }
+
+    async fn record_bet_receipt(
+        &self,
+        config: &Config,
+        intent: &BetIntent,
+        receipt: &TransactionReceipt,
+    ) -> Result<(), AppError> {
+        if intent.status != "submitted" {
+            return Ok(());
+        }
+
+        if receipt.status != Some(U64::from(1)) {
+            error!("❌ Bet transaction {:?} reverted", receipt.transaction_hash);
+            return self
+                .update_intent_status(&intent.id, "failed", Some("Transaction reverted"))
+                .await;
+        }
+
+        let contract_address = parse_address(&config.whizy_prediction_market_addr)?;
+        let provider = rpc::provider(config)?;
+        let contract = WhizyPredictionMarket::new(contract_address, Arc::new(provider.clone()));
+
+        let Some((log, event)) = receipt
+            .logs
+            .iter()
+            .filter(|log| log.address == contract_address)
+            .find_map(|log| {
+                contract
+                    .decode_event::<BetPlacedFilter>(
+                        "BetPlaced",
+                        log.topics.clone(),
+                        log.data.clone(),
+                    )
+                    .ok()
+                    .map(|event| (log, event))
+            })
+        else {
+            return self
+                .update_intent_status(&intent.id, "failed", Some("No BetPlaced event in receipt"))
+                .await;
+        };
+
+        let block_number = receipt.block_number.unwrap_or_default();
+        let block = provider
+            .get_block(block_number)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch block: {}", e)))?;
+        let block_timestamp = block
+            .as_ref()
+            .map(|block| block.timestamp.as_u64())
+            .unwrap_or_else(|| chrono::Utc::now().timestamp() as u64);
+        let block_hash = block
+            .and_then(|block| block.hash)
+            .or(receipt.block_hash)
+            .map(|hash| format!("{:?}", hash));
+
+        let mut tx = self.db.pool().begin().await?;
+        let bet_id = confirm_intent(
+            &mut tx,
+            &intent.id,
+            log,
+            &HashMap::from([(block_number.as_u64(), (block_timestamp, block_hash))]),
+            &EventRetryPolicy::from_config(config),
+        )
+        .await?;
+        tx.commit().await?;
+
+        info!(
+            "✅ Bet {} confirmed for {} on market {}",
+            bet_id,
+            ethers::utils::to_checksum(&event.user, None),
+            intent.market_id
+        );
+
+        Ok(())
+    }
+
+    async fn update_intent_status(
+        &self,
+        intent_id: &str,
+        status: &str,
+        error: Option<&str>,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"UPDATE bet_intents SET status = $2, error = $3 WHERE id = $1"#,
+            intent_id,
+            status,
+            error
+        )
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    fn ensure_market_open(&self, market: &MarketRecord) -> Result<u64, AppError> {
+        let blockchain_market_id = market
+            .blockchain_market_id
+            .ok_or_else(|| AppError::BadRequest("Market is not on blockchain yet".to_string()))?
+            as u64;
+
+        let state = market.status.parse::<MarketState>()?;
+        if !state.accepts_bets() {
+            return Err(AppError::BadRequest(format!(
+                "Market is {} and not accepting bets",
+                state
+            )));
+        }
+
+        if market.end_date <= chrono::Utc::now().naive_utc() {
+            return Err(AppError::BadRequest(
+                "Market has passed its end date and is closed for betting".to_string(),
+            ));
+        }
+
+        Ok(blockchain_market_id)
+    }
+
     async fn get_market_by_identifier(&self, identifier: &str) -> Result<MarketRecord, AppError> {
         let market = sqlx::query_as!(
             MarketRecord,
             r#"
-            SELECT id, "blockchainMarketId" as blockchain_market_id, status
+            SELECT id, "blockchainMarketId" as blockchain_market_id, status,
+                   "endDate" as end_date
             FROM markets_extended
             WHERE id = $1 OR "adjTicker" = $1 OR "blockchainMarketId"::text = $1
             LIMIT 1
@@ -158,37 +805,14 @@ impl BettingService {
         Ok(market)
     }
 
-    async fn submit_bet_transaction(
+    pub(crate) async fn ensure_allowance(
         &self,
+        contract_address: &str,
         market_id: u64,
-        position: bool,
         amount: u64,
-    ) -> Result<String, AppError> {
-        let rpc_url = std::env::var("HEDERA_RPC_URL")
-            .map_err(|_| AppError::Internal("HEDERA_RPC_URL not configured".to_string()))?;
-
-        let contract_address = std::env::var("BASE_CONTRACT_ADDRESS")
-            .map_err(|_| AppError::Internal("BASE_CONTRACT_ADDRESS not configured".to_string()))?;
-
-        let private_key = std::env::var("USER_PRIVATE_KEY")
-            .map_err(|_| AppError::Internal("USER_PRIVATE_KEY not configured".to_string()))?;
-
-        let provider = Provider::<Http>::try_from(rpc_url)
-            .map_err(|e| AppError::Internal(format!("Failed to connect to RPC: {}", e)))?;
-
-        // Read chain ID from environment
-        let chain_id = std::env::var("HEDERA_CHAIN_ID")
-            .unwrap_or_else(|_| "2484".to_string())
-            .parse::<u64>()
-            .unwrap_or(2484);
-
-        let wallet: LocalWallet = private_key
-            .parse::<LocalWallet>()
-            .map_err(|e| AppError::Internal(format!("Invalid private key: {}", e)))?
-            .with_chain_id(chain_id);
-
-        let client = SignerMiddleware::new(provider, wallet);
-        let client = Arc::new(client);
+    ) -> Result<(), AppError> {
+        let signer = SignerService::shared()?;
+        let client = signer.client();
 
         let address: Address = contract_address
             .parse()
@@ -196,14 +820,6 @@ impl BettingService {
 
         let contract = WhizyPredictionMarket::new(address, client.clone());
 
-        info!(
-            "Submitting bet transaction: market={}, position={}, amount={} ({} USDC)",
-            market_id,
-            position,
-            amount,
-            raw_to_usdc(amount)
-        );
-
         let market_data = contract
             .markets(U256::from(market_id))
             .call()
@@ -214,66 +830,59 @@ impl BettingService {
 
         let usdc_contract = IERC20::new(usdc_address, client.clone());
 
-        let wallet_address = client.address();
         let allowance = usdc_contract
-            .allowance(wallet_address, address)
+            .allowance(signer.address(), address)
             .call()
             .await
             .map_err(|e| AppError::Internal(format!("Failed to check allowance: {}", e)))?;
 
         info!("Current USDC allowance: {} (need: {})", allowance, amount);
 
-        if allowance < U256::from(amount) {
-            info!("Insufficient allowance, approving USDC...");
-
-            let approve_call = usdc_contract.approve(address, U256::from(u64::MAX));
-            let approve_pending = approve_call
-                .send()
-                .await
-                .map_err(|e| AppError::Internal(format!("Failed to send approval: {}", e)))?;
-
-            let tx_hash = approve_pending.tx_hash();
-            info!("Approval transaction sent: {:?}", tx_hash);
+        if allowance >= U256::from(amount) {
+            return Ok(());
+        }
 
-            approve_pending
-                .await
-                .map_err(|e| AppError::Internal(format!("Approval transaction failed: {}", e)))?;
+        info!("Insufficient allowance, approving USDC...");
 
-            info!("✅ USDC approved successfully");
-        } else {
-            info!("✅ Sufficient USDC allowance already exists");
-        }
+        let approval = signer
+            .send(usdc_contract.approve(address, U256::from(u64::MAX)).tx)
+            .await?;
+        info!("Approval transaction sent: {:?}", approval.hash);
 
-        let pending_tx = contract.place_bet(U256::from(market_id), position, U256::from(amount));
+        let mut tx = self.db.pool().begin().await?;
+        record_transaction(&mut tx, "approve", &approval, None, None).await?;
+        tx.commit().await?;
 
-        let pending_tx = pending_tx
-            .send()
+        PendingTransaction::new(approval.hash, client.provider())
             .await
-            .map_err(|e| AppError::Internal(format!("Failed to send transaction: {}", e)))?;
+            .map_err(|e| AppError::Internal(format!("Approval transaction failed: {}", e)))?;
 
-        let tx_hash = format!("{:?}", pending_tx.tx_hash());
-        info!("Transaction sent: {}", tx_hash);
+        info!("✅ USDC approved successfully");
+        Ok(())
+    }
 
-        match pending_tx.await {
-            Ok(Some(receipt)) => {
-                info!(
-                    "Transaction confirmed in block: {}",
-                    receipt.block_number.unwrap_or_default()
-                );
-            }
-            Ok(None) => {
-                error!("Transaction returned no receipt");
-                return Err(AppError::Internal(
-                    "Transaction returned no receipt".to_string(),
-                ));
-            }
-            Err(e) => {
-                error!("Transaction failed: {}", e);
-                return Err(AppError::Internal(format!("Transaction failed: {}", e)));
-            }
-        }
+    pub(crate) fn place_bet_call(
+        signer: &SignerService,
+        contract_address: &str,
+        market_id: u64,
+        position: bool,
+        amount: u64,
+    ) -> Result<TypedTransaction, AppError> {
+        let address: Address = contract_address
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
 
-        Ok(tx_hash)
+        info!(
+            "Building bet transaction: market={}, position={}, amount={} ({} USDC)",
+            market_id,
+            position,
+            amount,
+            raw_to_usdc(amount)
+        );
+
+        Ok(WhizyPredictionMarket::new(address, signer.client())
+            .place_bet(U256::from(market_id), position, U256::from(amount))
+            .tx)
     }
 
     async fn calculate_bet_odds(
@@ -294,98 +903,310 @@ impl BettingService {
         .await
         .map_err(|e| AppError::Internal(format!("Failed to fetch market: {}", e)))?;
 
-        let amount_usdc = raw_to_usdc(amount_raw);
+        let quote = quote_bet(
+            bigdecimal_to_raw(&market.yesPoolSize),
+            bigdecimal_to_raw(&market.noPoolSize),
+            position,
+            amount_raw,
+            0,
+            0,
+            0,
+        );
+
+        Ok(quote.odds)
+    }
+}
+
+#[derive(Debug)]
+struct MarketRecord {
+    id: String,
+    blockchain_market_id: Option<i64>,
+    status: String,
+    end_date: chrono::NaiveDateTime,
+}
 
-        let yes_pool_raw: f64 = market.yesPoolSize.to_string().parse::<f64>().unwrap_or(0.0);
-        let no_pool_raw: f64 = market.noPoolSize.to_string().parse::<f64>().unwrap_or(0.0);
+async fn confirm_intent(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    intent_id: &str,
+    log: &Log,
+    blocks: &HashMap<u64, (u64, Option<String>)>,
+    retry: &EventRetryPolicy,
+) -> Result<String, AppError> {
+    let bet_id = format!(
+        "{:?}-{}",
+        log.transaction_hash.unwrap_or_default(),
+        log.log_index.unwrap_or_default()
+    );
 
-        let yes_pool = raw_to_usdc(yes_pool_raw as u64);
-        let no_pool = raw_to_usdc(no_pool_raw as u64);
+    write_events(
+        tx,
+        IndexedContractKind::PredictionMarket,
+        std::slice::from_ref(log),
+        blocks,
+        retry,
+    )
+    .await?;
 
-        let (final_yes_pool, final_no_pool) = if position {
-            (yes_pool + amount_usdc, no_pool)
-        } else {
-            (yes_pool, no_pool + amount_usdc)
-        };
+    sqlx::query!(
+        r#"
+        UPDATE bet_intents
+        SET status = 'confirmed', "betId" = $2, error = NULL
+        WHERE id = $1 AND status = 'submitted'
+        "#,
+        intent_id,
+        bet_id
+    )
+    .execute(&mut **tx)
+    .await?;
 
-        let total_pool = final_yes_pool + final_no_pool;
+    Ok(bet_id)
+}
 
-        let raw_odds = if position {
-            if final_yes_pool > 0.0 {
-                total_pool / final_yes_pool
-            } else {
-                1.0
-            }
-        } else if final_no_pool > 0.0 {
-            total_pool / final_no_pool
-        } else {
-            1.0
-        };
+fn decode_signed_transaction(
+    raw: &Bytes,
+) -> Result<(H256, Address, Option<Address>, Bytes), AppError> {
+    let (tx, signature) = TypedTransaction::decode_signed(&ethers::utils::rlp::Rlp::new(raw))
+        .map_err(|e| AppError::BadRequest(format!("Invalid signed transaction: {}", e)))?;
+    let from = signature
+        .recover(tx.sighash())
+        .map_err(|e| AppError::BadRequest(format!("Invalid transaction signature: {}", e)))?;
+    let to = tx.to().and_then(|to| to.as_address().copied());
+    let input = tx.data().cloned().unwrap_or_default();
 
-        let odds = raw_odds.max(1.0);
+    Ok((tx.hash(&signature), from, to, input))
+}
 
-        Ok(odds)
+fn validate_place_bet(
+    input: &Bytes,
+    blockchain_market_id: i64,
+    position: bool,
+    amount_raw: u64,
+) -> Result<(), AppError> {
+    let call = PlaceBetCall::decode(input)
+        .map_err(|_| AppError::BadRequest("Transaction is not a placeBet call".to_string()))?;
+    if call.market_id != U256::from(blockchain_market_id as u64)
+        || call.is_yes != position
+        || call.amount != U256::from(amount_raw)
+    {
+        return Err(AppError::BadRequest(
+            "placeBet arguments do not match the bet intent".to_string(),
+        ));
     }
 
-    async fn update_market_pools(
-        &self,
-        market_id: &str,
-        position: bool,
-        amount_raw: u64,
-    ) -> Result<(), AppError> {
-        let amount_decimal = raw_to_bigdecimal(amount_raw);
-
-        if position {
-            sqlx::query!(
-                r#"
-                UPDATE markets_extended
-                SET "yesPoolSize" = "yesPoolSize" + $1,
-                    "totalPoolSize" = "totalPoolSize" + $1,
-                    volume = "yesPoolSize" + "noPoolSize" + $1,
-                    "countYes" = "countYes" + 1,
-                    "updatedAt" = NOW()
-                WHERE id = $2
-                "#,
-                amount_decimal,
-                market_id
-            )
-            .execute(self.db.pool())
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to update market pools: {}", e)))?;
-        } else {
-            sqlx::query!(
-                r#"
-                UPDATE markets_extended
-                SET "noPoolSize" = "noPoolSize" + $1,
-                    "totalPoolSize" = "totalPoolSize" + $1,
-                    volume = "yesPoolSize" + "noPoolSize" + $1,
-                    "countNo" = "countNo" + 1,
-                    "updatedAt" = NOW()
-                WHERE id = $2
-                "#,
-                amount_decimal,
-                market_id
-            )
-            .execute(self.db.pool())
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to update market pools: {}", e)))?;
+    Ok(())
+}
+
+fn parse_address(address: &str) -> Result<Address, AppError> {
+    address
+        .parse()
+        .map_err(|_| AppError::BadRequest(format!("Invalid address {}", address)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+
+    fn bet_placed_log(market_id: u64, user: Address, amount: u64, shares: u64) -> Log {
+        Log {
+            topics: vec![
+                BetPlacedFilter::signature(),
+                H256::from_low_u64_be(market_id),
+                H256::from(user),
+            ],
+            data: ethers::abi::encode(&[
+                ethers::abi::Token::Bool(true),
+                ethers::abi::Token::Uint(U256::from(amount)),
+                ethers::abi::Token::Uint(U256::from(shares)),
+            ])
+            .into(),
+            block_number: Some(U64::from(42)),
+            transaction_hash: Some(H256::repeat_byte(0xbe)),
+            log_index: Some(U256::from(1)),
+            ..Default::default()
         }
+    }
 
-        info!(
-            "Updated market {} pools: position={}, amount={} ({} USDC)",
-            market_id,
-            position,
-            amount_raw,
-            raw_to_usdc(amount_raw)
-        );
+    fn place_bet_input(market_id: u64, is_yes: bool, amount: U256) -> Bytes {
+        PlaceBetCall {
+            market_id: U256::from(market_id),
+            is_yes,
+            amount,
+        }
+        .encode()
+        .into()
+    }
 
-        Ok(())
+    #[test]
+    fn test_signed_transaction_is_decoded_with_its_sender() {
+        let wallet: LocalWallet =
+            "0x0000000000000000000000000000000000000000000000000000000000000001"
+                .parse::<LocalWallet>()
+                .unwrap()
+                .with_chain_id(296u64);
+        let contract: Address = "0x98A593E804C70a3fe039f91fF26f31B26A181960"
+            .parse()
+            .unwrap();
+        let input = place_bet_input(3, true, U256::from(5_000_000));
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(contract)
+            .data(input.clone())
+            .nonce(4)
+            .gas(200_000)
+            .max_fee_per_gas(10)
+            .max_priority_fee_per_gas(1)
+            .chain_id(296)
+            .into();
+        let signature = wallet.sign_transaction_sync(&tx).unwrap();
+
+        let (hash, from, to, decoded_input) =
+            decode_signed_transaction(&tx.rlp_signed(&signature)).unwrap();
+
+        assert_eq!(hash, tx.hash(&signature));
+        assert_eq!(from, wallet.address());
+        assert_eq!(to, Some(contract));
+        assert_eq!(decoded_input, input);
+        assert!(decode_signed_transaction(&Bytes::from(vec![0xde, 0xad])).is_err());
     }
-}
 
-#[derive(Debug)]
-struct MarketRecord {
-    id: String,
-    blockchain_market_id: Option<i64>,
-    status: String,
+    #[test]
+    fn test_place_bet_arguments_must_match_intent() {
+        let amount = 5_000_000u64;
+
+        assert!(validate_place_bet(
+            &place_bet_input(3, true, U256::from(amount)),
+            3,
+            true,
+            amount
+        )
+        .is_ok());
+        assert!(validate_place_bet(
+            &place_bet_input(4, true, U256::from(amount)),
+            3,
+            true,
+            amount
+        )
+        .is_err());
+        assert!(validate_place_bet(
+            &place_bet_input(3, false, U256::from(amount)),
+            3,
+            true,
+            amount
+        )
+        .is_err());
+        assert!(validate_place_bet(
+            &place_bet_input(3, true, U256::from(amount + 1)),
+            3,
+            true,
+            amount
+        )
+        .is_err());
+        assert!(validate_place_bet(&Bytes::from(vec![0x12, 0x34]), 3, true, amount).is_err());
+    }
+
+    #[test]
+    fn test_place_bet_amount_is_compared_in_full() {
+        let amount = 5_000_000u64;
+        let overflowing = (U256::one() << 64) + U256::from(amount);
+
+        assert_eq!(overflowing.low_u64(), amount);
+        assert!(
+            validate_place_bet(&place_bet_input(3, true, overflowing), 3, true, amount).is_err()
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_receipt_recorded_bet_is_projected(pool: sqlx::PgPool) {
+        let user: Address = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+            .parse()
+            .unwrap();
+        let address = ethers::utils::to_checksum(&user, None);
+
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, address) VALUES ('user-1', $1)
+            "#,
+        )
+        .bind(&address)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO markets_extended (id, "blockchainMarketId", question, "endDate")
+            VALUES ('market-1', 3, 'Will it rain?', NOW() + INTERVAL '1 day')
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO bet_intents (
+                id, "userId", "userAddress", "marketId", "blockchainMarketId", position, amount,
+                status, "expiresAt"
+            )
+            VALUES ('intent-1', 'user-1', $1, 'market-1', 3, true, 5, 'submitted', NOW())
+            "#,
+        )
+        .bind(&address)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let log = bet_placed_log(3, user, 5_000_000, 4_900_000);
+        let blocks = HashMap::from([(42, (1_700_000_000, None))]);
+
+        let mut tx = pool.begin().await.unwrap();
+        let bet_id = confirm_intent(
+            &mut tx,
+            "intent-1",
+            &log,
+            &blocks,
+            &EventRetryPolicy {
+                max_attempts: 5,
+                backoff_secs: 30,
+            },
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        let bet = sqlx::query!(
+            r#"SELECT status, amount, shares FROM bets_extended WHERE id = $1"#,
+            bet_id
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(bet.status, "active");
+        assert_eq!(bet.amount, Some(BigDecimal::from(5_000_000)));
+        assert_eq!(bet.shares, Some(BigDecimal::from(4_900_000)));
+
+        let market = sqlx::query!(
+            r#"SELECT "yesPoolSize" as yes_pool, "countYes" as count_yes FROM markets_extended WHERE id = 'market-1'"#
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(market.yes_pool, BigDecimal::from(5_000_000));
+        assert_eq!(market.count_yes, 1);
+
+        let intent = sqlx::query!(
+            r#"SELECT status, "betId" as bet_id FROM bet_intents WHERE id = 'intent-1'"#
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(intent.status, "confirmed");
+        assert_eq!(intent.bet_id, Some(bet_id.clone()));
+
+        let event_status: String =
+            sqlx::query_scalar(r#"SELECT status FROM blockchain_events WHERE "blockchainId" = $1"#)
+                .bind(&bet_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(event_status, "processed");
+    }
 }
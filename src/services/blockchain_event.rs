@@ -0,0 +1,299 @@
+use tracing::{error, warn};
+
+use crate::{
+    config::Config,
+    db::Database,
+    error::{AppError, Result},
+    models::BlockchainEvent,
+};
+
+const EVENT_COLUMNS: &str = r#"id, "eventType", "blockchainId", "blockNumber", "transactionHash", status, attempts, "lastError", "nextRetryAt", "processedAt", data, "createdAt", "updatedAt""#;
+
+pub const EVENT_STATUSES: [&str; 4] = ["pending", "processed", "dead_letter", "discarded"];
+const RETRYABLE_STATUSES: [&str; 2] = ["dead_letter", "discarded"];
+const DISCARDABLE_STATUSES: [&str; 2] = ["pending", "dead_letter"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct EventRetryPolicy {
+    pub max_attempts: i32,
+    pub backoff_secs: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EventFailure {
+    Retry { delay_secs: u64 },
+    DeadLetter,
+}
+
+impl EventRetryPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_attempts: config.event_max_attempts.max(1),
+            backoff_secs: config.event_retry_backoff_secs,
+        }
+    }
+
+    pub fn after_failure(&self, attempts: i32) -> EventFailure {
+        if attempts + 1 >= self.max_attempts {
+            return EventFailure::DeadLetter;
+        }
+
+        let factor = 1u64.checked_shl(attempts.max(0) as u32).unwrap_or(u64::MAX);
+        EventFailure::Retry {
+            delay_secs: self.backoff_secs.saturating_mul(factor),
+        }
+    }
+}
+
+impl EventFailure {
+    pub fn status(&self) -> &'static str {
+        match self {
+            EventFailure::Retry { .. } => "pending",
+            EventFailure::DeadLetter => "dead_letter",
+        }
+    }
+
+    pub fn delay_secs(&self) -> u64 {
+        match self {
+            EventFailure::Retry { delay_secs } => *delay_secs,
+            EventFailure::DeadLetter => 0,
+        }
+    }
+}
+
+pub struct BlockchainEventService {
+    db: Database,
+}
+
+impl BlockchainEventService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn list_events(
+        &self,
+        status: Option<&str>,
+        event_type: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<BlockchainEvent>> {
+        let events = sqlx::query_as::<_, BlockchainEvent>(&format!(
+            r#"
+            SELECT {}
+            FROM blockchain_events
+            WHERE ($1::text IS NULL OR status = $1)
+              AND ($2::text IS NULL OR "eventType" = $2)
+            ORDER BY "updatedAt" DESC
+            LIMIT $3
+            "#,
+            EVENT_COLUMNS
+        ))
+        .bind(status)
+        .bind(event_type)
+        .bind(limit.clamp(1, 500))
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(events)
+    }
+
+    pub async fn get_event(&self, id: &str) -> Result<BlockchainEvent> {
+        sqlx::query_as::<_, BlockchainEvent>(&format!(
+            "SELECT {} FROM blockchain_events WHERE id = $1",
+            EVENT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Event {} not found", id)))
+    }
+
+    pub async fn retry_event(&self, id: &str) -> Result<BlockchainEvent> {
+        let updated = sqlx::query!(
+            r#"
+            UPDATE blockchain_events
+            SET status = 'pending', processed = false, attempts = 0, "lastError" = NULL,
+                "nextRetryAt" = NOW()
+            WHERE id = $1 AND status = ANY($2)
+            "#,
+            id,
+            &RETRYABLE_STATUSES.map(String::from)
+        )
+        .execute(self.db.pool())
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::BadRequest(format!(
+                "Event {} is not dead-lettered or discarded",
+                id
+            )));
+        }
+
+        warn!("🔁 Event {} requeued by an admin", id);
+        self.get_event(id).await
+    }
+
+    pub async fn discard_event(&self, id: &str) -> Result<BlockchainEvent> {
+        let updated = sqlx::query!(
+            r#"
+            UPDATE blockchain_events
+            SET status = 'discarded', processed = true
+            WHERE id = $1 AND status = ANY($2)
+            "#,
+            id,
+            &DISCARDABLE_STATUSES.map(String::from)
+        )
+        .execute(self.db.pool())
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::BadRequest(format!(
+                "Event {} is already processed or discarded",
+                id
+            )));
+        }
+
+        warn!("🗑️ Event {} discarded by an admin", id);
+        self.get_event(id).await
+    }
+
+    pub async fn mark_processed(&self, id: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE blockchain_events
+            SET status = 'processed', processed = true, attempts = attempts + 1,
+                "lastError" = NULL, "processedAt" = NOW()
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn record_failure(&self, config: &Config, id: &str, reason: &str) -> Result<bool> {
+        let mut tx = self.db.pool().begin().await?;
+
+        let attempts = sqlx::query_scalar!(
+            "SELECT attempts FROM blockchain_events WHERE id = $1 FOR UPDATE",
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let failure = EventRetryPolicy::from_config(config).after_failure(attempts);
+
+        sqlx::query!(
+            r#"
+            UPDATE blockchain_events
+            SET attempts = attempts + 1, "lastError" = $2, status = $3,
+                "nextRetryAt" = NOW() + make_interval(secs => $4)
+            WHERE id = $1
+            "#,
+            id,
+            reason,
+            failure.status(),
+            failure.delay_secs() as f64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        if failure != EventFailure::DeadLetter {
+            warn!("⚠️ Event {} will be retried: {}", id, reason);
+            return Ok(false);
+        }
+
+        error!("❌ Event {} moved to dead letter: {}", id, reason);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: EventRetryPolicy = EventRetryPolicy {
+        max_attempts: 4,
+        backoff_secs: 10,
+    };
+
+    #[test]
+    fn test_failed_events_back_off_exponentially() {
+        assert_eq!(
+            POLICY.after_failure(0),
+            EventFailure::Retry { delay_secs: 10 }
+        );
+        assert_eq!(
+            POLICY.after_failure(1),
+            EventFailure::Retry { delay_secs: 20 }
+        );
+        assert_eq!(
+            POLICY.after_failure(2),
+            EventFailure::Retry { delay_secs: 40 }
+        );
+        assert_eq!(POLICY.after_failure(2).status(), "pending");
+    }
+
+    #[test]
+    fn test_events_dead_letter_at_max_attempts() {
+        assert_eq!(POLICY.after_failure(3), EventFailure::DeadLetter);
+        assert_eq!(POLICY.after_failure(3).status(), "dead_letter");
+
+        let single_attempt = EventRetryPolicy {
+            max_attempts: 1,
+            backoff_secs: 10,
+        };
+        assert_eq!(single_attempt.after_failure(0), EventFailure::DeadLetter);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_retry_and_discard_accept_only_their_states(pool: sqlx::PgPool) {
+        for status in EVENT_STATUSES {
+            sqlx::query(
+                r#"
+                INSERT INTO blockchain_events (
+                    id, "eventType", "blockchainId", "blockNumber", "blockTimestamp",
+                    "transactionHash", status, attempts
+                )
+                VALUES ($1, 'BetPlaced', $1, 1, 0, '0x1', $1, 3)
+                "#,
+            )
+            .bind(status)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        let events = BlockchainEventService::new(Database::from_pool(pool.clone()));
+
+        for status in EVENT_STATUSES {
+            let retried = events.retry_event(status).await;
+            assert_eq!(
+                retried.is_ok(),
+                RETRYABLE_STATUSES.contains(&status),
+                "retry of {}",
+                status
+            );
+            if let Ok(event) = retried {
+                assert_eq!(event.status, "pending");
+                assert_eq!(event.attempts, 0);
+            }
+        }
+
+        sqlx::query("UPDATE blockchain_events SET status = id")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for status in EVENT_STATUSES {
+            let discarded = events.discard_event(status).await;
+            assert_eq!(
+                discarded.is_ok(),
+                DISCARDABLE_STATUSES.contains(&status),
+                "discard of {}",
+                status
+            );
+        }
+    }
+}
@@ -1,8 +1,17 @@
 use crate::{
+    config::Config,
     db::Database,
     error::{AppError, Result},
+    models::{ReconciliationDrift, ReconciliationRun},
+    services::{
+        blockchain_yield::IWhizyPredictionMarket,
+        outbox::{enqueue, ContractCall},
+        rpc, MulticallReader, SignerService,
+    },
 };
-use ethers::prelude::*;
+use bigdecimal::BigDecimal;
+use ethers::{prelude::*, types::transaction::eip2718::TypedTransaction};
+use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
@@ -13,55 +22,397 @@ abigen!(
         function markets(uint256) external view returns (uint256 id, string question, uint256 endTime, address token, address vault, uint256 totalYesShares, uint256 totalNoShares, bool resolved, bool outcome, uint8 status)
         function nextMarketId() external view returns (uint256)
         event MarketCreated(uint256 indexed marketId, string question, uint256 endTime, address token, address vault)
+        event BetPlaced(uint256 indexed marketId, address indexed user, bool position, uint256 amount, uint256 shares)
+        event MarketResolved(uint256 indexed marketId, bool outcome)
+        event WinningsClaimed(uint256 indexed marketId, address indexed user, uint256 winningAmount)
+        event MarketVaultRebalanced(uint256 indexed marketId, uint256 amount)
+        event Paused(address account)
+        event Unpaused(address account)
+        event OwnershipTransferred(address indexed previousOwner, address indexed newOwner)
     ]"#,
 );
 
+pub struct OnChainMarket {
+    pub blockchain_market_id: u64,
+    pub tx_hash: H256,
+    pub block_number: u64,
+}
+
 pub struct BlockchainSyncService {
     db: Database,
 }
 
+#[derive(Debug, Clone)]
+struct StoredMarket {
+    total_yes_shares: String,
+    total_no_shares: String,
+    total_pool_size: String,
+    status: String,
+    result: Option<bool>,
+    vault_address: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct ChainMarket {
+    total_yes_shares: U256,
+    total_no_shares: U256,
+    pool_size: U256,
+    resolved: bool,
+    outcome: bool,
+    vault: Address,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct MarketDrift {
+    field: &'static str,
+    database_value: Option<String>,
+    chain_value: Option<String>,
+    safe: bool,
+}
+
+const RECONCILIATION_RUN_COLUMNS: &str = r#"id, status, "marketsChecked", "marketsDrifted", "marketsUnreadable", "fieldsHealed", error, "startedAt", "completedAt""#;
+
 impl BlockchainSyncService {
     pub fn new(db: Database) -> Self {
         Self { db }
     }
 
-    pub async fn run_full_sync(&self) -> Result<()> {
+    pub async fn run_full_sync(&self, config: &Config) -> Result<ReconciliationRun> {
+        self.run_reconciliation(config, None).await
+    }
+
+    pub async fn reconcile_market(
+        &self,
+        config: &Config,
+        market_id: &str,
+    ) -> Result<ReconciliationRun> {
+        self.run_reconciliation(config, Some(market_id)).await
+    }
+
+    async fn run_reconciliation(
+        &self,
+        config: &Config,
+        market_id: Option<&str>,
+    ) -> Result<ReconciliationRun> {
+        let run_id = uuid::Uuid::new_v4().to_string();
+        sqlx::query!("INSERT INTO reconciliation_runs (id) VALUES ($1)", run_id)
+            .execute(self.db.pool())
+            .await?;
+
+        if let Err(e) = self.reconcile(&run_id, config, market_id).await {
+            sqlx::query!(
+                r#"
+                UPDATE reconciliation_runs
+                SET status = 'failed', error = $2, "completedAt" = NOW()
+                WHERE id = $1
+                "#,
+                run_id,
+                e.to_string()
+            )
+            .execute(self.db.pool())
+            .await?;
+        }
+
+        self.get_reconciliation_run(&run_id).await
+    }
+
+    async fn reconcile(
+        &self,
+        run_id: &str,
+        config: &Config,
+        market_id: Option<&str>,
+    ) -> Result<()> {
+        let provider = rpc::provider(config)?;
+        let client = Arc::new(provider);
+        let address: Address = config
+            .whizy_prediction_market_addr
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
+        let contract = IWhizyPredictionMarket::new(address, client.clone());
+
+        let markets = sqlx::query!(
+            r#"
+            SELECT id, "blockchainMarketId" as "blockchain_market_id!",
+                   "totalYesShares"::text as "total_yes_shares!",
+                   "totalNoShares"::text as "total_no_shares!",
+                   "totalPoolSize"::text as "total_pool_size!",
+                   status, result, "vaultAddress" as vault_address
+            FROM markets_extended
+            WHERE "blockchainMarketId" IS NOT NULL AND ($1::text IS NULL OR id = $1)
+            ORDER BY "blockchainMarketId"
+            "#,
+            market_id
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let on_chain_markets = MulticallReader::new(client, config)?
+            .read(
+                markets
+                    .iter()
+                    .map(|market| contract.get_market_info(U256::from(market.blockchain_market_id)))
+                    .collect(),
+            )
+            .await;
+
+        let mut checked = 0;
+        let mut drifted = 0;
+        let mut unreadable = 0;
+        let mut healed = 0;
+
+        for (market, on_chain) in markets.into_iter().zip(on_chain_markets) {
+            let Some((on_chain, total_assets, current_yield, _)) = on_chain else {
+                warn!(
+                    "⚠️ [Reconciliation] Failed to read market {} from chain",
+                    market.blockchain_market_id
+                );
+                unreadable += 1;
+                continue;
+            };
+            checked += 1;
+
+            let drifts = compare_market(
+                &StoredMarket {
+                    total_yes_shares: market.total_yes_shares,
+                    total_no_shares: market.total_no_shares,
+                    total_pool_size: market.total_pool_size,
+                    status: market.status,
+                    result: market.result,
+                    vault_address: market.vault_address,
+                },
+                &ChainMarket {
+                    total_yes_shares: on_chain.5,
+                    total_no_shares: on_chain.6,
+                    pool_size: total_assets.saturating_sub(current_yield),
+                    resolved: on_chain.7,
+                    outcome: on_chain.8,
+                    vault: on_chain.4,
+                },
+            );
+            if drifts.is_empty() {
+                continue;
+            }
+            drifted += 1;
+
+            let mut tx = self.db.pool().begin().await?;
+
+            for drift in &drifts {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO reconciliation_drifts (
+                        "runId", "marketId", "blockchainMarketId", field, "databaseValue", "chainValue", healed
+                    )
+                    VALUES ($1, $2, $3, $4, $5, $6, $7)
+                    "#,
+                    run_id,
+                    market.id,
+                    market.blockchain_market_id,
+                    drift.field,
+                    drift.database_value,
+                    drift.chain_value,
+                    drift.safe
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            let healed_vault = drifts
+                .iter()
+                .find(|drift| drift.safe && drift.field == "vaultAddress")
+                .and_then(|drift| drift.chain_value.clone());
+            let heals = drifts.iter().filter(|drift| drift.safe).count();
+            if heals > 0 {
+                sqlx::query!(
+                    r#"
+                    UPDATE markets_extended
+                    SET "vaultAddress" = COALESCE($2, "vaultAddress"), "updatedAt" = NOW()
+                    WHERE id = $1
+                    "#,
+                    market.id,
+                    healed_vault
+                )
+                .execute(&mut *tx)
+                .await?;
+                healed += heals;
+            }
+
+            tx.commit().await?;
+
+            warn!(
+                "🩺 [Reconciliation] Market {} (blockchain ID {}) drifted on {} ({} healed)",
+                market.id,
+                market.blockchain_market_id,
+                drifts
+                    .iter()
+                    .map(|drift| drift.field)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                heals
+            );
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE reconciliation_runs
+            SET status = 'completed', "marketsChecked" = $2, "marketsDrifted" = $3,
+                "marketsUnreadable" = $4, "fieldsHealed" = $5, "completedAt" = NOW()
+            WHERE id = $1
+            "#,
+            run_id,
+            checked,
+            drifted,
+            unreadable,
+            healed as i32
+        )
+        .execute(self.db.pool())
+        .await?;
+
         Ok(())
     }
 
-    pub async fn sync_markets_to_blockchain(
+    pub async fn get_reconciliation_run(&self, run_id: &str) -> Result<ReconciliationRun> {
+        sqlx::query_as::<_, ReconciliationRun>(&format!(
+            "SELECT {} FROM reconciliation_runs WHERE id = $1",
+            RECONCILIATION_RUN_COLUMNS
+        ))
+        .bind(run_id)
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Reconciliation run {} not found", run_id)))
+    }
+
+    pub async fn list_reconciliation_runs(&self, limit: i64) -> Result<Vec<ReconciliationRun>> {
+        let runs = sqlx::query_as::<_, ReconciliationRun>(&format!(
+            r#"SELECT {} FROM reconciliation_runs ORDER BY "startedAt" DESC LIMIT $1"#,
+            RECONCILIATION_RUN_COLUMNS
+        ))
+        .bind(limit.clamp(1, 100))
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(runs)
+    }
+
+    pub async fn list_reconciliation_drifts(
         &self,
+        run_id: &str,
+    ) -> Result<Vec<ReconciliationDrift>> {
+        let drifts = sqlx::query_as::<_, ReconciliationDrift>(
+            r#"
+            SELECT id, "marketId", "blockchainMarketId", field, "databaseValue", "chainValue", healed
+            FROM reconciliation_drifts
+            WHERE "runId" = $1
+            ORDER BY "blockchainMarketId", id
+            "#,
+        )
+        .bind(run_id)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(drifts)
+    }
+
+    pub fn create_market_call(
+        signer: &SignerService,
         contract_address: &str,
-        rpc_url: &str,
-        private_key: &str,
         usdc_address: &str,
-        chain_id: u64,
-    ) -> Result<usize> {
-        info!("🔄 Starting blockchain sync for markets...");
+        question: &str,
+        end_time: u64,
+    ) -> Result<TypedTransaction> {
+        let address: Address = contract_address
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
+        let token_address: Address = usdc_address
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid token address: {}", e)))?;
 
-        let provider = Provider::<Http>::try_from(rpc_url)
-            .map_err(|e| AppError::Internal(format!("Failed to connect to RPC: {}", e)))?;
+        Ok(WhizyPredictionMarket::new(address, signer.client())
+            .create_market(question.to_string(), U256::from(end_time), token_address)
+            .tx)
+    }
 
-        let wallet: LocalWallet = private_key
-            .parse::<LocalWallet>()
-            .map_err(|e| AppError::Internal(format!("Invalid private key: {}", e)))?
-            .with_chain_id(chain_id);
+    pub async fn wait_for_market_created(
+        &self,
+        config: &Config,
+        tx_hash: H256,
+    ) -> Result<OnChainMarket> {
+        let provider = rpc::provider(config)?;
 
-        let client = SignerMiddleware::new(provider, wallet);
-        let client = Arc::new(client);
+        let address: Address = config
+            .whizy_prediction_market_addr
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
 
-        let address: Address = contract_address
+        let receipt = PendingTransaction::new(tx_hash, &provider)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch receipt: {}", e)))?
+            .ok_or_else(|| AppError::Internal(format!("Transaction {:?} was dropped", tx_hash)))?;
+
+        if receipt.status != Some(U64::from(1)) {
+            return Err(AppError::BadRequest(format!(
+                "createMarket transaction {:?} reverted",
+                tx_hash
+            )));
+        }
+
+        let contract = WhizyPredictionMarket::new(address, Arc::new(provider.clone()));
+
+        let blockchain_market_id = receipt
+            .logs
+            .iter()
+            .filter(|log| log.address == address)
+            .find_map(|log| {
+                contract
+                    .decode_event::<MarketCreatedFilter>(
+                        "MarketCreated",
+                        log.topics.clone(),
+                        log.data.clone(),
+                    )
+                    .ok()
+            })
+            .map(|event| event.market_id.as_u64())
+            .ok_or_else(|| {
+                AppError::Internal(format!(
+                    "No MarketCreated event in transaction {:?}",
+                    tx_hash
+                ))
+            })?;
+
+        info!(
+            "✅ Market {} created on blockchain in block {}",
+            blockchain_market_id,
+            receipt.block_number.unwrap_or_default()
+        );
+
+        Ok(OnChainMarket {
+            blockchain_market_id,
+            tx_hash,
+            block_number: receipt.block_number.unwrap_or_default().as_u64(),
+        })
+    }
+
+    pub async fn sync_markets_to_blockchain(&self, config: &Config) -> Result<usize> {
+        info!("🔄 Starting blockchain sync for markets...");
+
+        let signer = SignerService::shared()?;
+
+        let address: Address = config
+            .whizy_prediction_market_addr
             .parse()
             .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
 
-        let contract = WhizyPredictionMarket::new(address, client);
+        let contract = WhizyPredictionMarket::new(address, signer.client());
 
         let markets = sqlx::query!(
             r#"
             SELECT id, "marketId", question, description, "endDate"
-            FROM markets_extended
+            FROM markets_extended m
             WHERE "blockchainMarketId" IS NULL
             AND status = 'active'
+            AND NOT EXISTS (
+                SELECT 1 FROM outbox o
+                WHERE o."marketId" = m.id AND o.kind = 'create_market' AND o.status <> 'failed'
+            )
             ORDER BY "createdAt" ASC
             "#
         )
@@ -76,10 +427,6 @@ impl BlockchainSyncService {
 
         info!("📋 Found {} markets to sync to blockchain", markets.len());
 
-        let token_address: Address = usdc_address
-            .parse()
-            .map_err(|e| AppError::Internal(format!("Invalid token address: {}", e)))?;
-
         let next_market_id = contract
             .next_market_id()
             .call()
@@ -92,6 +439,14 @@ impl BlockchainSyncService {
             total_blockchain_markets
         );
 
+        let blockchain_markets = MulticallReader::new(signer.client(), config)?
+            .read(
+                (0..total_blockchain_markets)
+                    .map(|blockchain_id| contract.markets(U256::from(blockchain_id)))
+                    .collect(),
+            )
+            .await;
+
         let mut synced_count = 0;
 
         for market in markets {
@@ -100,245 +455,92 @@ impl BlockchainSyncService {
 
             let end_time = end_date.and_utc().timestamp() as u64;
 
-            let mut found_existing = false;
-            for blockchain_id in 0..total_blockchain_markets {
-                if let Ok(blockchain_market) =
-                    contract.markets(U256::from(blockchain_id)).call().await
-                {
-                    let blockchain_question = blockchain_market.1.trim();
-                    let db_question = question.trim();
-
+            let db_question = question.trim();
+            let existing_id = blockchain_markets.iter().enumerate().find_map(
+                |(blockchain_id, blockchain_market)| {
+                    let blockchain_question = blockchain_market.as_ref()?.1.trim();
                     let is_match = blockchain_question == db_question
                         || db_question.starts_with(blockchain_question);
+                    is_match.then_some(blockchain_id as u64)
+                },
+            );
+
+            if let Some(blockchain_id) = existing_id {
+                info!(
+                    "⏭️  Market '{}' already exists on blockchain with ID {}. Updating database...",
+                    question.chars().take(60).collect::<String>(),
+                    blockchain_id
+                );
 
-                    if is_match {
-                        info!(
-                            "⏭️  Market '{}' already exists on blockchain with ID {}. Updating database...",
-                            question.chars().take(60).collect::<String>(),
-                            blockchain_id
-                        );
-
-                        match sqlx::query!(
-                            r#"
-                            UPDATE markets_extended
-                            SET "blockchainMarketId" = $1, "updatedAt" = CURRENT_TIMESTAMP
-                            WHERE id = $2 AND "blockchainMarketId" IS NULL
-                            "#,
-                            blockchain_id as i64,
-                            market.id
-                        )
-                        .execute(self.db.pool())
-                        .await
-                        {
-                            Ok(result) => {
-                                if result.rows_affected() > 0 {
-                                    info!(
-                                        "✅ Updated market '{}' with existing blockchain ID: {}",
-                                        question, blockchain_id
-                                    );
-                                    synced_count += 1;
-                                }
-                            }
-                            Err(e) => {
-                                error!("❌ Failed to update DB for market '{}': {}", question, e);
-                            }
+                match sqlx::query!(
+                    r#"
+                    UPDATE markets_extended
+                    SET "blockchainMarketId" = $1, "updatedAt" = CURRENT_TIMESTAMP
+                    WHERE id = $2 AND "blockchainMarketId" IS NULL
+                    "#,
+                    blockchain_id as i64,
+                    market.id
+                )
+                .execute(self.db.pool())
+                .await
+                {
+                    Ok(result) => {
+                        if result.rows_affected() > 0 {
+                            info!(
+                                "✅ Updated market '{}' with existing blockchain ID: {}",
+                                question, blockchain_id
+                            );
+                            synced_count += 1;
                         }
-
-                        found_existing = true;
-                        break;
+                    }
+                    Err(e) => {
+                        error!("❌ Failed to update DB for market '{}': {}", question, e);
                     }
                 }
-            }
 
-            if found_existing {
                 continue;
             }
 
-            let current_next_id = contract.next_market_id().call().await.map_err(|e| {
-                AppError::Internal(format!("Failed to get current market ID: {}", e))
-            })?;
-            let next_blockchain_id = current_next_id.as_u64();
-
-            let check_existing_id = sqlx::query!(
-                r#"SELECT id, question FROM markets_extended WHERE "blockchainMarketId" = $1"#,
-                next_blockchain_id as i64
+            let mut tx = self.db.pool().begin().await?;
+            let queued = enqueue(
+                &mut tx,
+                &ContractCall::CreateMarket {
+                    question: question.clone(),
+                    end_time,
+                },
+                Some(&market.id),
+                None,
+                config.outbox_max_attempts,
             )
-            .fetch_optional(self.db.pool())
-            .await
-            .map_err(|e| {
-                AppError::Internal(format!("Failed to check existing blockchain ID: {}", e))
-            })?;
+            .await?;
+            tx.commit().await?;
 
-            if let Some(existing) = check_existing_id {
-                warn!(
-                    "⚠️  Blockchain ID {} already assigned to market {} ('{}'). Skipping creation of '{}'",
-                    next_blockchain_id,
-                    existing.id,
-                    existing.question.unwrap_or_default().chars().take(50).collect::<String>(),
-                    question.chars().take(50).collect::<String>()
-                );
-                continue;
+            if queued.is_some() {
+                info!("📝 Queued on-chain creation of market: {}", question);
+                synced_count += 1;
             }
-
-            info!("📝 Creating NEW market on blockchain: {}", question);
-
-            match contract
-                .create_market(question.clone(), U256::from(end_time), token_address)
-                .send()
-                .await
-            {
-                Ok(pending_tx) => {
-                    info!("⏳ Transaction sent: {:?}", pending_tx.tx_hash());
-
-                    match pending_tx.await {
-                        Ok(Some(receipt)) => {
-                            info!(
-                                "✅ Transaction confirmed in block: {}",
-                                receipt.block_number.unwrap_or_default()
-                            );
-
-                            let mut blockchain_market_id: Option<u64> = None;
-
-                            for log in receipt.logs {
-                                if let Ok(event) = contract.decode_event::<MarketCreatedFilter>(
-                                    "MarketCreated",
-                                    log.topics,
-                                    log.data,
-                                ) {
-                                    blockchain_market_id = Some(event.market_id.as_u64());
-                                    break;
-                                }
-                            }
-
-                            if let Some(market_id) = blockchain_market_id {
-                                info!("🔍 Verifying market ID {} on blockchain...", market_id);
-
-                                match contract.markets(U256::from(market_id)).call().await {
-                                    Ok(blockchain_market) => {
-                                        let blockchain_question = blockchain_market.1;
-
-                                        if blockchain_question.trim() != question.trim() {
-                                            warn!(
-                                                "⚠️  Question truncated by blockchain! Database: '{}' -> Blockchain: '{}'",
-                                                question.chars().take(100).collect::<String>(),
-                                                blockchain_question
-                                            );
-                                            warn!("   Updating database to match blockchain question...");
-
-                                            if let Err(e) = sqlx::query!(
-                                                r#"UPDATE markets_extended SET question = $1, "updatedAt" = CURRENT_TIMESTAMP WHERE id = $2"#,
-                                                blockchain_question,
-                                                market.id
-                                            )
-                                            .execute(self.db.pool())
-                                            .await {
-                                                error!("Failed to update question in database: {}", e);
-                                            } else {
-                                                info!("   ✅ Database question updated to match blockchain");
-                                            }
-                                        } else {
-                                            info!("✅ Verified market {} on blockchain with matching question", market_id);
-                                        }
-                                    }
-                                    Err(e) => {
-                                        error!(
-                                            "❌ Failed to verify market {} on blockchain: {}",
-                                            market_id, e
-                                        );
-                                        return Ok(synced_count);
-                                    }
-                                }
-
-                                let update_result = sqlx::query(
-                                    r#"
-                                    UPDATE markets_extended
-                                    SET "blockchainMarketId" = $1, "updatedAt" = CURRENT_TIMESTAMP
-                                    WHERE id = $2 AND "blockchainMarketId" IS NULL
-                                    "#,
-                                )
-                                .bind(market_id as i64)
-                                .bind(&market.id)
-                                .execute(self.db.pool())
-                                .await;
-
-                                match update_result {
-                                    Ok(result) => {
-                                        if result.rows_affected() > 0 {
-                                            info!(
-                                                "🎉 Market '{}' synced with blockchain ID: {}",
-                                                question, market_id
-                                            );
-                                            synced_count += 1;
-
-                                            tokio::time::sleep(tokio::time::Duration::from_millis(
-                                                100,
-                                            ))
-                                            .await;
-                                        } else {
-                                            warn!("⚠️  Market '{}' was already updated or no longer eligible", question);
-                                        }
-                                    }
-                                    Err(e) => {
-                                        let error_msg = e.to_string();
-                                        if error_msg.contains("duplicate key")
-                                            || error_msg.contains("blockchainMarketId_key")
-                                        {
-                                            warn!(
-                                                "⚠️  Blockchain ID {} already taken (race condition). Market '{}' will be retried later.",
-                                                market_id, question
-                                            );
-                                        } else {
-                                            error!(
-                                                "❌ Failed to update DB for market '{}': {}",
-                                                question, e
-                                            );
-                                        }
-                                    }
-                                }
-                            } else {
-                                error!("❌ Failed to parse MarketCreated event from transaction receipt");
-                            }
-                        }
-                        Ok(None) => {
-                            error!("❌ Transaction returned no receipt");
-                        }
-                        Err(e) => {
-                            error!("❌ Transaction failed for '{}': {}", question, e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    error!("❌ Failed to send transaction for '{}': {}", question, e);
-                }
-            }
-
-            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
         }
 
         info!(
-            "✅ Blockchain sync complete - {} markets synced",
+            "✅ Blockchain sync complete - {} markets linked or queued",
             synced_count
         );
         Ok(synced_count)
     }
 
-    pub async fn verify_blockchain_sync(
-        &self,
-        contract_address: &str,
-        rpc_url: &str,
-    ) -> Result<usize> {
+    pub async fn verify_blockchain_sync(&self, config: &Config) -> Result<usize> {
         info!("🔍 Verifying all markets against blockchain...");
 
-        let provider = Provider::<Http>::try_from(rpc_url)
-            .map_err(|e| AppError::Internal(format!("Failed to connect to RPC: {}", e)))?;
+        let provider = rpc::provider(config)?;
 
         let client = Arc::new(provider);
 
-        let address: Address = contract_address
+        let address: Address = config
+            .whizy_prediction_market_addr
             .parse()
             .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
 
-        let contract = WhizyPredictionMarket::new(address, client);
+        let contract = WhizyPredictionMarket::new(address, client.clone());
 
         let next_market_id = contract
             .next_market_id()
@@ -349,12 +551,20 @@ impl BlockchainSyncService {
 
         info!("📊 Found {} markets on blockchain", total_markets);
 
+        let blockchain_markets = MulticallReader::new(client, config)?
+            .read(
+                (0..total_markets)
+                    .map(|market_id| contract.markets(U256::from(market_id)))
+                    .collect(),
+            )
+            .await;
+
         let mut verified_count = 0;
         let created_count = 0;
 
-        for market_id in 0..total_markets {
-            match contract.markets(U256::from(market_id)).call().await {
-                Ok(blockchain_market) => {
+        for (market_id, blockchain_market) in blockchain_markets.into_iter().enumerate() {
+            match blockchain_market {
+                Some(blockchain_market) => {
                     let question = blockchain_market.1;
 
                     let existing = sqlx::query!(
@@ -411,8 +621,8 @@ impl BlockchainSyncService {
                         }
                     }
                 }
-                Err(e) => {
-                    error!("❌ Failed to read market {}: {}", market_id, e);
+                None => {
+                    error!("❌ Failed to read market {}", market_id);
                 }
             }
         }
@@ -424,3 +634,176 @@ impl BlockchainSyncService {
         Ok(created_count)
     }
 }
+
+fn compare_market(stored: &StoredMarket, chain: &ChainMarket) -> Vec<MarketDrift> {
+    let mut drifts = Vec::new();
+
+    for (field, stored_shares, chain_shares) in [
+        (
+            "totalYesShares",
+            &stored.total_yes_shares,
+            chain.total_yes_shares,
+        ),
+        (
+            "totalNoShares",
+            &stored.total_no_shares,
+            chain.total_no_shares,
+        ),
+    ] {
+        let matches = match (
+            BigDecimal::from_str(stored_shares),
+            BigDecimal::from_str(&chain_shares.to_string()),
+        ) {
+            (Ok(stored_shares), Ok(chain_shares)) => stored_shares == chain_shares,
+            _ => false,
+        };
+        if !matches {
+            drifts.push(MarketDrift {
+                field,
+                database_value: Some(stored_shares.clone()),
+                chain_value: Some(chain_shares.to_string()),
+                safe: false,
+            });
+        }
+    }
+
+    let pool_matches = match (
+        BigDecimal::from_str(&stored.total_pool_size),
+        BigDecimal::from_str(&chain.pool_size.to_string()),
+    ) {
+        (Ok(stored_pool), Ok(chain_pool)) => stored_pool == chain_pool,
+        _ => false,
+    };
+    if !pool_matches {
+        drifts.push(MarketDrift {
+            field: "totalPoolSize",
+            database_value: Some(stored.total_pool_size.clone()),
+            chain_value: Some(chain.pool_size.to_string()),
+            safe: false,
+        });
+    }
+
+    if !chain.vault.is_zero() {
+        let chain_vault = ethers::utils::to_checksum(&chain.vault, None);
+        let matches = stored
+            .vault_address
+            .as_deref()
+            .is_some_and(|vault| vault.eq_ignore_ascii_case(&chain_vault));
+        if !matches {
+            drifts.push(MarketDrift {
+                field: "vaultAddress",
+                database_value: stored.vault_address.clone(),
+                chain_value: Some(chain_vault),
+                safe: true,
+            });
+        }
+    }
+
+    let stored_resolved = stored.status == "resolved";
+    if stored_resolved != chain.resolved {
+        drifts.push(MarketDrift {
+            field: "resolved",
+            database_value: Some(stored.status.clone()),
+            chain_value: Some(chain.resolved.to_string()),
+            safe: false,
+        });
+    } else if chain.resolved && stored.result != Some(chain.outcome) {
+        drifts.push(MarketDrift {
+            field: "outcome",
+            database_value: stored.result.map(|result| result.to_string()),
+            chain_value: Some(chain.outcome.to_string()),
+            safe: false,
+        });
+    }
+
+    drifts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored() -> StoredMarket {
+        StoredMarket {
+            total_yes_shares: "4000000.000000000000000000".to_string(),
+            total_no_shares: "0.000000000000000000".to_string(),
+            total_pool_size: "4000000.000000000000000000".to_string(),
+            status: "active".to_string(),
+            result: None,
+            vault_address: Some("0x7e5f4552091a69125d5dfcb7b8c2659029395bdf".to_string()),
+        }
+    }
+
+    fn chain() -> ChainMarket {
+        ChainMarket {
+            total_yes_shares: U256::from(4_000_000),
+            total_no_shares: U256::zero(),
+            pool_size: U256::from(4_000_000),
+            resolved: false,
+            outcome: false,
+            vault: "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+                .parse()
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_matching_market_has_no_drift() {
+        assert!(compare_market(&stored(), &chain()).is_empty());
+    }
+
+    #[test]
+    fn test_only_vault_drift_is_healable() {
+        let mut chain = chain();
+        chain.total_no_shares = U256::from(1_500_000);
+        chain.vault = Address::repeat_byte(0x11);
+
+        let drifts = compare_market(&stored(), &chain);
+
+        assert_eq!(drifts.len(), 2);
+        assert_eq!(drifts[0].field, "totalNoShares");
+        assert_eq!(drifts[0].chain_value.as_deref(), Some("1500000"));
+        assert!(!drifts[0].safe);
+        assert_eq!(drifts[1].field, "vaultAddress");
+        assert!(drifts[1].safe);
+    }
+
+    #[test]
+    fn test_pool_size_drift_is_reported_only() {
+        let mut chain = chain();
+        chain.pool_size = U256::from(5_000_000);
+
+        assert_eq!(
+            compare_market(&stored(), &chain),
+            vec![MarketDrift {
+                field: "totalPoolSize",
+                database_value: Some("4000000.000000000000000000".to_string()),
+                chain_value: Some("5000000".to_string()),
+                safe: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_resolution_drift_is_reported_only() {
+        let mut chain = chain();
+        chain.resolved = true;
+        chain.outcome = true;
+        assert_eq!(
+            compare_market(&stored(), &chain),
+            vec![MarketDrift {
+                field: "resolved",
+                database_value: Some("active".to_string()),
+                chain_value: Some("true".to_string()),
+                safe: false,
+            }]
+        );
+
+        let mut resolved = stored();
+        resolved.status = "resolved".to_string();
+        resolved.result = Some(false);
+        let drifts = compare_market(&resolved, &chain);
+        assert_eq!(drifts[0].field, "outcome");
+        assert!(!drifts[0].safe);
+    }
+}
@@ -5,8 +5,10 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::{
+    config::Config,
     db::Database,
     error::{AppError, Result},
+    services::{rpc, MulticallReader},
 };
 
 abigen!(
@@ -93,6 +95,10 @@ abigen!(
     r#"[
         function getTotalBalance(address user, address token) external view returns (uint256)
         function getUserDeposit(address user, address token) external view returns (uint256)
+        event ProtocolRegistered(uint8 protocolType, address indexed protocolAddress, string name, uint8 riskLevel)
+        event ProtocolUpdated(address indexed protocolAddress, uint256 newApy, uint256 newTvl)
+        event AutoDepositExecuted(address indexed user, address indexed protocol, uint256 amount, bool success)
+        event AutoWithdrawExecuted(address indexed user, address indexed protocol, uint256 amount, bool success)
     ]"#,
 );
 
@@ -108,6 +114,7 @@ abigen!(
 pub struct MarketYieldInfo {
     pub blockchain_market_id: u64,
     pub total_pool_size: String,
+    pub total_shares: String,
     pub current_yield_earned: String,
     pub protocol_id: u64,
     pub token_address: String,
@@ -124,18 +131,26 @@ pub struct UserYieldInfo {
     pub current_balance: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PotentialPayout {
+    pub blockchain_market_id: u64,
+    pub user_address: String,
+    pub yes_payout_if_win: String,
+    pub no_payout_if_win: String,
+    pub current_yield: String,
+}
+
 pub struct BlockchainYieldService {
     db: Database,
-    rpc_url: String,
-    whizy_market_address: String,
+    config: Config,
 }
 
 impl BlockchainYieldService {
-    pub fn new(db: Database, rpc_url: String, whizy_market_address: String) -> Self {
+    pub fn new(db: Database, config: &Config) -> Self {
         Self {
             db,
-            rpc_url,
-            whizy_market_address,
+            config: config.clone(),
         }
     }
 
@@ -143,13 +158,13 @@ impl BlockchainYieldService {
         &self,
         blockchain_market_id: u64,
     ) -> Result<MarketYieldInfo> {
-        let provider = Provider::<Http>::try_from(&self.rpc_url)
-            .map_err(|e| AppError::Internal(format!("Failed to connect to RPC: {}", e)))?;
+        let provider = rpc::provider(&self.config)?;
 
         let provider = Arc::new(provider);
 
         let contract_address: Address = self
-            .whizy_market_address
+            .config
+            .whizy_prediction_market_addr
             .parse()
             .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
 
@@ -185,8 +200,7 @@ impl BlockchainYieldService {
 
         let token_address = market.3;
         let vault_address = market.4;
-        let _total_yes_shares = market.5;
-        let _total_no_shares = market.6;
+        let total_shares = market.5 + market.6;
 
         tracing::info!(
             "Market {} data: totalAssets={}, currentYield={}, yieldWithdrawn={}, vault={:?}",
@@ -202,6 +216,7 @@ impl BlockchainYieldService {
         Ok(MarketYieldInfo {
             blockchain_market_id,
             total_pool_size: total_assets.to_string(),
+            total_shares: total_shares.to_string(),
             current_yield_earned: current_yield_earned.to_string(),
             protocol_id: 0,
             token_address: format!("{:?}", token_address),
@@ -213,8 +228,7 @@ impl BlockchainYieldService {
         blockchain_market_id: u64,
         user_address: &str,
     ) -> Result<UserYieldInfo> {
-        let provider = Provider::<Http>::try_from(&self.rpc_url)
-            .map_err(|e| AppError::Internal(format!("Failed to connect to RPC: {}", e)))?;
+        let provider = rpc::provider(&self.config)?;
 
         let provider = Arc::new(provider);
 
@@ -223,7 +237,8 @@ impl BlockchainYieldService {
             .map_err(|e| AppError::BadRequest(format!("Invalid user address: {}", e)))?;
 
         let contract_address: Address = self
-            .whizy_market_address
+            .config
+            .whizy_prediction_market_addr
             .parse()
             .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
 
@@ -293,6 +308,40 @@ impl BlockchainYieldService {
         })
     }
 
+    pub async fn get_potential_payout(
+        &self,
+        blockchain_market_id: u64,
+        user_address: &str,
+    ) -> Result<PotentialPayout> {
+        let provider = rpc::provider(&self.config)?;
+
+        let user_addr: Address = user_address
+            .parse()
+            .map_err(|e| AppError::BadRequest(format!("Invalid user address: {}", e)))?;
+
+        let contract_address: Address = self
+            .config
+            .whizy_prediction_market_addr
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
+
+        let contract = IWhizyPredictionMarket::new(contract_address, Arc::new(provider));
+
+        let (yes_payout_if_win, no_payout_if_win, current_yield) = contract
+            .get_potential_payout(U256::from(blockchain_market_id), user_addr)
+            .call()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch potential payout: {}", e)))?;
+
+        Ok(PotentialPayout {
+            blockchain_market_id,
+            user_address: user_address.to_string(),
+            yes_payout_if_win: yes_payout_if_win.to_string(),
+            no_payout_if_win: no_payout_if_win.to_string(),
+            current_yield: current_yield.to_string(),
+        })
+    }
+
     pub async fn sync_market_yield_to_db(&self, blockchain_market_id: u64) -> Result<()> {
         let yield_info = self.get_market_current_yield(blockchain_market_id).await?;
 
@@ -317,23 +366,67 @@ impl BlockchainYieldService {
     pub async fn sync_all_active_markets_yields(&self) -> Result<usize> {
         let markets = sqlx::query!(
             r#"
-            SELECT "blockchainMarketId"
+            SELECT "blockchainMarketId" as "blockchain_market_id!"
             FROM markets_extended
-            WHERE status = 'active' AND "blockchainMarketId" IS NOT NULL
+            WHERE status IN ('active', 'closed', 'pending_resolution')
+              AND "blockchainMarketId" IS NOT NULL
             "#
         )
         .fetch_all(self.db.pool())
         .await?;
 
+        let provider = rpc::provider(&self.config)?;
+        let provider = Arc::new(provider);
+
+        let contract_address: Address = self
+            .config
+            .whizy_prediction_market_addr
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
+
+        let contract = IWhizyPredictionMarket::new(contract_address, provider.clone());
+
+        let market_infos = MulticallReader::new(provider, &self.config)?
+            .read(
+                markets
+                    .iter()
+                    .map(|market| contract.get_market_info(U256::from(market.blockchain_market_id)))
+                    .collect(),
+            )
+            .await;
+
         let mut synced_count = 0;
 
-        for market in markets {
-            if let Some(blockchain_id) = market.blockchainMarketId {
-                match self.sync_market_yield_to_db(blockchain_id as u64).await {
-                    Ok(_) => synced_count += 1,
-                    Err(e) => {
-                        tracing::warn!("Failed to sync yield for market {}: {}", blockchain_id, e);
-                    }
+        for (market, market_info) in markets.into_iter().zip(market_infos) {
+            let blockchain_id = market.blockchain_market_id;
+            let Some(market_info) =
+                market_info.filter(|market_info| market_info.0 .0 == U256::from(blockchain_id))
+            else {
+                tracing::warn!(
+                    "Failed to sync yield for market {}: not readable on-chain",
+                    blockchain_id
+                );
+                continue;
+            };
+
+            let current_yield = BigDecimal::from_str(&market_info.2.to_string())
+                .unwrap_or_else(|_| BigDecimal::from(0));
+
+            match sqlx::query!(
+                r#"
+                UPDATE markets_extended
+                SET "currentYield" = $1, "updatedAt" = CURRENT_TIMESTAMP
+                WHERE "blockchainMarketId" = $2
+                "#,
+                current_yield,
+                blockchain_id
+            )
+            .execute(self.db.pool())
+            .await
+            {
+                Ok(_) => synced_count += 1,
+                Err(e) => {
+                    tracing::warn!("Failed to sync yield for market {}: {}", blockchain_id, e);
                 }
             }
         }
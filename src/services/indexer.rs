@@ -0,0 +1,1584 @@
+use chrono::NaiveDateTime;
+use ethers::{abi::RawLog, prelude::*};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time;
+use tracing::{error, info, warn};
+
+use crate::{
+    config::Config,
+    db::Database,
+    error::{AppError, Result},
+    models::ChainReorg,
+    services::{
+        blockchain_event::EventRetryPolicy,
+        blockchain_sync::WhizyPredictionMarketEvents,
+        blockchain_yield::IProtocolSelectorEvents,
+        projection::{self, PROJECTED_EVENT_TYPES},
+        rpc::{self, RpcPool},
+    },
+};
+
+abigen!(
+    IRebalancerDelegation,
+    r#"[
+        event AutoRebalanceEnabled(address indexed user, uint8 riskProfile)
+        event AutoRebalanceDisabled(address indexed user)
+        event Deposited(address indexed user, uint256 amount)
+        event Withdrawn(address indexed user, uint256 amount)
+        event Rebalanced(address indexed user, address indexed operator, uint256 amount)
+        event OperatorAdded(address indexed operator)
+        event OperatorRemoved(address indexed operator)
+        function rebalance(address user, address targetProtocol) external
+    ]"#,
+);
+
+static STREAMING: AtomicBool = AtomicBool::new(false);
+
+pub fn is_streaming() -> bool {
+    STREAMING.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexedContractKind {
+    PredictionMarket,
+    ProtocolSelector,
+    RebalancerDelegation,
+}
+
+impl IndexedContractKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            IndexedContractKind::PredictionMarket => "WhizyPredictionMarket",
+            IndexedContractKind::ProtocolSelector => "ProtocolSelector",
+            IndexedContractKind::RebalancerDelegation => "RebalancerDelegation",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        [
+            IndexedContractKind::PredictionMarket,
+            IndexedContractKind::ProtocolSelector,
+            IndexedContractKind::RebalancerDelegation,
+        ]
+        .into_iter()
+        .find(|kind| kind.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn tables(&self) -> &'static [&'static str] {
+        match self {
+            IndexedContractKind::PredictionMarket => &[
+                "market_createds",
+                "bet_placeds",
+                "market_resolveds",
+                "winnings_claimeds",
+                "market_vault_rebalanceds",
+                "pauseds",
+                "unpauseds",
+                "ownership_transferreds",
+            ],
+            IndexedContractKind::ProtocolSelector => &[
+                "protocol_registereds",
+                "protocol_updateds",
+                "auto_deposit_executeds",
+                "auto_withdraw_executeds",
+            ],
+            IndexedContractKind::RebalancerDelegation => &[
+                "auto_rebalance_enableds",
+                "auto_rebalance_disableds",
+                "depositeds",
+                "withdrawns",
+                "rebalanceds",
+                "operator_addeds",
+                "operator_removeds",
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexedContract {
+    pub kind: IndexedContractKind,
+    pub address: Address,
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexerSettings {
+    pub start_block: u64,
+    pub batch_size: u64,
+    pub confirmations: u64,
+    pub reorg_depth: u64,
+    pub event_retry: EventRetryPolicy,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexSummary {
+    pub blocks: u64,
+    pub events: usize,
+    pub reorgs: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorgStats {
+    pub total_reorgs: i64,
+    pub events_rolled_back: i64,
+    pub max_depth: i64,
+    pub last_detected_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, PartialEq)]
+struct ForkPoint {
+    block: u64,
+    hash: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum EventValue {
+    Numeric(String),
+    Int(i64),
+    Text(String),
+    Bool(bool),
+}
+
+#[derive(Debug, PartialEq)]
+struct RawEventRow {
+    table: &'static str,
+    columns: Vec<(&'static str, EventValue)>,
+}
+
+struct LogMeta {
+    id: String,
+    block_number: u64,
+    block_timestamp: u64,
+    transaction_hash: String,
+}
+
+pub struct IndexerService<P = RpcPool> {
+    db: Database,
+    provider: Arc<Provider<P>>,
+    contracts: Vec<IndexedContract>,
+    settings: IndexerSettings,
+}
+
+impl IndexerService<RpcPool> {
+    pub fn new(db: Database, config: &Config) -> Result<Self> {
+        let provider = rpc::provider(config)?;
+
+        Ok(Self::with_provider(
+            db,
+            provider,
+            configured_contracts(config)?,
+            IndexerSettings {
+                start_block: config.indexer_start_block,
+                batch_size: config.indexer_batch_size,
+                confirmations: config.indexer_confirmations,
+                reorg_depth: config.indexer_reorg_depth,
+                event_retry: EventRetryPolicy::from_config(config),
+            },
+        ))
+    }
+}
+
+impl<P: JsonRpcClient> IndexerService<P> {
+    pub fn with_provider(
+        db: Database,
+        provider: Provider<P>,
+        contracts: Vec<IndexedContract>,
+        settings: IndexerSettings,
+    ) -> Self {
+        Self {
+            db,
+            provider: Arc::new(provider),
+            contracts,
+            settings,
+        }
+    }
+
+    pub async fn run(&self, max_batches: usize) -> Result<IndexSummary> {
+        let head = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch block number: {}", e)))?
+            .as_u64();
+        let safe_head = head.saturating_sub(self.settings.confirmations);
+
+        let mut summary = IndexSummary::default();
+
+        for contract in &self.contracts {
+            let mut from = match self.checkpoint(contract).await? {
+                Some((last_block, Some(expected_hash))) => {
+                    match self
+                        .detect_reorg(contract, last_block, &expected_hash)
+                        .await?
+                    {
+                        Some(fork_block) => {
+                            summary.reorgs += 1;
+                            fork_block + 1
+                        }
+                        None => last_block + 1,
+                    }
+                }
+                Some((last_block, None)) => last_block + 1,
+                None => self.settings.start_block,
+            };
+            let mut span = self.settings.batch_size;
+            let mut batches = 0;
+
+            while from <= safe_head && batches < max_batches {
+                let to = (from + span - 1).min(safe_head);
+
+                let logs = match self.fetch_logs(contract, from, to).await {
+                    Ok(logs) => logs,
+                    Err(e) if span > 1 => {
+                        span = (span / 2).max(1);
+                        warn!(
+                            "⚠️ [Indexer] getLogs {}..{} for {} failed ({}), retrying with {} blocks",
+                            from,
+                            to,
+                            contract.kind.name(),
+                            e,
+                            span
+                        );
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
+
+                summary.events += self.ingest(contract, to, logs).await?;
+                summary.blocks += to - from + 1;
+                from = to + 1;
+                batches += 1;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    pub async fn replay(
+        &self,
+        kinds: &[IndexedContractKind],
+        tables: &[String],
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<IndexSummary> {
+        let head = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch block number: {}", e)))?
+            .as_u64();
+        let to_block = to_block.min(head);
+
+        let mut summary = IndexSummary::default();
+
+        for contract in self
+            .contracts
+            .iter()
+            .filter(|contract| kinds.is_empty() || kinds.contains(&contract.kind))
+        {
+            let mut from = from_block;
+            let mut span = self.settings.batch_size;
+
+            while from <= to_block {
+                let to = (from + span - 1).min(to_block);
+
+                let logs = match self.fetch_logs(contract, from, to).await {
+                    Ok(logs) => logs,
+                    Err(e) if span > 1 => {
+                        span = (span / 2).max(1);
+                        warn!(
+                            "⚠️ [Replay] getLogs {}..{} for {} failed ({}), retrying with {} blocks",
+                            from,
+                            to,
+                            contract.kind.name(),
+                            e,
+                            span
+                        );
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
+
+                let logs = logs
+                    .into_iter()
+                    .filter(|log| {
+                        tables.is_empty()
+                            || decode_log(contract.kind, log)
+                                .is_some_and(|row| tables.iter().any(|table| table == row.table))
+                    })
+                    .collect::<Vec<_>>();
+                let blocks = self.log_blocks(&logs).await?;
+
+                let mut tx = self.db.pool().begin().await?;
+                for table in contract
+                    .kind
+                    .tables()
+                    .iter()
+                    .filter(|table| tables.is_empty() || tables.iter().any(|t| t == *table))
+                {
+                    sqlx::query(&format!(
+                        "DELETE FROM {} WHERE block_number BETWEEN $1 AND $2",
+                        table
+                    ))
+                    .bind(from as i64)
+                    .bind(to as i64)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                let inserted = write_events(
+                    &mut tx,
+                    contract.kind,
+                    &logs,
+                    &blocks,
+                    &self.settings.event_retry,
+                )
+                .await?;
+                tx.commit().await?;
+
+                info!(
+                    "🔁 [Replay] {} blocks {}..{}: {} logs, {} events written",
+                    contract.kind.name(),
+                    from,
+                    to,
+                    logs.len(),
+                    inserted
+                );
+
+                summary.events += inserted;
+                summary.blocks += to - from + 1;
+                from = to + 1;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    async fn checkpoint(
+        &self,
+        contract: &IndexedContract,
+    ) -> Result<Option<(u64, Option<String>)>> {
+        let state = sqlx::query!(
+            "SELECT last_block, last_block_hash FROM sync_states WHERE contract_address = $1",
+            format!("{:?}", contract.address)
+        )
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        Ok(state.map(|state| (state.last_block as u64, state.last_block_hash)))
+    }
+
+    async fn detect_reorg(
+        &self,
+        contract: &IndexedContract,
+        last_block: u64,
+        expected_hash: &str,
+    ) -> Result<Option<u64>> {
+        let canonical_hash = canonical_hash(&self.provider, last_block).await?;
+        if canonical_hash.as_deref() == Some(expected_hash) {
+            return Ok(None);
+        }
+
+        let floor = last_block
+            .saturating_sub(self.settings.reorg_depth)
+            .max(self.settings.start_block.saturating_sub(1));
+
+        let recorded = sqlx::query!(
+            r#"
+            SELECT "blockNumber" as block_number, "blockHash" as block_hash
+            FROM indexed_blocks
+            WHERE "blockNumber" < $1 AND "blockNumber" >= $2
+            ORDER BY "blockNumber" DESC
+            "#,
+            last_block as i64,
+            floor as i64
+        )
+        .fetch_all(self.db.pool())
+        .await?
+        .into_iter()
+        .map(|block| (block.block_number as u64, block.block_hash))
+        .collect::<Vec<_>>();
+
+        let fork = find_fork_point(&self.provider, &recorded, floor).await?;
+        self.roll_back(
+            contract,
+            last_block,
+            expected_hash,
+            canonical_hash.as_deref(),
+            &fork,
+        )
+        .await?;
+
+        Ok(Some(fork.block))
+    }
+
+    async fn roll_back(
+        &self,
+        contract: &IndexedContract,
+        detected_at_block: u64,
+        expected_hash: &str,
+        canonical_hash: Option<&str>,
+        fork: &ForkPoint,
+    ) -> Result<u64> {
+        let mut tx = self.db.pool().begin().await?;
+
+        if contract.kind == IndexedContractKind::PredictionMarket {
+            revert_market_projections(&mut tx, fork.block).await?;
+        }
+
+        let mut rolled_back = 0;
+        for table in contract.kind.tables() {
+            rolled_back += sqlx::query(&format!("DELETE FROM {} WHERE block_number > $1", table))
+                .bind(fork.block as i64)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+
+        let event_types = contract
+            .kind
+            .tables()
+            .iter()
+            .map(|table| event_type(table))
+            .collect::<Vec<_>>();
+        sqlx::query!(
+            r#"DELETE FROM blockchain_events WHERE "blockNumber" > $1 AND "eventType" = ANY($2)"#,
+            fork.block as i64,
+            &event_types
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"DELETE FROM indexed_blocks WHERE "blockNumber" > $1"#,
+            fork.block as i64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE sync_states
+            SET last_block = $2, last_block_hash = $3
+            WHERE contract_address = $1 AND last_block > $2
+            "#,
+            format!("{:?}", contract.address),
+            fork.block as i64,
+            fork.hash
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO chain_reorgs (
+                id, "contractAddress", "contractName", "detectedAtBlock", "forkBlock", depth,
+                "expectedHash", "canonicalHash", "eventsRolledBack"
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            uuid::Uuid::new_v4().to_string(),
+            format!("{:?}", contract.address),
+            contract.kind.name(),
+            detected_at_block as i64,
+            fork.block as i64,
+            detected_at_block.saturating_sub(fork.block) as i64,
+            expected_hash,
+            canonical_hash,
+            rolled_back as i64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        warn!(
+            "🔀 [Indexer] Reorg on {}: block {} is now {:?} (expected {}), rolled back {} events above block {}",
+            contract.kind.name(),
+            detected_at_block,
+            canonical_hash,
+            expected_hash,
+            rolled_back,
+            fork.block
+        );
+
+        Ok(rolled_back)
+    }
+
+    pub async fn list_reorgs(&self, limit: i64) -> Result<Vec<ChainReorg>> {
+        let reorgs = sqlx::query_as::<_, ChainReorg>(
+            r#"
+            SELECT id, "contractAddress", "contractName", "detectedAtBlock", "forkBlock", depth,
+                   "expectedHash", "canonicalHash", "eventsRolledBack", "createdAt"
+            FROM chain_reorgs
+            ORDER BY "createdAt" DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit.clamp(1, 500))
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(reorgs)
+    }
+
+    pub async fn reorg_stats(&self) -> Result<ReorgStats> {
+        let stats = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "total_reorgs!",
+                   COALESCE(SUM("eventsRolledBack"), 0)::bigint as "events_rolled_back!",
+                   COALESCE(MAX(depth), 0) as "max_depth!",
+                   MAX("createdAt") as last_detected_at
+            FROM chain_reorgs
+            "#
+        )
+        .fetch_one(self.db.pool())
+        .await?;
+
+        Ok(ReorgStats {
+            total_reorgs: stats.total_reorgs,
+            events_rolled_back: stats.events_rolled_back,
+            max_depth: stats.max_depth,
+            last_detected_at: stats.last_detected_at,
+        })
+    }
+
+    async fn fetch_logs(&self, contract: &IndexedContract, from: u64, to: u64) -> Result<Vec<Log>> {
+        let filter = Filter::new()
+            .address(contract.address)
+            .from_block(from)
+            .to_block(to);
+
+        self.provider
+            .get_logs(&filter)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch logs: {}", e)))
+    }
+
+    async fn block(&self, number: u64) -> Result<Block<H256>> {
+        self.provider
+            .get_block(number)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch block {}: {}", number, e)))?
+            .ok_or_else(|| AppError::Internal(format!("Block {} not found", number)))
+    }
+
+    async fn log_blocks(&self, logs: &[Log]) -> Result<HashMap<u64, (u64, Option<String>)>> {
+        let mut blocks = HashMap::new();
+        for log in logs {
+            if let Some(number) = log.block_number {
+                let number = number.as_u64();
+                if let std::collections::hash_map::Entry::Vacant(entry) = blocks.entry(number) {
+                    let block = self.block(number).await?;
+                    entry.insert((
+                        block.timestamp.as_u64(),
+                        block.hash.map(|hash| format!("{:?}", hash)),
+                    ));
+                }
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    async fn ingest(&self, contract: &IndexedContract, to: u64, logs: Vec<Log>) -> Result<usize> {
+        let mut blocks = self.log_blocks(&logs).await?;
+        let checkpoint_hash = self.block(to).await?.hash.map(|hash| format!("{:?}", hash));
+        blocks
+            .entry(to)
+            .or_insert((0, None))
+            .1
+            .clone_from(&checkpoint_hash);
+
+        let mut tx = self.db.pool().begin().await?;
+        let inserted = write_events(
+            &mut tx,
+            contract.kind,
+            &logs,
+            &blocks,
+            &self.settings.event_retry,
+        )
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO sync_states (contract_address, contract_name, last_block, last_block_hash)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (contract_address) DO UPDATE
+            SET last_block = EXCLUDED.last_block, last_block_hash = EXCLUDED.last_block_hash
+            "#,
+            format!("{:?}", contract.address),
+            contract.kind.name(),
+            to as i64,
+            checkpoint_hash
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        record_blocks(&mut tx, &blocks).await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM indexed_blocks
+            WHERE "blockNumber" < (SELECT COALESCE(MIN(last_block), 0) FROM sync_states) - $1
+            "#,
+            self.settings.reorg_depth as i64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        if inserted > 0 {
+            info!(
+                "📥 [Indexer] {} new {} events up to block {}",
+                inserted,
+                contract.kind.name(),
+                to
+            );
+        }
+
+        Ok(inserted)
+    }
+
+    pub async fn stream(&self, ws_url: &str, checkpoint_interval: Duration) -> Result<()> {
+        let ws = Provider::<Ws>::connect(ws_url).await.map_err(|e| {
+            AppError::Internal(format!("Failed to connect to WebSocket RPC: {}", e))
+        })?;
+        let filter = Filter::new().address(
+            self.contracts
+                .iter()
+                .map(|contract| contract.address)
+                .collect::<Vec<_>>(),
+        );
+        let mut logs = ws
+            .subscribe_logs(&filter)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to subscribe to logs: {}", e)))?;
+
+        STREAMING.store(true, Ordering::Relaxed);
+        info!("🔌 [Indexer] Subscribed to contract logs over WebSocket");
+
+        let mut interval = time::interval(checkpoint_interval);
+        let mut pending = Vec::new();
+        loop {
+            tokio::select! {
+                log = logs.next() => {
+                    let Some(log) = log else {
+                        break;
+                    };
+                    if let Err(e) = self.buffer_streamed(&mut pending, log).await {
+                        error!("❌ [Indexer] Failed to handle streamed log: {}", e);
+                    }
+                }
+                _ = interval.tick() => {
+                    if let Err(e) = self.run(usize::MAX).await {
+                        error!("❌ [Indexer] Failed to advance checkpoints: {}", e);
+                    }
+                }
+            }
+
+            if let Err(e) = self.ingest_streamed(&mut pending).await {
+                error!("❌ [Indexer] Failed to ingest streamed logs: {}", e);
+            }
+        }
+
+        STREAMING.store(false, Ordering::Relaxed);
+        Err(AppError::Internal("Log subscription closed".to_string()))
+    }
+
+    async fn buffer_streamed(&self, pending: &mut Vec<Log>, log: Log) -> Result<()> {
+        let Some(contract) = self
+            .contracts
+            .iter()
+            .find(|contract| contract.address == log.address)
+        else {
+            return Ok(());
+        };
+        let Some(block_number) = log.block_number.map(|number| number.as_u64()) else {
+            return Ok(());
+        };
+
+        if log.removed != Some(true) {
+            pending.push(log);
+            return Ok(());
+        }
+
+        if remove_pending(pending, &log) {
+            return Ok(());
+        }
+
+        let fork_block = block_number.saturating_sub(1);
+        let fork = ForkPoint {
+            block: fork_block,
+            hash: canonical_hash(&self.provider, fork_block).await?,
+        };
+        let canonical_hash = canonical_hash(&self.provider, block_number).await?;
+        let expected_hash = log
+            .block_hash
+            .map(|hash| format!("{:?}", hash))
+            .unwrap_or_default();
+
+        self.roll_back(
+            contract,
+            block_number,
+            &expected_hash,
+            canonical_hash.as_deref(),
+            &fork,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn ingest_streamed(&self, pending: &mut Vec<Log>) -> Result<()> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let head = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch block number: {}", e)))?
+            .as_u64();
+        let confirmed = take_confirmed(pending, head.saturating_sub(self.settings.confirmations));
+        if confirmed.is_empty() {
+            return Ok(());
+        }
+
+        let blocks = self.log_blocks(&confirmed).await?;
+        let (canonical, orphaned): (Vec<_>, Vec<_>) = confirmed.into_iter().partition(|log| {
+            let canonical_hash = log
+                .block_number
+                .and_then(|number| blocks.get(&number.as_u64()))
+                .and_then(|(_, hash)| hash.clone());
+            log.block_hash.map(|hash| format!("{:?}", hash)) == canonical_hash
+        });
+        if !orphaned.is_empty() {
+            warn!(
+                "🔀 [Indexer] Dropped {} streamed logs from orphaned blocks",
+                orphaned.len()
+            );
+        }
+
+        let mut tx = self.db.pool().begin().await?;
+        let mut inserted = 0;
+        for contract in &self.contracts {
+            let logs = canonical
+                .iter()
+                .filter(|log| log.address == contract.address)
+                .cloned()
+                .collect::<Vec<_>>();
+            if !logs.is_empty() {
+                inserted += write_events(
+                    &mut tx,
+                    contract.kind,
+                    &logs,
+                    &blocks,
+                    &self.settings.event_retry,
+                )
+                .await?;
+            }
+        }
+        record_blocks(&mut tx, &blocks).await?;
+        tx.commit().await?;
+
+        if inserted > 0 {
+            info!(
+                "⚡ [Indexer] Streamed {} events up to block {}",
+                inserted,
+                head.saturating_sub(self.settings.confirmations)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn take_confirmed(pending: &mut Vec<Log>, safe_head: u64) -> Vec<Log> {
+    let (confirmed, waiting) = std::mem::take(pending).into_iter().partition(|log| {
+        log.block_number
+            .is_some_and(|number| number.as_u64() <= safe_head)
+    });
+    *pending = waiting;
+    confirmed
+}
+
+fn remove_pending(pending: &mut Vec<Log>, removed: &Log) -> bool {
+    let before = pending.len();
+    pending.retain(|log| {
+        (log.transaction_hash, log.log_index, log.block_hash)
+            != (
+                removed.transaction_hash,
+                removed.log_index,
+                removed.block_hash,
+            )
+    });
+    pending.len() < before
+}
+
+async fn record_blocks(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    blocks: &HashMap<u64, (u64, Option<String>)>,
+) -> Result<()> {
+    for (number, (_, hash)) in blocks {
+        let Some(hash) = hash else {
+            continue;
+        };
+        sqlx::query!(
+            r#"
+            INSERT INTO indexed_blocks ("blockNumber", "blockHash") VALUES ($1, $2)
+            ON CONFLICT ("blockNumber") DO UPDATE SET "blockHash" = EXCLUDED."blockHash"
+            "#,
+            *number as i64,
+            hash
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn write_events(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    kind: IndexedContractKind,
+    logs: &[Log],
+    blocks: &HashMap<u64, (u64, Option<String>)>,
+    retry: &EventRetryPolicy,
+) -> Result<usize> {
+    let mut inserted = 0;
+
+    for log in logs {
+        if log.removed == Some(true) {
+            continue;
+        }
+        let (Some(block_number), Some(transaction_hash), Some(log_index)) =
+            (log.block_number, log.transaction_hash, log.log_index)
+        else {
+            continue;
+        };
+
+        let Some(row) = decode_log(kind, log) else {
+            continue;
+        };
+
+        let meta = LogMeta {
+            id: format!("{:?}-{}", transaction_hash, log_index),
+            block_number: block_number.as_u64(),
+            block_timestamp: blocks
+                .get(&block_number.as_u64())
+                .map(|(timestamp, _)| *timestamp)
+                .unwrap_or_default(),
+            transaction_hash: format!("{:?}", transaction_hash),
+        };
+
+        if row.table == "market_createds" {
+            link_created_market(tx, &row, &meta).await?;
+        }
+
+        if insert_raw_event(tx, &row, &meta).await? {
+            let event_type = event_type(row.table);
+            let projection_error = if PROJECTED_EVENT_TYPES.contains(&event_type.as_str()) {
+                project_event(tx, &event_type, &meta.id).await.err()
+            } else {
+                None
+            };
+            if let Some(e) = &projection_error {
+                warn!(
+                    "⚠️ [Indexer] Projection of {} {} deferred: {}",
+                    event_type, meta.id, e
+                );
+            }
+
+            track_event(
+                tx,
+                &row,
+                &meta,
+                &event_type,
+                projection_error.map(|e| e.to_string()),
+                retry,
+            )
+            .await?;
+            inserted += 1;
+        }
+    }
+
+    Ok(inserted)
+}
+
+async fn canonical_hash<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    number: u64,
+) -> Result<Option<String>> {
+    let block = provider
+        .get_block(number)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to fetch block {}: {}", number, e)))?;
+
+    Ok(block
+        .and_then(|block| block.hash)
+        .map(|hash| format!("{:?}", hash)))
+}
+
+async fn find_fork_point<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    recorded: &[(u64, String)],
+    floor: u64,
+) -> Result<ForkPoint> {
+    for (number, hash) in recorded {
+        let canonical = canonical_hash(provider, *number).await?;
+        if canonical.as_deref() == Some(hash.as_str()) {
+            return Ok(ForkPoint {
+                block: *number,
+                hash: canonical,
+            });
+        }
+    }
+
+    Ok(ForkPoint {
+        block: floor,
+        hash: canonical_hash(provider, floor).await?,
+    })
+}
+
+async fn revert_market_projections(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    fork_block: u64,
+) -> Result<()> {
+    let fork_block = fork_block as i64;
+
+    let affected_markets = sqlx::query_scalar!(
+        r#"
+        SELECT me.id
+        FROM markets_extended me
+        WHERE me."blockchainMarketId" IN (
+            SELECT market_id FROM bet_placeds WHERE block_number > $1::bigint
+            UNION SELECT market_id FROM market_resolveds WHERE block_number > $1::bigint
+            UNION SELECT market_id FROM winnings_claimeds WHERE block_number > $1::bigint
+        )
+        "#,
+        fork_block
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE bets_extended be
+        SET payout = NULL,
+            status = CASE
+                WHEN me.status = 'resolved' AND me.result IS NOT NULL THEN
+                    CASE WHEN be.position = me.result THEN 'won' ELSE 'lost' END
+                ELSE 'active'
+            END,
+            "updatedAt" = NOW()
+        FROM winnings_claimeds wc, users u, markets_extended me
+        WHERE wc.block_number > $1
+          AND be.status = 'claimed'
+          AND be."userId" = u.id AND u.address = wc.user
+          AND be."marketId" = me.id AND me."blockchainMarketId" = wc.market_id
+        "#,
+    )
+    .bind(fork_block)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        WITH reverted AS (
+            UPDATE markets_extended me
+            SET status = 'closed', result = NULL, "resolutionDate" = NULL, "updatedAt" = NOW()
+            FROM market_resolveds mr
+            WHERE mr.block_number > $1
+              AND me."blockchainMarketId" = mr.market_id
+              AND me.status = 'resolved'
+              AND me."resolutionDate" = to_timestamp(mr.block_timestamp)
+            RETURNING me.id
+        ),
+        reopened_bets AS (
+            UPDATE bets_extended
+            SET status = 'active', "updatedAt" = NOW()
+            WHERE "marketId" IN (SELECT id FROM reverted) AND status IN ('won', 'lost')
+        )
+        INSERT INTO market_status_history ("marketId", "fromStatus", "toStatus", actor, reason)
+        SELECT id, 'resolved', 'closed', 'indexer', 'resolution event removed by chain reorganization'
+        FROM reverted
+        "#,
+    )
+    .bind(fork_block)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        DELETE FROM bets_extended be
+        USING bet_placeds bp
+        WHERE bp.block_number > $1 AND be.id = bp.id
+        "#,
+    )
+    .bind(fork_block)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        DELETE FROM markets_extended me
+        USING market_createds mc
+        WHERE mc.block_number > $1
+          AND me."blockchainMarketId" = mc.market_id
+          AND NOT EXISTS (
+              SELECT 1 FROM transactions t WHERE t."marketId" = me.id AND t.kind = 'create_market'
+          )
+        "#,
+    )
+    .bind(fork_block)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE markets_extended me
+        SET "blockchainMarketId" = NULL, "updatedAt" = NOW()
+        FROM market_createds mc
+        WHERE mc.block_number > $1 AND me."blockchainMarketId" = mc.market_id
+        "#,
+    )
+    .bind(fork_block)
+    .execute(&mut **tx)
+    .await?;
+
+    for market_id in &affected_markets {
+        projection::refresh_market(tx, market_id).await?;
+    }
+
+    Ok(())
+}
+
+pub fn configured_contracts(config: &Config) -> Result<Vec<IndexedContract>> {
+    let parse = |address: &str| -> Result<Address> {
+        address
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid contract address {}: {}", address, e)))
+    };
+
+    let mut contracts = vec![
+        IndexedContract {
+            kind: IndexedContractKind::PredictionMarket,
+            address: parse(&config.whizy_prediction_market_addr)?,
+        },
+        IndexedContract {
+            kind: IndexedContractKind::ProtocolSelector,
+            address: parse(&config.protocol_selector_addr)?,
+        },
+    ];
+
+    if let Some(address) = config.rebalancer_delegation_addr.as_deref() {
+        contracts.push(IndexedContract {
+            kind: IndexedContractKind::RebalancerDelegation,
+            address: parse(address)?,
+        });
+    }
+
+    Ok(contracts)
+}
+
+fn decode_log(kind: IndexedContractKind, log: &Log) -> Option<RawEventRow> {
+    let raw = RawLog::from(log.clone());
+    let address = |address: Address| EventValue::Text(ethers::utils::to_checksum(&address, None));
+    let numeric = |value: U256| EventValue::Numeric(value.to_string());
+
+    let row = |table: &'static str, columns: Vec<(&'static str, EventValue)>| RawEventRow {
+        table,
+        columns,
+    };
+
+    match kind {
+        IndexedContractKind::PredictionMarket => {
+            match WhizyPredictionMarketEvents::decode_log(&raw).ok()? {
+                WhizyPredictionMarketEvents::MarketCreatedFilter(event) => Some(row(
+                    "market_createds",
+                    vec![
+                        ("market_id", numeric(event.market_id)),
+                        ("question", EventValue::Text(event.question)),
+                        ("end_time", numeric(event.end_time)),
+                        ("token_address", address(event.token)),
+                        ("vault_address", address(event.vault)),
+                    ],
+                )),
+                WhizyPredictionMarketEvents::BetPlacedFilter(event) => Some(row(
+                    "bet_placeds",
+                    vec![
+                        ("market_id", numeric(event.market_id)),
+                        ("user", address(event.user)),
+                        ("position", EventValue::Bool(event.position)),
+                        ("amount", numeric(event.amount)),
+                        ("shares", numeric(event.shares)),
+                    ],
+                )),
+                WhizyPredictionMarketEvents::MarketResolvedFilter(event) => Some(row(
+                    "market_resolveds",
+                    vec![
+                        ("market_id", numeric(event.market_id)),
+                        ("outcome", EventValue::Bool(event.outcome)),
+                    ],
+                )),
+                WhizyPredictionMarketEvents::WinningsClaimedFilter(event) => Some(row(
+                    "winnings_claimeds",
+                    vec![
+                        ("market_id", numeric(event.market_id)),
+                        ("user", address(event.user)),
+                        ("winning_amount", numeric(event.winning_amount)),
+                    ],
+                )),
+                WhizyPredictionMarketEvents::MarketVaultRebalancedFilter(event) => Some(row(
+                    "market_vault_rebalanceds",
+                    vec![
+                        ("market_id", numeric(event.market_id)),
+                        ("amount", numeric(event.amount)),
+                    ],
+                )),
+                WhizyPredictionMarketEvents::PausedFilter(event) => {
+                    Some(row("pauseds", vec![("account", address(event.account))]))
+                }
+                WhizyPredictionMarketEvents::UnpausedFilter(event) => {
+                    Some(row("unpauseds", vec![("account", address(event.account))]))
+                }
+                WhizyPredictionMarketEvents::OwnershipTransferredFilter(event) => Some(row(
+                    "ownership_transferreds",
+                    vec![
+                        ("previous_owner", address(event.previous_owner)),
+                        ("new_owner", address(event.new_owner)),
+                    ],
+                )),
+            }
+        }
+        IndexedContractKind::ProtocolSelector => {
+            match IProtocolSelectorEvents::decode_log(&raw).ok()? {
+                IProtocolSelectorEvents::ProtocolRegisteredFilter(event) => Some(row(
+                    "protocol_registereds",
+                    vec![
+                        ("protocol_type", EventValue::Int(event.protocol_type as i64)),
+                        ("protocol_address", address(event.protocol_address)),
+                        ("name", EventValue::Text(event.name)),
+                        ("risk_level", EventValue::Int(event.risk_level as i64)),
+                    ],
+                )),
+                IProtocolSelectorEvents::ProtocolUpdatedFilter(event) => Some(row(
+                    "protocol_updateds",
+                    vec![
+                        ("protocol_address", address(event.protocol_address)),
+                        ("new_apy", numeric(event.new_apy)),
+                        ("new_tvl", numeric(event.new_tvl)),
+                    ],
+                )),
+                IProtocolSelectorEvents::AutoDepositExecutedFilter(event) => Some(row(
+                    "auto_deposit_executeds",
+                    vec![
+                        ("user", address(event.user)),
+                        ("protocol", address(event.protocol)),
+                        ("amount", numeric(event.amount)),
+                        ("success", EventValue::Bool(event.success)),
+                    ],
+                )),
+                IProtocolSelectorEvents::AutoWithdrawExecutedFilter(event) => Some(row(
+                    "auto_withdraw_executeds",
+                    vec![
+                        ("user", address(event.user)),
+                        ("protocol", address(event.protocol)),
+                        ("amount", numeric(event.amount)),
+                        ("success", EventValue::Bool(event.success)),
+                    ],
+                )),
+            }
+        }
+        IndexedContractKind::RebalancerDelegation => {
+            match IRebalancerDelegationEvents::decode_log(&raw).ok()? {
+                IRebalancerDelegationEvents::AutoRebalanceEnabledFilter(event) => Some(row(
+                    "auto_rebalance_enableds",
+                    vec![
+                        ("user", address(event.user)),
+                        ("risk_profile", EventValue::Int(event.risk_profile as i64)),
+                    ],
+                )),
+                IRebalancerDelegationEvents::AutoRebalanceDisabledFilter(event) => Some(row(
+                    "auto_rebalance_disableds",
+                    vec![("user", address(event.user))],
+                )),
+                IRebalancerDelegationEvents::DepositedFilter(event) => Some(row(
+                    "depositeds",
+                    vec![
+                        ("user", address(event.user)),
+                        ("amount", numeric(event.amount)),
+                    ],
+                )),
+                IRebalancerDelegationEvents::WithdrawnFilter(event) => Some(row(
+                    "withdrawns",
+                    vec![
+                        ("user", address(event.user)),
+                        ("amount", numeric(event.amount)),
+                    ],
+                )),
+                IRebalancerDelegationEvents::RebalancedFilter(event) => Some(row(
+                    "rebalanceds",
+                    vec![
+                        ("user", address(event.user)),
+                        ("operator", address(event.operator)),
+                        ("amount", numeric(event.amount)),
+                    ],
+                )),
+                IRebalancerDelegationEvents::OperatorAddedFilter(event) => Some(row(
+                    "operator_addeds",
+                    vec![("operator", address(event.operator))],
+                )),
+                IRebalancerDelegationEvents::OperatorRemovedFilter(event) => Some(row(
+                    "operator_removeds",
+                    vec![("operator", address(event.operator))],
+                )),
+            }
+        }
+    }
+}
+
+async fn link_created_market(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    row: &RawEventRow,
+    meta: &LogMeta,
+) -> Result<()> {
+    let Some((_, EventValue::Numeric(market_id))) = row
+        .columns
+        .iter()
+        .find(|(column, _)| *column == "market_id")
+    else {
+        return Ok(());
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE markets_extended m
+        SET "blockchainMarketId" = $1::numeric, "updatedAt" = NOW()
+        FROM transactions t
+        WHERE t.hash = $2 AND t.kind = 'create_market' AND t."marketId" = m.id
+          AND m."blockchainMarketId" IS NULL
+          AND NOT EXISTS (
+              SELECT 1 FROM markets_extended e WHERE e."blockchainMarketId" = $1::numeric
+          )
+        "#,
+    )
+    .bind(market_id)
+    .bind(&meta.transaction_hash)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_raw_event(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    row: &RawEventRow,
+    meta: &LogMeta,
+) -> Result<bool> {
+    let columns = row
+        .columns
+        .iter()
+        .map(|(column, _)| format!("\"{}\"", column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let placeholders = row
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(index, (_, value))| match value {
+            EventValue::Numeric(_) => format!("${}::numeric", index + 2),
+            EventValue::Int(_) => format!("${}::bigint", index + 2),
+            EventValue::Text(_) | EventValue::Bool(_) => format!("${}", index + 2),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let next = row.columns.len() + 2;
+
+    let sql = format!(
+        r#"
+        INSERT INTO {} (id, {}, block_number, block_timestamp, transaction_hash)
+        VALUES ($1, {}, ${}::numeric, ${}::numeric, ${})
+        ON CONFLICT (id) DO NOTHING
+        "#,
+        row.table,
+        columns,
+        placeholders,
+        next,
+        next + 1,
+        next + 2
+    );
+
+    let mut query = sqlx::query(&sql).bind(&meta.id);
+    for (_, value) in &row.columns {
+        query = match value {
+            EventValue::Numeric(value) | EventValue::Text(value) => query.bind(value),
+            EventValue::Int(value) => query.bind(value),
+            EventValue::Bool(value) => query.bind(value),
+        };
+    }
+
+    let result = query
+        .bind(meta.block_number.to_string())
+        .bind(meta.block_timestamp.to_string())
+        .bind(&meta.transaction_hash)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+async fn project_event(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    event_type: &str,
+    id: &str,
+) -> Result<()> {
+    let mut savepoint = sqlx::Connection::begin(&mut **tx).await?;
+
+    let result = match projection::load_event(&mut savepoint, event_type, id).await {
+        Ok(Some(event)) => projection::apply(&mut savepoint, &event).await.map(|_| ()),
+        Ok(None) => Ok(()),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(()) => savepoint.commit().await?,
+        Err(_) => savepoint.rollback().await?,
+    }
+
+    result
+}
+
+async fn track_event(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    row: &RawEventRow,
+    meta: &LogMeta,
+    event_type: &str,
+    projection_error: Option<String>,
+    retry: &EventRetryPolicy,
+) -> Result<()> {
+    let processed = projection_error.is_none();
+    let failure = retry.after_failure(0);
+    let data = row
+        .columns
+        .iter()
+        .map(|(column, value)| {
+            let value = match value {
+                EventValue::Numeric(value) | EventValue::Text(value) => json!(value),
+                EventValue::Int(value) => json!(value),
+                EventValue::Bool(value) => json!(value),
+            };
+            (column.to_string(), value)
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO blockchain_events (
+            id, "eventType", "blockchainId", "blockNumber", "blockTimestamp", "transactionHash",
+            processed, status, data, "processedAt", attempts, "lastError", "nextRetryAt"
+        )
+        VALUES (
+            $1, $2, $1, $3, $4, $5, $6,
+            CASE WHEN $6 THEN 'processed' ELSE $9 END, $7,
+            CASE WHEN $6 THEN NOW() END, 1, $8, NOW() + make_interval(secs => $10)
+        )
+        ON CONFLICT ("eventType", "blockchainId") DO NOTHING
+        "#,
+        meta.id,
+        event_type,
+        meta.block_number as i64,
+        meta.block_timestamp as i64,
+        meta.transaction_hash,
+        processed,
+        serde_json::Value::Object(data).to_string(),
+        projection_error,
+        failure.status(),
+        failure.delay_secs() as f64
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+fn event_type(table: &str) -> String {
+    table
+        .strip_suffix('s')
+        .unwrap_or(table)
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::blockchain_sync::BetPlacedFilter;
+
+    #[test]
+    fn test_decodes_bet_placed_into_raw_row() {
+        let user: Address = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+            .parse()
+            .unwrap();
+        let event = BetPlacedFilter {
+            market_id: U256::from(3),
+            user,
+            position: true,
+            amount: U256::from(5_000_000),
+            shares: U256::from(4_900_000),
+        };
+        let log = Log {
+            topics: vec![
+                BetPlacedFilter::signature(),
+                H256::from_low_u64_be(3),
+                H256::from(user),
+            ],
+            data: ethers::abi::encode(&[
+                ethers::abi::Token::Bool(true),
+                ethers::abi::Token::Uint(event.amount),
+                ethers::abi::Token::Uint(event.shares),
+            ])
+            .into(),
+            ..Default::default()
+        };
+
+        let row = decode_log(IndexedContractKind::PredictionMarket, &log).unwrap();
+
+        assert_eq!(row.table, "bet_placeds");
+        assert_eq!(
+            row.columns,
+            vec![
+                ("market_id", EventValue::Numeric("3".to_string())),
+                (
+                    "user",
+                    EventValue::Text("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".to_string())
+                ),
+                ("position", EventValue::Bool(true)),
+                ("amount", EventValue::Numeric("5000000".to_string())),
+                ("shares", EventValue::Numeric("4900000".to_string())),
+            ]
+        );
+    }
+
+    fn block(number: u64, hash: H256) -> Block<H256> {
+        Block {
+            number: Some(U64::from(number)),
+            hash: Some(hash),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_finds_fork_point_after_reorg() {
+        let (provider, mock) = Provider::mocked();
+        let indexed = |number: u64| H256::from_low_u64_be(number);
+        let forked = |number: u64| H256::from_low_u64_be(1_000 + number);
+
+        // responses are served last-in first-out
+        mock.push::<Block<H256>, _>(block(8, indexed(8))).unwrap();
+        mock.push::<Block<H256>, _>(block(9, forked(9))).unwrap();
+        mock.push::<Block<H256>, _>(block(10, forked(10))).unwrap();
+
+        let head = canonical_hash(&provider, 10).await.unwrap();
+        assert_ne!(head, Some(format!("{:?}", indexed(10))));
+
+        let recorded = vec![
+            (9, format!("{:?}", indexed(9))),
+            (8, format!("{:?}", indexed(8))),
+            (7, format!("{:?}", indexed(7))),
+        ];
+        let fork = find_fork_point(&provider, &recorded, 0).await.unwrap();
+
+        assert_eq!(
+            fork,
+            ForkPoint {
+                block: 8,
+                hash: Some(format!("{:?}", indexed(8))),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_reorg_floor() {
+        let (provider, mock) = Provider::mocked();
+
+        mock.push::<Block<H256>, _>(block(4, H256::repeat_byte(4)))
+            .unwrap();
+        mock.push::<Block<H256>, _>(block(5, H256::repeat_byte(0xf5)))
+            .unwrap();
+
+        let recorded = vec![(5, format!("{:?}", H256::repeat_byte(5)))];
+        let fork = find_fork_point(&provider, &recorded, 4).await.unwrap();
+
+        assert_eq!(fork.block, 4);
+        assert_eq!(fork.hash, Some(format!("{:?}", H256::repeat_byte(4))));
+    }
+
+    #[test]
+    fn test_ignores_logs_from_other_contracts() {
+        let log = Log {
+            topics: vec![H256::repeat_byte(0xab)],
+            ..Default::default()
+        };
+
+        assert!(decode_log(IndexedContractKind::PredictionMarket, &log).is_none());
+        assert!(decode_log(IndexedContractKind::RebalancerDelegation, &log).is_none());
+    }
+
+    fn streamed_log(block: u64, log_index: u64) -> Log {
+        Log {
+            block_number: Some(U64::from(block)),
+            block_hash: Some(H256::from_low_u64_be(block)),
+            transaction_hash: Some(H256::from_low_u64_be(1_000 + block)),
+            log_index: Some(U256::from(log_index)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_streamed_logs_wait_for_confirmations() {
+        let mut pending = vec![streamed_log(8, 0), streamed_log(10, 0), streamed_log(9, 1)];
+
+        let confirmed = take_confirmed(&mut pending, 9);
+
+        assert_eq!(confirmed, vec![streamed_log(8, 0), streamed_log(9, 1)]);
+        assert_eq!(pending, vec![streamed_log(10, 0)]);
+        assert!(take_confirmed(&mut pending, 9).is_empty());
+    }
+
+    #[test]
+    fn test_removed_logs_are_dropped_before_ingestion() {
+        let mut pending = vec![streamed_log(10, 0), streamed_log(10, 1)];
+        let removed = Log {
+            removed: Some(true),
+            ..streamed_log(10, 1)
+        };
+
+        assert!(remove_pending(&mut pending, &removed));
+        assert_eq!(pending, vec![streamed_log(10, 0)]);
+        assert!(!remove_pending(&mut pending, &removed));
+    }
+
+    #[test]
+    fn test_event_types_follow_raw_table_names() {
+        assert_eq!(event_type("bet_placeds"), "BetPlaced");
+        assert_eq!(event_type("market_resolveds"), "MarketResolved");
+        assert_eq!(event_type("pauseds"), "Paused");
+    }
+}
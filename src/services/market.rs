@@ -2,8 +2,11 @@ use crate::{
     db::Database,
     error::{AppError, Result},
     models::*,
+    services::projection,
+    utils::MarketState,
 };
 use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
 use sqlx::Row;
 
 pub struct MarketService {
@@ -16,10 +19,9 @@ impl MarketService {
     }
 
     pub async fn get_markets(&self, params: MarketQueryParams) -> Result<MarketResponse> {
-        let status_filter = match params.status {
-            MarketStatus::Active => "WHERE m.status = 'active'",
-            MarketStatus::Resolved => "WHERE m.status = 'resolved'",
-            MarketStatus::All => "",
+        let status_filter = match params.status.state() {
+            Some(state) => format!("WHERE m.status = '{}'", state.as_str()),
+            None => "WHERE m.status <> 'draft'".to_string(),
         };
 
         let sort_column = match params.sort_by {
@@ -212,81 +214,126 @@ impl MarketService {
         Ok(id)
     }
 
-    pub async fn resolve_market(&self, blockchain_market_id: &str, outcome: bool) -> Result<()> {
-        sqlx::query(
+    pub async fn transition_market(
+        &self,
+        id: &str,
+        next: MarketState,
+        actor: &str,
+        reason: Option<&str>,
+        start_date: Option<NaiveDateTime>,
+    ) -> Result<MarketExtended> {
+        let mut tx = self.db.pool().begin().await?;
+
+        let market = sqlx::query!(
             r#"
-            UPDATE markets_extended
-            SET status = 'resolved', result = $2, "updatedAt" = CURRENT_TIMESTAMP
-            WHERE "marketId" = $1
+            SELECT id, status, "endDate" > NOW() as "open!", "startDate" as start_date,
+                   "endDate" as end_date
+            FROM markets_extended
+            WHERE id = $1 OR "marketId" = $1
+            FOR UPDATE
             "#,
+            id
         )
-        .bind(blockchain_market_id)
-        .bind(outcome)
-        .execute(self.db.pool())
-        .await?;
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Market with id {} not found", id)))?;
+
+        let current = market.status.parse::<MarketState>()?;
+
+        if matches!(next, MarketState::PendingResolution | MarketState::Resolved)
+            || current == MarketState::PendingResolution
+        {
+            return Err(AppError::BadRequest(
+                "Resolution states are managed through the resolution workflow".to_string(),
+            ));
+        }
+        if matches!(next, MarketState::Scheduled | MarketState::Active) && !market.open {
+            return Err(AppError::BadRequest(format!(
+                "Market {} has already passed its end date",
+                id
+            )));
+        }
+        if next == MarketState::Scheduled && start_date.or(market.start_date).is_none() {
+            return Err(AppError::BadRequest(
+                "A scheduled market needs a start date".to_string(),
+            ));
+        }
 
-        Ok(())
+        if let Some(start_date) = start_date {
+            if !matches!(current, MarketState::Draft | MarketState::Scheduled) {
+                return Err(AppError::BadRequest(format!(
+                    "Market {} is not a draft or scheduled market",
+                    id
+                )));
+            }
+            if start_date >= market.end_date {
+                return Err(AppError::BadRequest(format!(
+                    "Start date {} must be before the market end date {}",
+                    start_date, market.end_date
+                )));
+            }
+
+            sqlx::query!(
+                r#"
+                UPDATE markets_extended
+                SET "startDate" = $2, "updatedAt" = NOW()
+                WHERE id = $1
+                "#,
+                market.id,
+                start_date
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        apply_transition(&mut tx, &market.id, current, next, actor, reason).await?;
+
+        tx.commit().await?;
+
+        self.get_market_by_id(&market.id).await
     }
 
-    pub async fn recalculate_market_stats(&self, market_id: &str) -> Result<()> {
-        let stats = sqlx::query(
+    pub async fn advance_lifecycle(&self) -> Result<(usize, usize)> {
+        let opened = sqlx::query!(
             r#"
-            SELECT 
-                COALESCE(SUM(CASE WHEN position = true THEN amount ELSE 0 END), 0) as yes_volume,
-                COALESCE(SUM(CASE WHEN position = false THEN amount ELSE 0 END), 0) as no_volume,
-                COALESCE(COUNT(CASE WHEN position = true THEN 1 END), 0) as yes_count,
-                COALESCE(COUNT(CASE WHEN position = false THEN 1 END), 0) as no_count,
-                COALESCE(SUM(amount), 0) as total_volume
-            FROM bets_extended
-            WHERE "marketId" = $1 AND status = 'active'
-            "#,
+            WITH opened AS (
+                UPDATE markets_extended
+                SET status = 'active', "updatedAt" = NOW()
+                WHERE status = 'scheduled' AND "startDate" <= NOW()
+                RETURNING id
+            )
+            INSERT INTO market_status_history ("marketId", "fromStatus", "toStatus", actor, reason)
+            SELECT id, 'scheduled', 'active', 'scheduler', 'start date reached' FROM opened
+            RETURNING "marketId"
+            "#
         )
-        .bind(market_id)
-        .fetch_one(self.db.pool())
+        .fetch_all(self.db.pool())
         .await?;
 
-        let yes_volume: BigDecimal = stats.try_get("yes_volume")?;
-        let no_volume: BigDecimal = stats.try_get("no_volume")?;
-        let yes_count: i64 = stats.try_get("yes_count")?;
-        let no_count: i64 = stats.try_get("no_count")?;
-
-        let volume = &yes_volume + &no_volume;
-
-        let probability = if volume > BigDecimal::from(0) {
-            ((&yes_volume / &volume) * BigDecimal::from(100))
-                .to_string()
-                .parse::<i32>()
-                .unwrap_or(50)
-        } else {
-            50
-        };
-
-        sqlx::query(
+        let closed = sqlx::query!(
             r#"
-            UPDATE markets_extended
-            SET 
-                "yesPoolSize" = $2,
-                "noPoolSize" = $3,
-                "countYes" = $4,
-                "countNo" = $5,
-                volume = $6,
-                "totalPoolSize" = $7,
-                probability = $8,
-                "updatedAt" = CURRENT_TIMESTAMP
-            WHERE id = $1
-            "#,
+            WITH closed AS (
+                UPDATE markets_extended
+                SET status = 'closed', "updatedAt" = NOW()
+                WHERE status = 'active' AND "endDate" <= NOW()
+                RETURNING id
+            )
+            INSERT INTO market_status_history ("marketId", "fromStatus", "toStatus", actor, reason)
+            SELECT id, 'active', 'closed', 'scheduler', 'end date reached' FROM closed
+            RETURNING "marketId"
+            "#
         )
-        .bind(market_id)
-        .bind(&yes_volume)
-        .bind(&no_volume)
-        .bind(yes_count as i32)
-        .bind(no_count as i32)
-        .bind(&volume)
-        .bind(&volume)
-        .bind(probability)
-        .execute(self.db.pool())
+        .fetch_all(self.db.pool())
         .await?;
 
+        Ok((opened.len(), closed.len()))
+    }
+
+    pub async fn recalculate_market_stats(&self, market_id: &str) -> Result<()> {
+        let mut tx = self.db.pool().begin().await?;
+        projection::refresh_market(&mut tx, market_id).await?;
+        tx.commit().await?;
+
         Ok(())
     }
 
@@ -313,3 +360,106 @@ impl MarketService {
         Ok(max_apy)
     }
 }
+
+pub(crate) async fn apply_transition(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    market_id: &str,
+    current: MarketState,
+    next: MarketState,
+    actor: &str,
+    reason: Option<&str>,
+) -> Result<()> {
+    current.transition_to(next)?;
+
+    sqlx::query!(
+        r#"UPDATE markets_extended SET status = $2, "updatedAt" = NOW() WHERE id = $1"#,
+        market_id,
+        next.as_str()
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO market_status_history ("marketId", "fromStatus", "toStatus", actor, reason)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        market_id,
+        current.as_str(),
+        next.as_str(),
+        actor,
+        reason
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_rejected_transition_keeps_start_date(pool: sqlx::PgPool) {
+        sqlx::query(
+            r#"INSERT INTO markets_extended (id, "blockchainMarketId", question, "endDate", status)
+               VALUES ('market-1', 7, 'Will it rain?', NOW() + INTERVAL '10 days', 'draft')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let service = MarketService::new(Database::from_pool(pool.clone()));
+        let start_date =
+            |days: i64| Some(chrono::Utc::now().naive_utc() + chrono::Duration::days(days));
+
+        assert!(service
+            .transition_market(
+                "market-1",
+                MarketState::Closed,
+                "admin",
+                None,
+                start_date(1)
+            )
+            .await
+            .is_err());
+        assert!(service
+            .transition_market(
+                "market-1",
+                MarketState::Scheduled,
+                "admin",
+                None,
+                start_date(10)
+            )
+            .await
+            .is_err());
+
+        let market = sqlx::query!(
+            r#"SELECT status, "startDate" as start_date FROM markets_extended WHERE id = 'market-1'"#
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(market.status, "draft");
+        assert_eq!(market.start_date, None);
+
+        service
+            .transition_market(
+                "market-1",
+                MarketState::Scheduled,
+                "admin",
+                None,
+                start_date(1),
+            )
+            .await
+            .unwrap();
+        let market = sqlx::query!(
+            r#"SELECT status, "startDate" as start_date FROM markets_extended WHERE id = 'market-1'"#
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(market.status, "scheduled");
+        assert!(market.start_date.is_some());
+    }
+}
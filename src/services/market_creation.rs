@@ -0,0 +1,365 @@
+use ethers::types::H256;
+use serde_json::json;
+use tracing::{error, info, warn};
+
+use crate::{
+    config::Config,
+    db::Database,
+    error::{AppError, Result},
+    services::{
+        outbox::{enqueue, requeue, ContractCall},
+        BlockchainSyncService, SignerService,
+    },
+};
+
+pub struct CreatedMarket {
+    pub id: String,
+    pub market_id: String,
+    pub adj_ticker: String,
+    pub blockchain_market_id: Option<i64>,
+    pub end_time: i64,
+    pub status: String,
+    pub tx_hash: Option<String>,
+    pub block_number: Option<i64>,
+    pub replayed: bool,
+}
+
+pub struct MarketCreationService {
+    db: Database,
+}
+
+impl MarketCreationService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_market(
+        &self,
+        config: &Config,
+        idempotency_key: &str,
+        question: &str,
+        description: &str,
+        duration: i64,
+        image_url: Option<&str>,
+        created_by: &str,
+    ) -> Result<CreatedMarket> {
+        if idempotency_key.trim().is_empty() || idempotency_key.len() > 255 {
+            return Err(AppError::BadRequest(
+                "Idempotency-Key header must be between 1 and 255 characters".to_string(),
+            ));
+        }
+        if question.trim().is_empty() {
+            return Err(AppError::BadRequest("Question is required".to_string()));
+        }
+        if duration <= 0 {
+            return Err(AppError::BadRequest(
+                "Duration must be a positive number of seconds".to_string(),
+            ));
+        }
+
+        SignerService::shared()?;
+
+        let request_hash = hex::encode(ethers::utils::keccak256(
+            json!({
+                "question": question,
+                "description": description,
+                "duration": duration,
+                "imageUrl": image_url,
+            })
+            .to_string()
+            .as_bytes(),
+        ));
+
+        let mut tx = self.db.pool().begin().await?;
+
+        let claimed = sqlx::query!(
+            r#"
+            INSERT INTO market_creation_requests ("idempotencyKey", "requestHash", "createdBy")
+            VALUES ($1, $2, $3)
+            ON CONFLICT ("idempotencyKey") DO NOTHING
+            RETURNING "idempotencyKey"
+            "#,
+            idempotency_key,
+            request_hash,
+            created_by
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let existing_market_id = if claimed.is_some() {
+            None
+        } else {
+            let existing = sqlx::query!(
+                r#"
+                SELECT "requestHash" as request_hash, "marketId" as market_id, status
+                FROM market_creation_requests
+                WHERE "idempotencyKey" = $1
+                "#,
+                idempotency_key
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            if existing.request_hash != request_hash {
+                return Err(AppError::BadRequest(
+                    "Idempotency-Key was already used with a different request".to_string(),
+                ));
+            }
+
+            if existing.status != "failed" {
+                return self.load_created(idempotency_key, true).await;
+            }
+
+            let reclaimed = sqlx::query!(
+                r#"
+                UPDATE market_creation_requests
+                SET status = 'pending', error = NULL
+                WHERE "idempotencyKey" = $1 AND status = 'failed'
+                RETURNING "idempotencyKey"
+                "#,
+                idempotency_key
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            if reclaimed.is_none() {
+                return self.load_created(idempotency_key, true).await;
+            }
+            warn!("🔁 Retrying failed market creation {}", idempotency_key);
+            existing.market_id
+        };
+
+        let end_time = chrono::Utc::now().timestamp() + duration;
+
+        let end_date = chrono::DateTime::from_timestamp(end_time, 0)
+            .ok_or_else(|| AppError::BadRequest("Duration is out of range".to_string()))?
+            .naive_utc();
+
+        let call = ContractCall::CreateMarket {
+            question: question.to_string(),
+            end_time: end_time as u64,
+        };
+        let payload = serde_json::to_string(&call)
+            .map_err(|e| AppError::Internal(format!("Failed to encode outbox payload: {}", e)))?;
+
+        let mut requeued = None;
+        let market_id = match existing_market_id {
+            Some(market_id) => {
+                let failed_entry = sqlx::query_scalar!(
+                    r#"
+                    SELECT id FROM outbox
+                    WHERE "marketId" = $1 AND kind = 'create_market' AND status = 'failed'
+                    ORDER BY "createdAt" DESC
+                    LIMIT 1
+                    "#,
+                    market_id
+                )
+                .fetch_optional(&mut *tx)
+                .await?;
+                if let Some(entry_id) = failed_entry {
+                    requeued = requeue(&mut tx, &entry_id)
+                        .await?
+                        .map(|entry| (entry_id, entry.resumes_signed));
+                }
+
+                if let Some((entry_id, true)) = &requeued {
+                    warn!(
+                        "🔁 Outbox entry {} resumes its signed createMarket transaction",
+                        entry_id
+                    );
+                } else {
+                    sqlx::query!(
+                        r#"UPDATE markets_extended SET "endDate" = $2, "updatedAt" = NOW() WHERE id = $1"#,
+                        market_id,
+                        end_date
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                market_id
+            }
+            None => {
+                let id = uuid::Uuid::new_v4().to_string();
+                let short_id = id.replace('-', "")[..8].to_uppercase();
+
+                sqlx::query!(
+                    r#"
+                    INSERT INTO markets_extended (
+                        id, "marketId", "adjTicker", question, description, "imageUrl",
+                        "endDate", status, "createdAt", "updatedAt"
+                    )
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, 'draft', NOW(), NOW())
+                    "#,
+                    id,
+                    format!("whizy-{}", short_id.to_lowercase()),
+                    format!("WHIZY-{}", short_id),
+                    question,
+                    description,
+                    image_url,
+                    end_date
+                )
+                .execute(&mut *tx)
+                .await?;
+
+                sqlx::query!(
+                    r#"UPDATE market_creation_requests SET "marketId" = $2 WHERE "idempotencyKey" = $1"#,
+                    idempotency_key,
+                    id
+                )
+                .execute(&mut *tx)
+                .await?;
+
+                id
+            }
+        };
+
+        match &requeued {
+            Some((_, true)) => {}
+            Some((entry_id, false)) => {
+                sqlx::query!(
+                    "UPDATE outbox SET payload = $2 WHERE id = $1",
+                    entry_id,
+                    payload
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            None => {
+                enqueue(
+                    &mut tx,
+                    &call,
+                    Some(&market_id),
+                    None,
+                    config.outbox_max_attempts,
+                )
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        info!(
+            "📝 Market {} queued for on-chain creation ({})",
+            market_id, idempotency_key
+        );
+
+        self.load_created(idempotency_key, false).await
+    }
+
+    pub async fn complete(&self, config: &Config, market_id: &str, tx_hash: H256) -> Result<()> {
+        let sync_service = BlockchainSyncService::new(self.db.clone());
+        let on_chain = sync_service
+            .wait_for_market_created(config, tx_hash)
+            .await?;
+
+        let mut tx = self.db.pool().begin().await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE markets_extended
+            SET "blockchainMarketId" = $2, "updatedAt" = NOW()
+            WHERE id = $1
+            "#,
+            market_id,
+            on_chain.blockchain_market_id as i64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            WITH activated AS (
+                UPDATE markets_extended
+                SET status = 'active', "updatedAt" = NOW()
+                WHERE id = $1 AND status = 'draft'
+                RETURNING id
+            )
+            INSERT INTO market_status_history ("marketId", "fromStatus", "toStatus", actor, reason)
+            SELECT id, 'draft', 'active',
+                   COALESCE(
+                       (SELECT "createdBy" FROM market_creation_requests WHERE "marketId" = $1),
+                       'system'
+                   ),
+                   'created on-chain'
+            FROM activated
+            "#,
+            market_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE market_creation_requests
+            SET status = 'confirmed', "txHash" = $2, "blockNumber" = $3, error = NULL
+            WHERE "marketId" = $1
+            "#,
+            market_id,
+            format!("{:?}", tx_hash),
+            on_chain.block_number as i64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        info!(
+            "✅ Market {} linked to blockchain market {}",
+            market_id, on_chain.blockchain_market_id
+        );
+
+        Ok(())
+    }
+
+    async fn load_created(&self, idempotency_key: &str, replayed: bool) -> Result<CreatedMarket> {
+        let row = sqlx::query!(
+            r#"
+            SELECT m.id, m."marketId" as market_id, m."adjTicker" as adj_ticker,
+                   m."blockchainMarketId" as blockchain_market_id, m."endDate" as end_date,
+                   r.status, r."txHash" as tx_hash, r."blockNumber" as block_number
+            FROM market_creation_requests r
+            JOIN markets_extended m ON r."marketId" = m.id
+            WHERE r."idempotencyKey" = $1
+            "#,
+            idempotency_key
+        )
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "Market for creation request {} no longer exists",
+                idempotency_key
+            ))
+        })?;
+
+        Ok(CreatedMarket {
+            id: row.id,
+            market_id: row.market_id.unwrap_or_default(),
+            adj_ticker: row.adj_ticker.unwrap_or_default(),
+            blockchain_market_id: row.blockchain_market_id,
+            end_time: row.end_date.and_utc().timestamp(),
+            status: row.status,
+            tx_hash: row.tx_hash,
+            block_number: row.block_number,
+            replayed,
+        })
+    }
+
+    pub async fn mark_failed(&self, market_id: &str, reason: &str) -> Result<()> {
+        error!("❌ Market creation for {} failed: {}", market_id, reason);
+        sqlx::query!(
+            r#"
+            UPDATE market_creation_requests
+            SET status = 'failed', error = $2
+            WHERE "marketId" = $1 AND status <> 'confirmed'
+            "#,
+            market_id,
+            reason
+        )
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+}
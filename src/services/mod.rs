@@ -1,25 +1,57 @@
 pub mod adjacent;
+pub mod api_key;
+pub mod auth;
+pub mod auto_rebalance;
 pub mod bet;
 pub mod betting_service;
+pub mod blockchain_event;
 pub mod blockchain_sync;
 pub mod blockchain_yield;
 pub mod image_service;
+pub mod indexer;
 pub mod market;
+pub mod market_creation;
 pub mod market_seeder;
+pub mod multicall;
+pub mod outbox;
+pub mod projection;
 pub mod protocol;
+pub mod rebalancer;
+pub mod replay;
+pub mod resolution;
+pub mod rpc;
 pub mod scheduler;
+pub mod session;
+pub mod signer;
 pub mod stats;
 pub mod sync;
+pub mod transaction;
 pub mod user;
 
+pub use api_key::{ApiKeyContext, ApiKeyService};
+pub use auth::AuthService;
+pub use auto_rebalance::AutoRebalanceService;
 pub use bet::BetService;
 pub use betting_service::BettingService;
+pub use blockchain_event::BlockchainEventService;
 pub use blockchain_sync::BlockchainSyncService;
 pub use blockchain_yield::BlockchainYieldService;
+pub use indexer::IndexerService;
 pub use market::MarketService;
+pub use market_creation::MarketCreationService;
 pub use market_seeder::MarketSeeder;
+pub use multicall::MulticallReader;
+pub use outbox::OutboxService;
+pub use projection::ProjectionService;
 pub use protocol::ProtocolService;
+pub use rebalancer::RebalancerService;
+pub use replay::ReplayService;
+pub use resolution::ResolutionService;
+pub use rpc::RpcPool;
 pub use scheduler::Scheduler;
+pub use session::SessionService;
+pub use signer::SignerService;
 pub use stats::StatsService;
 pub use sync::SyncService;
+pub use transaction::TransactionService;
 pub use user::UserService;
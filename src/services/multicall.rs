@@ -0,0 +1,201 @@
+use ethers::{
+    abi::Detokenize,
+    contract::{ContractCall, Multicall, MulticallVersion},
+    prelude::*,
+};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::{
+    config::Config,
+    error::{AppError, Result},
+};
+
+pub struct MulticallReader<M> {
+    client: Arc<M>,
+    address: Address,
+    batch_size: usize,
+}
+
+impl<M: Middleware + 'static> MulticallReader<M> {
+    pub fn new(client: Arc<M>, config: &Config) -> Result<Self> {
+        let address = config
+            .multicall_address
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid multicall address: {}", e)))?;
+
+        Ok(Self::with_settings(
+            client,
+            address,
+            config.multicall_batch_size,
+        ))
+    }
+
+    pub fn with_settings(client: Arc<M>, address: Address, batch_size: usize) -> Self {
+        Self {
+            client,
+            address,
+            batch_size: batch_size.max(1),
+        }
+    }
+
+    pub async fn read<D: Detokenize>(&self, calls: Vec<ContractCall<M, D>>) -> Vec<Option<D>> {
+        let mut results = Vec::with_capacity(calls.len());
+
+        for batch in calls.chunks(self.batch_size) {
+            match self.aggregate(batch).await {
+                Ok(batch_results) => results.extend(batch_results),
+                Err(e) => {
+                    warn!(
+                        "⚠️ [Multicall] Batch of {} calls failed, falling back to single calls: {}",
+                        batch.len(),
+                        e
+                    );
+                    for call in batch {
+                        results.push(call.call().await.ok());
+                    }
+                }
+            }
+        }
+
+        results
+    }
+
+    async fn aggregate<D: Detokenize>(
+        &self,
+        batch: &[ContractCall<M, D>],
+    ) -> Result<Vec<Option<D>>> {
+        let mut multicall = Multicall::new(self.client.clone(), Some(self.address))
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to build multicall: {}", e)))?
+            .version(MulticallVersion::Multicall3);
+
+        for call in batch {
+            multicall.add_call(call.clone(), true);
+        }
+
+        let tokens = multicall
+            .call_raw()
+            .await
+            .map_err(|e| AppError::Internal(format!("Multicall failed: {}", e)))?;
+
+        if tokens.len() != batch.len() {
+            return Err(AppError::Internal(format!(
+                "Multicall returned {} results for {} calls",
+                tokens.len(),
+                batch.len()
+            )));
+        }
+
+        Ok(tokens
+            .into_iter()
+            .map(|token| {
+                token
+                    .ok()
+                    .and_then(|token| D::from_tokens(vec![token]).ok())
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::blockchain_sync::WhizyPredictionMarket;
+    use ethers::abi::{self, Token};
+
+    fn reader(
+        mock: &MockProvider,
+    ) -> (
+        MulticallReader<Provider<MockProvider>>,
+        Arc<Provider<MockProvider>>,
+    ) {
+        let client = Arc::new(Provider::new(mock.clone()));
+        (
+            MulticallReader::with_settings(client.clone(), Address::repeat_byte(0xca), 2),
+            client,
+        )
+    }
+
+    fn market_data(id: u64) -> Bytes {
+        abi::encode(&[
+            Token::Uint(U256::from(id)),
+            Token::String(format!("Market {}", id)),
+            Token::Uint(U256::from(1_900_000_000u64)),
+            Token::Address(Address::repeat_byte(0x01)),
+            Token::Address(Address::repeat_byte(0x02)),
+            Token::Uint(U256::from(10 * id)),
+            Token::Uint(U256::zero()),
+            Token::Bool(false),
+            Token::Bool(false),
+            Token::Uint(U256::zero()),
+        ])
+        .into()
+    }
+
+    fn aggregate_result(results: Vec<Option<Bytes>>) -> Bytes {
+        abi::encode(&[Token::Array(
+            results
+                .into_iter()
+                .map(|result| {
+                    Token::Tuple(vec![
+                        Token::Bool(result.is_some()),
+                        Token::Bytes(result.unwrap_or_default().to_vec()),
+                    ])
+                })
+                .collect(),
+        )])
+        .into()
+    }
+
+    #[tokio::test]
+    async fn test_failed_call_does_not_fail_batch() {
+        let mock = MockProvider::new();
+        let (reader, client) = reader(&mock);
+        let contract = WhizyPredictionMarket::new(Address::repeat_byte(0x33), client);
+
+        mock.push::<Bytes, _>(aggregate_result(vec![Some(market_data(2))]))
+            .unwrap();
+        mock.push::<Bytes, _>(aggregate_result(vec![Some(market_data(0)), None]))
+            .unwrap();
+
+        let markets = reader
+            .read(
+                (0..3u64)
+                    .map(|id| contract.markets(U256::from(id)))
+                    .collect(),
+            )
+            .await;
+
+        assert_eq!(markets.len(), 3);
+        assert_eq!(markets[0].as_ref().unwrap().1, "Market 0");
+        assert!(markets[1].is_none());
+        assert_eq!(markets[2].as_ref().unwrap().5, U256::from(20));
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_single_calls() {
+        let mock = MockProvider::new();
+        let (reader, client) = reader(&mock);
+        let contract = WhizyPredictionMarket::new(Address::repeat_byte(0x33), client);
+
+        mock.push::<Bytes, _>(market_data(1)).unwrap();
+        mock.push::<Bytes, _>(market_data(0)).unwrap();
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: -32000,
+            message: "execution reverted".to_string(),
+            data: None,
+        }));
+
+        let markets = reader
+            .read(
+                (0..2u64)
+                    .map(|id| contract.markets(U256::from(id)))
+                    .collect(),
+            )
+            .await;
+
+        assert_eq!(markets[0].as_ref().unwrap().1, "Market 0");
+        assert_eq!(markets[1].as_ref().unwrap().1, "Market 1");
+    }
+}
@@ -0,0 +1,913 @@
+use ethers::{prelude::*, types::transaction::eip2718::TypedTransaction};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    config::Config,
+    db::Database,
+    error::{AppError, Result},
+    models::OutboxEntry,
+    services::{
+        signer::is_nonce_error,
+        transaction::{record_transaction, SubmittedTransaction},
+        BettingService, BlockchainSyncService, MarketCreationService, RebalancerService,
+        SignerService,
+    },
+};
+
+const OUTBOX_COLUMNS: &str = r#"id, kind, "marketId", "betId", payload, status, attempts, "maxAttempts", "nextAttemptAt", "txHash", "lastError", "completedAt", "createdAt", "updatedAt""#;
+const OUTBOX_BATCH_SIZE: i64 = 20;
+const OUTBOX_LEASE_SECS: f64 = 300.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    tag = "call",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ContractCall {
+    CreateMarket {
+        question: String,
+        end_time: u64,
+    },
+    PlaceBet {
+        blockchain_market_id: u64,
+        position: bool,
+        amount: u64,
+    },
+    Rebalance {
+        user: String,
+        protocol: String,
+    },
+}
+
+impl ContractCall {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ContractCall::CreateMarket { .. } => "create_market",
+            ContractCall::PlaceBet { .. } => "place_bet",
+            ContractCall::Rebalance { .. } => "rebalance",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct OutboxSummary {
+    pub submitted: usize,
+    pub completed: usize,
+    pub retried: usize,
+    pub failed: usize,
+}
+
+struct ClaimedEntry {
+    id: String,
+    kind: String,
+    market_id: Option<String>,
+    bet_id: Option<String>,
+    payload: String,
+    request: Option<String>,
+    signed_transaction: Option<String>,
+    tx_hash: Option<String>,
+    attempts: i32,
+    max_attempts: i32,
+}
+
+#[derive(Debug, PartialEq)]
+enum RetryDecision {
+    Retry { delay_secs: u64 },
+    Fail,
+}
+
+#[derive(Debug, PartialEq)]
+enum BroadcastFailure {
+    Mined,
+    Resign,
+    Retry,
+}
+
+#[derive(Debug, PartialEq)]
+enum DomainFailure<'a> {
+    MarketCreation(&'a str),
+    Bet(&'a str),
+}
+
+struct SiblingTransaction {
+    hash: String,
+    status: String,
+    error: Option<String>,
+    created_at: chrono::NaiveDateTime,
+}
+
+pub struct OutboxService {
+    db: Database,
+}
+
+impl OutboxService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn list_entries(&self, status: Option<&str>, limit: i64) -> Result<Vec<OutboxEntry>> {
+        let entries = sqlx::query_as::<_, OutboxEntry>(&format!(
+            r#"
+            SELECT {}
+            FROM outbox
+            WHERE $1::text IS NULL OR status = $1
+            ORDER BY "createdAt" DESC
+            LIMIT $2
+            "#,
+            OUTBOX_COLUMNS
+        ))
+        .bind(status)
+        .bind(limit.clamp(1, 500))
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(entries)
+    }
+
+    pub async fn get_entry(&self, id: &str) -> Result<OutboxEntry> {
+        sqlx::query_as::<_, OutboxEntry>(&format!(
+            "SELECT {} FROM outbox WHERE id = $1",
+            OUTBOX_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Outbox entry {} not found", id)))
+    }
+
+    pub async fn retry_entry(&self, id: &str) -> Result<OutboxEntry> {
+        let mut tx = self.db.pool().begin().await?;
+
+        let entry = requeue(&mut tx, id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Outbox entry {} is not failed", id)))?;
+
+        match entry.kind.as_str() {
+            "create_market" => {
+                sqlx::query!(
+                    r#"
+                    UPDATE market_creation_requests SET status = 'pending', error = NULL
+                    WHERE "marketId" = $1 AND status = 'failed'
+                    "#,
+                    entry.market_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            "place_bet" => {
+                sqlx::query!(
+                    r#"UPDATE bets_extended SET status = 'pending' WHERE id = $1 AND status = 'failed'"#,
+                    entry.bet_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            _ => {}
+        }
+
+        tx.commit().await?;
+
+        warn!("🔁 Outbox entry {} requeued by an admin", id);
+        self.get_entry(id).await
+    }
+
+    pub async fn process(&self, config: &Config) -> Result<OutboxSummary> {
+        let mut summary = OutboxSummary::default();
+        let signer = SignerService::shared()?;
+
+        let claimed = claim_batch(self.db.pool()).await?;
+
+        for entry in claimed {
+            match self.dispatch(config, &signer, &entry).await {
+                Ok(hash) => {
+                    info!("📤 Outbox entry {} submitted as {:?}", entry.id, hash);
+                    summary.submitted += 1;
+                }
+                Err(e) => {
+                    if self.schedule_retry(config, &entry, &e.to_string()).await? {
+                        summary.failed += 1;
+                    } else {
+                        summary.retried += 1;
+                    }
+                }
+            }
+        }
+
+        let (completed, failed) = self.settle_submitted(config).await?;
+        summary.completed += completed;
+        summary.failed += failed;
+
+        Ok(summary)
+    }
+
+    async fn dispatch(
+        &self,
+        config: &Config,
+        signer: &SignerService,
+        entry: &ClaimedEntry,
+    ) -> Result<H256> {
+        let call: ContractCall = serde_json::from_str(&entry.payload)
+            .map_err(|e| AppError::Internal(format!("Invalid outbox payload: {}", e)))?;
+
+        let (submitted, signed_transaction) = match (
+            entry.request.as_deref(),
+            entry.signed_transaction.as_deref(),
+            entry.tx_hash.as_deref(),
+        ) {
+            (Some(request), Some(signed_transaction), Some(tx_hash)) => {
+                let request: TypedTransaction = serde_json::from_str(request)
+                    .map_err(|e| AppError::Internal(format!("Invalid stored request: {}", e)))?;
+                let hash: H256 = tx_hash
+                    .parse()
+                    .map_err(|_| AppError::Internal(format!("Invalid hash {}", tx_hash)))?;
+                let signed_transaction: Bytes = signed_transaction.parse().map_err(|e| {
+                    AppError::Internal(format!("Invalid stored transaction: {}", e))
+                })?;
+                (
+                    SubmittedTransaction::from_request(hash, signer.address(), &request),
+                    signed_transaction,
+                )
+            }
+            _ => {
+                let request = self.build_call(config, signer, &call).await?;
+                let (submitted, signed_transaction) = signer.sign(request).await?;
+
+                sqlx::query!(
+                    r#"
+                    UPDATE outbox SET request = $2, "signedTransaction" = $3, "txHash" = $4
+                    WHERE id = $1
+                    "#,
+                    entry.id,
+                    serde_json::to_string(&submitted.request).ok(),
+                    signed_transaction.to_string(),
+                    format!("{:?}", submitted.hash)
+                )
+                .execute(self.db.pool())
+                .await?;
+
+                (submitted, signed_transaction)
+            }
+        };
+
+        if let Err(e) = signer.broadcast(signed_transaction).await {
+            let mined = signer
+                .client()
+                .get_transaction_receipt(submitted.hash)
+                .await
+                .ok()
+                .flatten()
+                .is_some();
+
+            match broadcast_failure(mined, &e.to_string()) {
+                BroadcastFailure::Mined => {}
+                BroadcastFailure::Resign => {
+                    warn!(
+                        "⚠️ Nonce of outbox entry {} was used by another transaction, re-signing",
+                        entry.id
+                    );
+                    sqlx::query!(
+                        r#"
+                        UPDATE outbox SET request = NULL, "signedTransaction" = NULL, "txHash" = NULL
+                        WHERE id = $1
+                        "#,
+                        entry.id
+                    )
+                    .execute(self.db.pool())
+                    .await?;
+                    return Err(e);
+                }
+                BroadcastFailure::Retry => return Err(e),
+            }
+        }
+
+        let mut tx = self.db.pool().begin().await?;
+
+        record_transaction(
+            &mut tx,
+            &entry.kind,
+            &submitted,
+            entry.market_id.as_deref(),
+            entry.bet_id.as_deref(),
+        )
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE outbox
+            SET status = 'submitted', attempts = attempts + 1, "lockedAt" = NULL, "lastError" = NULL
+            WHERE id = $1
+            "#,
+            entry.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if let ContractCall::CreateMarket { .. } = call {
+            sqlx::query!(
+                r#"
+                UPDATE market_creation_requests SET status = 'submitted', "txHash" = $2
+                WHERE "marketId" = $1 AND status = 'pending'
+                "#,
+                entry.market_id,
+                format!("{:?}", submitted.hash)
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(submitted.hash)
+    }
+
+    async fn build_call(
+        &self,
+        config: &Config,
+        signer: &SignerService,
+        call: &ContractCall,
+    ) -> Result<TypedTransaction> {
+        match call {
+            ContractCall::CreateMarket { question, end_time } => {
+                BlockchainSyncService::create_market_call(
+                    signer,
+                    &config.whizy_prediction_market_addr,
+                    &config.usdc_address,
+                    question,
+                    *end_time,
+                )
+            }
+            ContractCall::PlaceBet {
+                blockchain_market_id,
+                position,
+                amount,
+            } => {
+                BettingService::new(self.db.clone())
+                    .ensure_allowance(
+                        &config.whizy_prediction_market_addr,
+                        *blockchain_market_id,
+                        *amount,
+                    )
+                    .await?;
+                BettingService::place_bet_call(
+                    signer,
+                    &config.whizy_prediction_market_addr,
+                    *blockchain_market_id,
+                    *position,
+                    *amount,
+                )
+            }
+            ContractCall::Rebalance { user, protocol } => RebalancerService::rebalance_call(
+                signer,
+                config
+                    .rebalancer_delegation_addr
+                    .as_deref()
+                    .unwrap_or_default(),
+                user,
+                protocol,
+            ),
+        }
+    }
+
+    async fn schedule_retry(
+        &self,
+        config: &Config,
+        entry: &ClaimedEntry,
+        reason: &str,
+    ) -> Result<bool> {
+        let decision = retry_decision(
+            entry.attempts,
+            entry.max_attempts,
+            config.outbox_retry_backoff_secs,
+        );
+        let (status, delay_secs) = match decision {
+            RetryDecision::Retry { delay_secs } => ("pending", delay_secs),
+            RetryDecision::Fail => ("failed", 0),
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE outbox
+            SET attempts = attempts + 1, "lastError" = $2, "lockedAt" = NULL, status = $3,
+                "nextAttemptAt" = NOW() + make_interval(secs => $4)
+            WHERE id = $1
+            "#,
+            entry.id,
+            reason,
+            status,
+            delay_secs as f64
+        )
+        .execute(self.db.pool())
+        .await?;
+
+        if decision != RetryDecision::Fail {
+            warn!("⚠️ Outbox entry {} will be retried: {}", entry.id, reason);
+            return Ok(false);
+        }
+
+        error!(
+            "❌ Outbox entry {} failed permanently: {}",
+            entry.id, reason
+        );
+        self.fail_domain(
+            &entry.kind,
+            entry.market_id.as_deref(),
+            entry.bet_id.as_deref(),
+            reason,
+        )
+        .await?;
+
+        Ok(true)
+    }
+
+    async fn settle_submitted(&self, config: &Config) -> Result<(usize, usize)> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT o.id, o.kind, o."marketId" as market_id, o."betId" as bet_id,
+                   c.hash, c.status, c.error, c."createdAt" as created_at
+            FROM outbox o
+            JOIN transactions s ON s.hash = o."txHash"
+            JOIN transactions c ON c."fromAddress" = s."fromAddress" AND c.nonce = s.nonce
+            WHERE o.status = 'submitted'
+            ORDER BY o.id
+            "#
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut submitted: Vec<SubmittedEntry> = Vec::new();
+        for row in rows {
+            let sibling = SiblingTransaction {
+                hash: row.hash,
+                status: row.status,
+                error: row.error,
+                created_at: row.created_at,
+            };
+            match submitted.last_mut() {
+                Some(entry) if entry.id == row.id => entry.siblings.push(sibling),
+                _ => submitted.push(SubmittedEntry {
+                    id: row.id,
+                    kind: row.kind,
+                    market_id: row.market_id,
+                    bet_id: row.bet_id,
+                    siblings: vec![sibling],
+                }),
+            }
+        }
+
+        let mut completed = 0;
+        let mut failed = 0;
+
+        for submitted in submitted {
+            let Some(settled) = resolve_siblings(&submitted.siblings) else {
+                continue;
+            };
+            let entry = SettledEntry {
+                hash: settled.hash.clone(),
+                status: settled.status.clone(),
+                error: settled.error.clone(),
+                id: submitted.id,
+                kind: submitted.kind,
+                market_id: submitted.market_id,
+                bet_id: submitted.bet_id,
+            };
+
+            if entry.status == "failed" {
+                let reason = entry
+                    .error
+                    .unwrap_or_else(|| format!("Transaction {} failed", entry.hash));
+                sqlx::query!(
+                    r#"UPDATE outbox SET status = 'failed', "lastError" = $2 WHERE id = $1"#,
+                    entry.id,
+                    reason
+                )
+                .execute(self.db.pool())
+                .await?;
+                error!("❌ Outbox entry {} failed on-chain: {}", entry.id, reason);
+                self.fail_domain(
+                    &entry.kind,
+                    entry.market_id.as_deref(),
+                    entry.bet_id.as_deref(),
+                    &reason,
+                )
+                .await?;
+                failed += 1;
+                continue;
+            }
+
+            if let (Some(market_id), "create_market") = (&entry.market_id, entry.kind.as_str()) {
+                let hash: H256 = entry
+                    .hash
+                    .parse()
+                    .map_err(|_| AppError::Internal(format!("Invalid hash {}", entry.hash)))?;
+                if let Err(e) = MarketCreationService::new(self.db.clone())
+                    .complete(config, market_id, hash)
+                    .await
+                {
+                    error!(
+                        "❌ Could not link market {} from outbox entry {}: {}",
+                        market_id, entry.id, e
+                    );
+                    sqlx::query!(
+                        r#"UPDATE outbox SET "lastError" = $2 WHERE id = $1"#,
+                        entry.id,
+                        e.to_string()
+                    )
+                    .execute(self.db.pool())
+                    .await?;
+                    continue;
+                }
+            }
+
+            sqlx::query!(
+                r#"
+                UPDATE outbox
+                SET status = 'completed', "txHash" = $2, "lastError" = NULL, "completedAt" = NOW()
+                WHERE id = $1
+                "#,
+                entry.id,
+                entry.hash
+            )
+            .execute(self.db.pool())
+            .await?;
+            completed += 1;
+        }
+
+        Ok((completed, failed))
+    }
+
+    async fn fail_domain(
+        &self,
+        kind: &str,
+        market_id: Option<&str>,
+        bet_id: Option<&str>,
+        reason: &str,
+    ) -> Result<()> {
+        match domain_failure(kind, market_id, bet_id) {
+            Some(DomainFailure::MarketCreation(market_id)) => {
+                MarketCreationService::new(self.db.clone())
+                    .mark_failed(market_id, reason)
+                    .await
+            }
+            Some(DomainFailure::Bet(bet_id)) => {
+                sqlx::query!(
+                    r#"UPDATE bets_extended SET status = 'failed' WHERE id = $1 AND status = 'pending'"#,
+                    bet_id
+                )
+                .execute(self.db.pool())
+                .await?;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+pub(crate) struct RequeuedEntry {
+    pub kind: String,
+    pub market_id: Option<String>,
+    pub bet_id: Option<String>,
+    pub resumes_signed: bool,
+}
+
+struct SubmittedEntry {
+    id: String,
+    kind: String,
+    market_id: Option<String>,
+    bet_id: Option<String>,
+    siblings: Vec<SiblingTransaction>,
+}
+
+struct SettledEntry {
+    id: String,
+    kind: String,
+    market_id: Option<String>,
+    bet_id: Option<String>,
+    hash: String,
+    status: String,
+    error: Option<String>,
+}
+
+async fn claim_batch(pool: &sqlx::PgPool) -> Result<Vec<ClaimedEntry>> {
+    let claimed = sqlx::query_as!(
+        ClaimedEntry,
+        r#"
+        UPDATE outbox
+        SET status = 'processing', "lockedAt" = NOW()
+        WHERE id IN (
+            SELECT id FROM outbox
+            WHERE (status = 'pending' AND "nextAttemptAt" <= NOW())
+               OR (status = 'processing' AND "lockedAt" < NOW() - make_interval(secs => $2))
+            ORDER BY "createdAt"
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id, kind, "marketId" as market_id, "betId" as bet_id, payload, request,
+                  "signedTransaction" as signed_transaction, "txHash" as tx_hash,
+                  attempts, "maxAttempts" as max_attempts
+        "#,
+        OUTBOX_BATCH_SIZE,
+        OUTBOX_LEASE_SECS
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(claimed)
+}
+
+fn retry_decision(attempts: i32, max_attempts: i32, backoff_secs: u64) -> RetryDecision {
+    if attempts + 1 >= max_attempts {
+        return RetryDecision::Fail;
+    }
+
+    let factor = 1u64.checked_shl(attempts.max(0) as u32).unwrap_or(u64::MAX);
+    RetryDecision::Retry {
+        delay_secs: backoff_secs.saturating_mul(factor),
+    }
+}
+
+fn broadcast_failure(mined: bool, error: &str) -> BroadcastFailure {
+    if mined {
+        BroadcastFailure::Mined
+    } else if is_nonce_error(error) {
+        BroadcastFailure::Resign
+    } else {
+        BroadcastFailure::Retry
+    }
+}
+
+fn domain_failure<'a>(
+    kind: &str,
+    market_id: Option<&'a str>,
+    bet_id: Option<&'a str>,
+) -> Option<DomainFailure<'a>> {
+    match (kind, market_id, bet_id) {
+        ("create_market", Some(market_id), _) => Some(DomainFailure::MarketCreation(market_id)),
+        ("place_bet", _, Some(bet_id)) => Some(DomainFailure::Bet(bet_id)),
+        _ => None,
+    }
+}
+
+fn resolve_siblings(siblings: &[SiblingTransaction]) -> Option<&SiblingTransaction> {
+    siblings
+        .iter()
+        .filter(|tx| tx.status != "replaced")
+        .max_by_key(|tx| tx.created_at)
+        .filter(|tx| tx.status == "confirmed" || tx.status == "failed")
+}
+
+pub(crate) async fn requeue(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: &str,
+) -> Result<Option<RequeuedEntry>> {
+    let entry = sqlx::query!(
+        r#"
+        UPDATE outbox o
+        SET status = 'pending', attempts = 0, "nextAttemptAt" = NOW(), "lockedAt" = NULL,
+            "lastError" = NULL,
+            request = CASE WHEN t.status = 'failed' THEN NULL ELSE o.request END,
+            "signedTransaction" = CASE WHEN t.status = 'failed' THEN NULL ELSE o."signedTransaction" END,
+            "txHash" = CASE WHEN t.status = 'failed' THEN NULL ELSE o."txHash" END
+        FROM outbox f
+        LEFT JOIN transactions t ON t.hash = f."txHash"
+        WHERE o.id = $1 AND f.id = o.id AND o.status = 'failed'
+        RETURNING o.kind, o."marketId" as market_id, o."betId" as bet_id,
+                  o."signedTransaction" IS NOT NULL as "resumes_signed!"
+        "#,
+        id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(entry.map(|entry| RequeuedEntry {
+        kind: entry.kind,
+        market_id: entry.market_id,
+        bet_id: entry.bet_id,
+        resumes_signed: entry.resumes_signed,
+    }))
+}
+
+pub(crate) async fn enqueue(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    call: &ContractCall,
+    market_id: Option<&str>,
+    bet_id: Option<&str>,
+    max_attempts: i32,
+) -> Result<Option<String>> {
+    let payload = serde_json::to_string(call)
+        .map_err(|e| AppError::Internal(format!("Failed to encode outbox payload: {}", e)))?;
+
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO outbox (id, kind, "marketId", "betId", payload, "maxAttempts")
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT ("marketId") WHERE kind = 'create_market' AND status <> 'failed' DO NOTHING
+        RETURNING id
+        "#,
+        Uuid::new_v4().to_string(),
+        call.kind(),
+        market_id,
+        bet_id,
+        payload,
+        max_attempts.max(1)
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contract_call_payload_round_trips() {
+        let call = ContractCall::PlaceBet {
+            blockchain_market_id: 7,
+            position: true,
+            amount: 1_000_000,
+        };
+        let payload = serde_json::to_string(&call).unwrap();
+
+        assert_eq!(
+            payload,
+            r#"{"call":"placeBet","blockchainMarketId":7,"position":true,"amount":1000000}"#
+        );
+        assert!(matches!(
+            serde_json::from_str::<ContractCall>(&payload).unwrap(),
+            ContractCall::PlaceBet {
+                blockchain_market_id: 7,
+                ..
+            }
+        ));
+        assert_eq!(call.kind(), "place_bet");
+    }
+
+    #[test]
+    fn test_retry_backs_off_exponentially() {
+        assert_eq!(
+            retry_decision(0, 5, 30),
+            RetryDecision::Retry { delay_secs: 30 }
+        );
+        assert_eq!(
+            retry_decision(1, 5, 30),
+            RetryDecision::Retry { delay_secs: 60 }
+        );
+        assert_eq!(
+            retry_decision(3, 5, 30),
+            RetryDecision::Retry { delay_secs: 240 }
+        );
+        assert_eq!(
+            retry_decision(0, 100, u64::MAX),
+            RetryDecision::Retry {
+                delay_secs: u64::MAX
+            }
+        );
+    }
+
+    #[test]
+    fn test_last_attempt_fails_entry_and_its_domain() {
+        assert_eq!(retry_decision(4, 5, 30), RetryDecision::Fail);
+        assert_eq!(retry_decision(0, 1, 30), RetryDecision::Fail);
+
+        assert_eq!(
+            domain_failure("place_bet", Some("market-1"), Some("bet-1")),
+            Some(DomainFailure::Bet("bet-1"))
+        );
+        assert_eq!(
+            domain_failure("create_market", Some("market-1"), None),
+            Some(DomainFailure::MarketCreation("market-1"))
+        );
+        assert_eq!(domain_failure("rebalance", None, None), None);
+    }
+
+    #[test]
+    fn test_nonce_error_re_signs_unless_mined() {
+        assert_eq!(
+            broadcast_failure(false, "nonce too low"),
+            BroadcastFailure::Resign
+        );
+        assert_eq!(
+            broadcast_failure(false, "replacement transaction underpriced"),
+            BroadcastFailure::Resign
+        );
+        assert_eq!(
+            broadcast_failure(true, "nonce too low"),
+            BroadcastFailure::Mined
+        );
+        assert_eq!(
+            broadcast_failure(false, "connection refused"),
+            BroadcastFailure::Retry
+        );
+    }
+
+    #[test]
+    fn test_latest_live_sibling_settles_entry() {
+        let at = |secs: i64| {
+            chrono::DateTime::from_timestamp(secs, 0)
+                .unwrap()
+                .naive_utc()
+        };
+        let sibling = |hash: &str, status: &str, secs: i64| SiblingTransaction {
+            hash: hash.to_string(),
+            status: status.to_string(),
+            error: None,
+            created_at: at(secs),
+        };
+
+        let speed_up = [
+            sibling("0x1", "replaced", 1),
+            sibling("0x2", "confirmed", 2),
+        ];
+        assert_eq!(resolve_siblings(&speed_up).unwrap().hash, "0x2");
+
+        let stale_replacement = [
+            sibling("0x1", "confirmed", 1),
+            sibling("0x2", "replaced", 2),
+        ];
+        assert_eq!(resolve_siblings(&stale_replacement).unwrap().hash, "0x1");
+
+        let still_pending = [sibling("0x1", "failed", 1), sibling("0x2", "pending", 2)];
+        assert!(resolve_siblings(&still_pending).is_none());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_requeue_keeps_signed_transaction_unless_it_failed(pool: sqlx::PgPool) {
+        sqlx::query(
+            r#"
+            INSERT INTO outbox (id, kind, payload, status, attempts, request, "signedTransaction", "txHash")
+            VALUES ('unknown', 'place_bet', '{}', 'failed', 5, '{}', '0x01', '0xaa'),
+                   ('reverted', 'place_bet', '{}', 'failed', 5, '{}', '0x02', '0xbb'),
+                   ('open', 'place_bet', '{}', 'pending', 0, NULL, NULL, NULL)
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"INSERT INTO transactions (hash, kind, "fromAddress", status)
+               VALUES ('0xbb', 'place_bet', '0x0000000000000000000000000000000000000001', 'failed')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        assert!(
+            requeue(&mut tx, "unknown")
+                .await
+                .unwrap()
+                .unwrap()
+                .resumes_signed
+        );
+        assert!(
+            !requeue(&mut tx, "reverted")
+                .await
+                .unwrap()
+                .unwrap()
+                .resumes_signed
+        );
+        assert!(requeue(&mut tx, "open").await.unwrap().is_none());
+        tx.commit().await.unwrap();
+
+        let unknown = sqlx::query!(
+            r#"SELECT status, attempts, "txHash" as tx_hash FROM outbox WHERE id = 'unknown'"#
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(unknown.status, "pending");
+        assert_eq!(unknown.attempts, 0);
+        assert_eq!(unknown.tx_hash.as_deref(), Some("0xaa"));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_stale_processing_entries_are_reclaimed(pool: sqlx::PgPool) {
+        sqlx::query(
+            r#"
+            INSERT INTO outbox (id, kind, payload, status, "lockedAt", "nextAttemptAt")
+            VALUES ('stale', 'place_bet', '{}', 'processing', NOW() - INTERVAL '10 minutes', NOW()),
+                   ('leased', 'place_bet', '{}', 'processing', NOW(), NOW()),
+                   ('due', 'place_bet', '{}', 'pending', NULL, NOW()),
+                   ('later', 'place_bet', '{}', 'pending', NULL, NOW() + INTERVAL '1 hour')
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut claimed: Vec<String> = claim_batch(&pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect();
+        claimed.sort();
+
+        assert_eq!(claimed, vec!["due", "stale"]);
+        assert!(claim_batch(&pool).await.unwrap().is_empty());
+    }
+}
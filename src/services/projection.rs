@@ -0,0 +1,899 @@
+use bigdecimal::{BigDecimal, RoundingMode, ToPrimitive, Zero};
+use chrono::NaiveDateTime;
+use tracing::info;
+
+use crate::{
+    config::Config,
+    db::Database,
+    error::{AppError, Result},
+    services::BlockchainEventService,
+};
+
+pub const PROJECTED_EVENT_TYPES: [&str; 4] = [
+    "MarketCreated",
+    "BetPlaced",
+    "MarketResolved",
+    "WinningsClaimed",
+];
+
+const COUNTED_BET_STATUSES: [&str; 4] = ["active", "won", "lost", "claimed"];
+const CLAIMABLE_BET_STATUSES: [&str; 2] = ["active", "won"];
+const UNCONFIRMED_BET_STATUSES: [&str; 3] = ["pending", "reorged", "failed"];
+const PROJECTION_BATCH_SIZE: i64 = 100;
+
+#[derive(Debug, Clone)]
+pub enum ProjectedEvent {
+    MarketCreated {
+        market_id: i64,
+        question: String,
+        end_time: i64,
+        vault_address: Option<String>,
+        block_timestamp: i64,
+    },
+    BetPlaced {
+        id: String,
+        market_id: i64,
+        user: String,
+        position: bool,
+        amount: BigDecimal,
+        shares: Option<BigDecimal>,
+        block_timestamp: i64,
+        transaction_hash: String,
+    },
+    MarketResolved {
+        market_id: i64,
+        outcome: bool,
+        block_timestamp: i64,
+    },
+    WinningsClaimed {
+        market_id: i64,
+        user: String,
+        winning_amount: BigDecimal,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct BetSnapshot {
+    pub position: bool,
+    pub amount: BigDecimal,
+    pub shares: Option<BigDecimal>,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketAggregate {
+    pub yes_pool: BigDecimal,
+    pub no_pool: BigDecimal,
+    pub count_yes: i32,
+    pub count_no: i32,
+    pub yes_shares: BigDecimal,
+    pub no_shares: BigDecimal,
+}
+
+impl Default for MarketAggregate {
+    fn default() -> Self {
+        Self {
+            yes_pool: BigDecimal::zero(),
+            no_pool: BigDecimal::zero(),
+            count_yes: 0,
+            count_no: 0,
+            yes_shares: BigDecimal::zero(),
+            no_shares: BigDecimal::zero(),
+        }
+    }
+}
+
+impl MarketAggregate {
+    pub fn from_bets<'a>(bets: impl IntoIterator<Item = &'a BetSnapshot>) -> Self {
+        let mut aggregate = Self::default();
+        for bet in bets
+            .into_iter()
+            .filter(|bet| counts_towards_pools(&bet.status))
+        {
+            aggregate.add(bet.position, &bet.amount, bet.shares.as_ref());
+        }
+        aggregate
+    }
+
+    pub fn add(&mut self, position: bool, amount: &BigDecimal, shares: Option<&BigDecimal>) {
+        let shares = shares.cloned().unwrap_or_default();
+        if position {
+            self.yes_pool += amount;
+            self.count_yes += 1;
+            self.yes_shares += shares;
+        } else {
+            self.no_pool += amount;
+            self.count_no += 1;
+            self.no_shares += shares;
+        }
+    }
+
+    pub fn total_pool(&self) -> BigDecimal {
+        &self.yes_pool + &self.no_pool
+    }
+
+    pub fn probability(&self) -> i32 {
+        let total = self.total_pool();
+        if total <= BigDecimal::zero() {
+            return 50;
+        }
+
+        (&self.yes_pool * BigDecimal::from(100) / total)
+            .with_scale_round(0, RoundingMode::HalfUp)
+            .to_i32()
+            .unwrap_or(50)
+    }
+
+    pub fn odds(&self, position: bool) -> BigDecimal {
+        let side = if position {
+            &self.yes_pool
+        } else {
+            &self.no_pool
+        };
+        if *side <= BigDecimal::zero() {
+            return BigDecimal::from(1);
+        }
+
+        (self.total_pool() / side).with_scale_round(2, RoundingMode::HalfUp)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ProjectionSummary {
+    pub markets: usize,
+    pub bets: usize,
+    pub resolutions: usize,
+    pub claims: usize,
+    pub failed: usize,
+}
+
+impl ProjectionSummary {
+    fn record(&mut self, event: &ProjectedEvent) {
+        match event {
+            ProjectedEvent::MarketCreated { .. } => self.markets += 1,
+            ProjectedEvent::BetPlaced { .. } => self.bets += 1,
+            ProjectedEvent::MarketResolved { .. } => self.resolutions += 1,
+            ProjectedEvent::WinningsClaimed { .. } => self.claims += 1,
+        }
+    }
+}
+
+pub struct ProjectionService {
+    db: Database,
+}
+
+impl ProjectionService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn process_pending(&self, config: &Config) -> Result<ProjectionSummary> {
+        let pending = sqlx::query!(
+            r#"
+            SELECT id, "eventType" as event_type, "blockchainId" as blockchain_id
+            FROM blockchain_events
+            WHERE status = 'pending' AND "nextRetryAt" <= NOW() AND "eventType" = ANY($1)
+            ORDER BY "blockNumber" ASC, id ASC
+            LIMIT $2
+            "#,
+            &PROJECTED_EVENT_TYPES.map(String::from),
+            PROJECTION_BATCH_SIZE
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let events = BlockchainEventService::new(self.db.clone());
+        let mut summary = ProjectionSummary::default();
+
+        for event in pending {
+            let mut tx = self.db.pool().begin().await?;
+            let result = match load_event(&mut tx, &event.event_type, &event.blockchain_id).await {
+                Ok(Some(projected)) => apply(&mut tx, &projected).await.map(|changed| {
+                    if changed {
+                        summary.record(&projected);
+                    }
+                }),
+                Ok(None) => Err(AppError::NotFound(format!(
+                    "Raw {} event {} not found",
+                    event.event_type, event.blockchain_id
+                ))),
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(()) => {
+                    tx.commit().await?;
+                    events.mark_processed(&event.id).await?;
+                }
+                Err(e) => {
+                    tx.rollback().await?;
+                    summary.failed += 1;
+                    events
+                        .record_failure(config, &event.id, &e.to_string())
+                        .await?;
+                }
+            }
+        }
+
+        if summary.markets + summary.bets + summary.resolutions + summary.claims > 0 {
+            info!(
+                "📐 Projected {} markets, {} bets, {} resolutions, {} claims ({} failed)",
+                summary.markets, summary.bets, summary.resolutions, summary.claims, summary.failed
+            );
+        }
+
+        Ok(summary)
+    }
+}
+
+pub fn counts_towards_pools(status: &str) -> bool {
+    COUNTED_BET_STATUSES.contains(&status)
+}
+
+pub fn resolved_bet_status(status: &str, position: bool, outcome: bool) -> Option<&'static str> {
+    if !matches!(status, "active" | "won" | "lost") {
+        return None;
+    }
+
+    let resolved = if position == outcome { "won" } else { "lost" };
+    (status != resolved).then_some(resolved)
+}
+
+pub fn is_claimable(status: &str) -> bool {
+    CLAIMABLE_BET_STATUSES.contains(&status)
+}
+
+pub async fn load_event(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    event_type: &str,
+    id: &str,
+) -> Result<Option<ProjectedEvent>> {
+    let event = match event_type {
+        "MarketCreated" => sqlx::query!(
+            r#"
+            SELECT market_id::bigint as "market_id!", question, end_time::bigint as "end_time!",
+                   vault_address, block_timestamp::bigint as "block_timestamp!"
+            FROM market_createds
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .map(|row| ProjectedEvent::MarketCreated {
+            market_id: row.market_id,
+            question: row.question,
+            end_time: row.end_time,
+            vault_address: row.vault_address,
+            block_timestamp: row.block_timestamp,
+        }),
+        "BetPlaced" => sqlx::query!(
+            r#"
+            SELECT id, market_id::bigint as "market_id!", "user", position, amount, shares,
+                   block_timestamp::bigint as "block_timestamp!", transaction_hash
+            FROM bet_placeds
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .map(|row| ProjectedEvent::BetPlaced {
+            id: row.id,
+            market_id: row.market_id,
+            user: row.user,
+            position: row.position,
+            amount: row.amount,
+            shares: row.shares,
+            block_timestamp: row.block_timestamp,
+            transaction_hash: row.transaction_hash,
+        }),
+        "MarketResolved" => sqlx::query!(
+            r#"
+            SELECT market_id::bigint as "market_id!", outcome,
+                   block_timestamp::bigint as "block_timestamp!"
+            FROM market_resolveds
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .map(|row| ProjectedEvent::MarketResolved {
+            market_id: row.market_id,
+            outcome: row.outcome,
+            block_timestamp: row.block_timestamp,
+        }),
+        "WinningsClaimed" => sqlx::query!(
+            r#"
+            SELECT market_id::bigint as "market_id!", "user", winning_amount
+            FROM winnings_claimeds
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .map(|row| ProjectedEvent::WinningsClaimed {
+            market_id: row.market_id,
+            user: row.user,
+            winning_amount: row.winning_amount,
+        }),
+        _ => None,
+    };
+
+    Ok(event)
+}
+
+pub async fn apply(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    event: &ProjectedEvent,
+) -> Result<bool> {
+    match event {
+        ProjectedEvent::MarketCreated {
+            market_id,
+            question,
+            end_time,
+            vault_address,
+            block_timestamp,
+        } => {
+            sqlx::query!(
+                r#"
+                INSERT INTO markets_extended (
+                    id, "blockchainMarketId", question, "endDate", "vaultAddress", status,
+                    "createdAt", "updatedAt"
+                )
+                VALUES (
+                    gen_random_uuid()::text, $1, $2, to_timestamp($3)::timestamp, $4, 'active',
+                    to_timestamp($5)::timestamp, NOW()
+                )
+                ON CONFLICT ("blockchainMarketId") DO UPDATE
+                SET "vaultAddress" = EXCLUDED."vaultAddress", "endDate" = EXCLUDED."endDate",
+                    "updatedAt" = NOW()
+                "#,
+                market_id,
+                question,
+                *end_time as f64,
+                vault_address.as_deref(),
+                *block_timestamp as f64
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            Ok(true)
+        }
+        ProjectedEvent::BetPlaced {
+            id,
+            market_id,
+            user,
+            position,
+            amount,
+            shares,
+            block_timestamp,
+            transaction_hash,
+        } => {
+            let market = sqlx::query_scalar!(
+                r#"SELECT id FROM markets_extended WHERE "blockchainMarketId" = $1 FOR UPDATE"#,
+                market_id
+            )
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Market {} not found in database", market_id))
+            })?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO users (id, address, "createdAt", "updatedAt")
+                VALUES (gen_random_uuid()::text, $1, NOW(), NOW())
+                ON CONFLICT (address) DO NOTHING
+                "#,
+                user
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            let server_bet = sqlx::query_scalar!(
+                r#"SELECT "betId" as "bet_id!" FROM transactions WHERE hash = $1 AND "betId" IS NOT NULL"#,
+                transaction_hash
+            )
+            .fetch_optional(&mut **tx)
+            .await?;
+
+            if let Some(bet_id) = server_bet {
+                let confirmed = sqlx::query!(
+                    r#"
+                    UPDATE bets_extended
+                    SET shares = $2,
+                        status = CASE WHEN status = ANY($3) THEN 'active' ELSE status END,
+                        "updatedAt" = NOW()
+                    WHERE id = $1 AND (shares IS DISTINCT FROM $2 OR status = ANY($3))
+                    "#,
+                    bet_id,
+                    shares.as_ref(),
+                    &UNCONFIRMED_BET_STATUSES.map(String::from)
+                )
+                .execute(&mut **tx)
+                .await?
+                .rows_affected()
+                    > 0;
+
+                if confirmed {
+                    refresh_market(tx, &market).await?;
+                }
+
+                return Ok(confirmed);
+            }
+
+            let mut aggregate = load_aggregate(tx, &market).await?;
+            aggregate.add(*position, amount, shares.as_ref());
+
+            let inserted = sqlx::query!(
+                r#"
+                INSERT INTO bets_extended (
+                    id, "userId", "marketId", position, amount, shares, odds, status,
+                    "createdAt", "updatedAt"
+                )
+                SELECT $1, u.id, $3, $4, $5, $6, $7, 'active', to_timestamp($8)::timestamp, NOW()
+                FROM users u
+                WHERE u.address = $2
+                ON CONFLICT (id) DO NOTHING
+                "#,
+                id,
+                user,
+                market,
+                position,
+                amount,
+                shares.as_ref(),
+                aggregate.odds(*position),
+                *block_timestamp as f64
+            )
+            .execute(&mut **tx)
+            .await?
+            .rows_affected()
+                > 0;
+
+            if inserted {
+                store_aggregate(tx, &market, &aggregate).await?;
+            }
+
+            Ok(inserted)
+        }
+        ProjectedEvent::MarketResolved {
+            market_id,
+            outcome,
+            block_timestamp,
+        } => {
+            let resolved_at =
+                chrono::DateTime::from_timestamp(*block_timestamp, 0).map(|at| at.naive_utc());
+            let resolved = resolve_market(
+                tx,
+                *market_id,
+                *outcome,
+                resolved_at,
+                "MarketResolved event",
+            )
+            .await?;
+
+            Ok(resolved.is_some())
+        }
+        ProjectedEvent::WinningsClaimed {
+            market_id,
+            user,
+            winning_amount,
+        } => {
+            let claimed = sqlx::query!(
+                r#"
+                UPDATE bets_extended be
+                SET status = 'claimed', payout = $3, "updatedAt" = NOW()
+                FROM users u, markets_extended me
+                WHERE be."userId" = u.id AND u.address = $2
+                  AND be."marketId" = me.id AND me."blockchainMarketId" = $1
+                  AND be.status = ANY($4)
+                "#,
+                market_id,
+                user,
+                winning_amount,
+                &CLAIMABLE_BET_STATUSES.map(String::from)
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            Ok(claimed.rows_affected() > 0)
+        }
+    }
+}
+
+pub async fn resolve_market(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    blockchain_market_id: i64,
+    outcome: bool,
+    resolved_at: Option<NaiveDateTime>,
+    reason: &str,
+) -> Result<Option<String>> {
+    let Some(market) = sqlx::query!(
+        r#"
+        SELECT id, status, result
+        FROM markets_extended
+        WHERE "blockchainMarketId" = $1
+        FOR UPDATE
+        "#,
+        blockchain_market_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    else {
+        return Err(AppError::NotFound(format!(
+            "Market {} not found in database",
+            blockchain_market_id
+        )));
+    };
+
+    if market.status == "resolved" {
+        if market.result == Some(outcome) {
+            return Ok(None);
+        }
+        return Err(AppError::BadRequest(format!(
+            "Market {} is already resolved {}; refusing to re-resolve it {}",
+            market.id,
+            match market.result {
+                Some(true) => "YES",
+                Some(false) => "NO",
+                None => "without an outcome",
+            },
+            if outcome { "YES" } else { "NO" }
+        )));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE markets_extended
+        SET status = 'resolved', result = $2,
+            "resolutionDate" = COALESCE("resolutionDate", $3, NOW()::timestamp),
+            "updatedAt" = NOW()
+        WHERE id = $1
+        "#,
+        market.id,
+        outcome,
+        resolved_at
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let bets = sqlx::query!(
+        r#"
+        SELECT id, position as "position!", status
+        FROM bets_extended
+        WHERE "marketId" = $1 AND position IS NOT NULL
+        "#,
+        market.id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let (ids, statuses): (Vec<String>, Vec<String>) = bets
+        .into_iter()
+        .filter_map(|bet| {
+            resolved_bet_status(&bet.status, bet.position, outcome)
+                .map(|status| (bet.id, status.to_string()))
+        })
+        .unzip();
+
+    sqlx::query!(
+        r#"
+        UPDATE bets_extended b
+        SET status = s.status, "updatedAt" = NOW()
+        FROM UNNEST($1::text[], $2::text[]) AS s(id, status)
+        WHERE b.id = s.id
+        "#,
+        &ids,
+        &statuses
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    if market.status != "resolved" {
+        sqlx::query!(
+            r#"
+            INSERT INTO market_status_history ("marketId", "fromStatus", "toStatus", actor, reason)
+            VALUES ($1, $2, 'resolved', 'sync', $3)
+            "#,
+            market.id,
+            market.status,
+            reason
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    info!(
+        "🏁 Market {} (blockchain ID {}) resolved {}",
+        market.id,
+        blockchain_market_id,
+        if outcome { "YES" } else { "NO" }
+    );
+
+    Ok(Some(market.id))
+}
+
+pub async fn refresh_market(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    market_id: &str,
+) -> Result<MarketAggregate> {
+    let aggregate = load_aggregate(tx, market_id).await?;
+    store_aggregate(tx, market_id, &aggregate).await?;
+    Ok(aggregate)
+}
+
+async fn load_aggregate(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    market_id: &str,
+) -> Result<MarketAggregate> {
+    let bets = sqlx::query!(
+        r#"
+        SELECT position as "position!", amount as "amount!", shares, status
+        FROM bets_extended
+        WHERE "marketId" = $1 AND position IS NOT NULL AND amount IS NOT NULL
+        "#,
+        market_id
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(|row| BetSnapshot {
+        position: row.position,
+        amount: row.amount,
+        shares: row.shares,
+        status: row.status,
+    })
+    .collect::<Vec<_>>();
+
+    Ok(MarketAggregate::from_bets(&bets))
+}
+
+async fn store_aggregate(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    market_id: &str,
+    aggregate: &MarketAggregate,
+) -> Result<()> {
+    let total_pool = aggregate.total_pool();
+
+    sqlx::query!(
+        r#"
+        UPDATE markets_extended
+        SET "yesPoolSize" = $2, "noPoolSize" = $3, "totalPoolSize" = $4, volume = $4,
+            "countYes" = $5, "countNo" = $6, "totalYesShares" = $7, "totalNoShares" = $8,
+            probability = $9, "updatedAt" = NOW()
+        WHERE id = $1
+        "#,
+        market_id,
+        aggregate.yes_pool,
+        aggregate.no_pool,
+        total_pool,
+        aggregate.count_yes,
+        aggregate.count_no,
+        aggregate.yes_shares,
+        aggregate.no_shares,
+        aggregate.probability()
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bet(position: bool, amount: i64, shares: Option<i64>, status: &str) -> BetSnapshot {
+        BetSnapshot {
+            position,
+            amount: BigDecimal::from(amount),
+            shares: shares.map(BigDecimal::from),
+            status: status.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_only_placed_bets_count_towards_pools() {
+        for status in ["active", "won", "lost", "claimed"] {
+            assert!(counts_towards_pools(status), "{}", status);
+        }
+        for status in ["pending", "failed", "reorged", "cancelled"] {
+            assert!(!counts_towards_pools(status), "{}", status);
+        }
+    }
+
+    #[test]
+    fn test_aggregate_sums_pools_counts_and_shares_per_side() {
+        let aggregate = MarketAggregate::from_bets(&[
+            bet(true, 100, Some(90), "active"),
+            bet(true, 50, None, "won"),
+            bet(false, 30, Some(30), "lost"),
+            bet(false, 20, Some(20), "pending"),
+            bet(true, 70, Some(70), "failed"),
+        ]);
+
+        assert_eq!(aggregate.yes_pool, BigDecimal::from(150));
+        assert_eq!(aggregate.no_pool, BigDecimal::from(30));
+        assert_eq!((aggregate.count_yes, aggregate.count_no), (2, 1));
+        assert_eq!(aggregate.yes_shares, BigDecimal::from(90));
+        assert_eq!(aggregate.no_shares, BigDecimal::from(30));
+        assert_eq!(aggregate.total_pool(), BigDecimal::from(180));
+    }
+
+    #[test]
+    fn test_aggregate_is_independent_of_bet_order() {
+        let bets = vec![
+            bet(true, 10, Some(10), "active"),
+            bet(false, 25, Some(20), "claimed"),
+            bet(true, 5, None, "active"),
+        ];
+        let reversed = bets.iter().rev().cloned().collect::<Vec<_>>();
+
+        assert_eq!(
+            MarketAggregate::from_bets(&bets),
+            MarketAggregate::from_bets(&reversed)
+        );
+    }
+
+    #[test]
+    fn test_probability_is_yes_share_of_pool() {
+        assert_eq!(MarketAggregate::default().probability(), 50);
+        assert_eq!(
+            MarketAggregate::from_bets(&[
+                bet(true, 2, None, "active"),
+                bet(false, 1, None, "active")
+            ])
+            .probability(),
+            67
+        );
+        assert_eq!(
+            MarketAggregate::from_bets(&[bet(false, 10, None, "active")]).probability(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_odds_are_pool_over_side() {
+        let aggregate = MarketAggregate::from_bets(&[
+            bet(true, 100, None, "active"),
+            bet(false, 50, None, "active"),
+        ]);
+
+        assert_eq!(aggregate.odds(true), "1.50".parse::<BigDecimal>().unwrap());
+        assert_eq!(aggregate.odds(false), BigDecimal::from(3));
+        assert_eq!(
+            MarketAggregate::from_bets(&[bet(true, 10, None, "active")]).odds(false),
+            BigDecimal::from(1)
+        );
+    }
+
+    #[test]
+    fn test_resolution_settles_open_bets_only() {
+        assert_eq!(resolved_bet_status("active", true, true), Some("won"));
+        assert_eq!(resolved_bet_status("active", false, true), Some("lost"));
+        assert_eq!(resolved_bet_status("won", true, false), Some("lost"));
+        assert_eq!(resolved_bet_status("won", true, true), None);
+        assert_eq!(resolved_bet_status("claimed", false, true), None);
+        assert_eq!(resolved_bet_status("pending", true, true), None);
+    }
+
+    #[test]
+    fn test_claims_apply_to_unsettled_and_winning_bets() {
+        assert!(is_claimable("active"));
+        assert!(is_claimable("won"));
+        assert!(!is_claimable("lost"));
+        assert!(!is_claimable("claimed"));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_conflicting_resolution_is_rejected(pool: sqlx::PgPool) {
+        sqlx::query("INSERT INTO users (id, address) VALUES ('user-1', $1)")
+            .bind("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for query in [
+            r#"INSERT INTO markets_extended (id, "blockchainMarketId", question, "endDate", status, result)
+               VALUES ('market-1', 7, 'Will it rain?', NOW() - INTERVAL '1 day', 'resolved', true)"#,
+            r#"INSERT INTO bets_extended (id, "userId", "marketId", position, amount, odds, status, payout)
+               VALUES ('yes-bet', 'user-1', 'market-1', true, 25, 1, 'won', 40),
+                      ('no-bet', 'user-1', 'market-1', false, 15, 1, 'lost', 0)"#,
+        ] {
+            sqlx::query(query).execute(&pool).await.unwrap();
+        }
+
+        let mut tx = pool.begin().await.unwrap();
+        assert_eq!(
+            resolve_market(&mut tx, 7, true, None, "test")
+                .await
+                .unwrap(),
+            None
+        );
+        assert!(resolve_market(&mut tx, 7, false, None, "test")
+            .await
+            .is_err());
+        tx.rollback().await.unwrap();
+
+        let market = sqlx::query!(r#"SELECT result FROM markets_extended WHERE id = 'market-1'"#)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(market.result, Some(true));
+        let bets = sqlx::query!(r#"SELECT id, status, payout FROM bets_extended ORDER BY id"#)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(bets[0].status, "lost");
+        assert_eq!(bets[1].status, "won");
+        assert_eq!(bets[1].payout, Some(BigDecimal::from(40)));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_bet_placed_confirms_server_bet_instead_of_adding_one(pool: sqlx::PgPool) {
+        let user = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf";
+        let hash = format!("{:?}", ethers::types::H256::repeat_byte(0xab));
+
+        sqlx::query("INSERT INTO users (id, address) VALUES ('user-1', $1)")
+            .bind(user)
+            .execute(&pool)
+            .await
+            .unwrap();
+        for query in [
+            r#"INSERT INTO markets_extended (id, "blockchainMarketId", question, "endDate")
+               VALUES ('market-1', 7, 'Will it rain?', NOW() + INTERVAL '1 day')"#,
+            r#"INSERT INTO bets_extended (id, "userId", "marketId", position, amount, odds, status)
+               VALUES ('server-bet', 'user-1', 'market-1', true, 25, 1, 'active')"#,
+        ] {
+            sqlx::query(query).execute(&pool).await.unwrap();
+        }
+        sqlx::query(
+            r#"INSERT INTO transactions (hash, kind, "fromAddress", "marketId", "betId", status)
+               VALUES ($1, 'place_bet', $2, 'market-1', 'server-bet', 'confirmed')"#,
+        )
+        .bind(&hash)
+        .bind(user)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let event = ProjectedEvent::BetPlaced {
+            id: format!("{}-0", hash),
+            market_id: 7,
+            user: user.to_string(),
+            position: true,
+            amount: BigDecimal::from(25),
+            shares: Some(BigDecimal::from(24)),
+            block_timestamp: 1_700_000_000,
+            transaction_hash: hash.clone(),
+        };
+
+        let mut tx = pool.begin().await.unwrap();
+        refresh_market(&mut tx, "market-1").await.unwrap();
+        assert!(apply(&mut tx, &event).await.unwrap());
+        assert!(!apply(&mut tx, &event).await.unwrap());
+        tx.commit().await.unwrap();
+
+        let bets = sqlx::query!(r#"SELECT id, status, shares FROM bets_extended"#)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(bets.len(), 1);
+        assert_eq!(bets[0].id, "server-bet");
+        assert_eq!(bets[0].status, "active");
+        assert_eq!(bets[0].shares, Some(BigDecimal::from(24)));
+
+        let market = sqlx::query!(
+            r#"SELECT "yesPoolSize" as yes_pool, "countYes" as count_yes, "totalYesShares" as yes_shares
+               FROM markets_extended WHERE id = 'market-1'"#
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(market.yes_pool, BigDecimal::from(25));
+        assert_eq!(market.count_yes, 1);
+        assert_eq!(market.yes_shares, BigDecimal::from(24));
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
+    config::Config,
     db::Database,
     error::{AppError, Result},
     models::*,
+    services::{rpc, MulticallReader},
 };
 use bigdecimal::BigDecimal;
 use ethers::prelude::*;
@@ -195,33 +197,11 @@ impl ProtocolService {
         }))
     }
 
-    async fn fetch_apy_from_blockchain(
-        provider: &Provider<Http>,
-        adapter_address: &str,
-    ) -> Result<String> {
-        let address: Address = adapter_address
-            .parse()
-            .map_err(|e| AppError::Internal(format!("Invalid adapter address: {}", e)))?;
-
-        let contract = IYieldProtocol::new(address, Arc::new(provider.clone()));
-
-        match contract.get_current_apy().call().await {
-            Ok(apy_basis_points) => {
-                let apy_percent = apy_basis_points.as_u64() as f64 / 100.0;
-                Ok(format!("{:.2}", apy_percent))
-            }
-            Err(e) => Err(AppError::Internal(format!(
-                "Failed to call getCurrentApy: {}",
-                e
-            ))),
-        }
-    }
-
-    pub async fn update_all_apys_from_blockchain(&self, rpc_url: &str) -> Result<usize> {
+    pub async fn update_all_apys_from_blockchain(&self, config: &Config) -> Result<usize> {
         info!("🔄 Updating protocol APYs from blockchain...");
 
-        let provider = Provider::<Http>::try_from(rpc_url)
-            .map_err(|e| AppError::Internal(format!("Failed to connect to RPC: {}", e)))?;
+        let provider = rpc::provider(config)?;
+        let client = Arc::new(provider);
 
         let protocols = sqlx::query!(
             r#"
@@ -233,7 +213,8 @@ impl ProtocolService {
         .fetch_all(self.db.pool())
         .await?;
 
-        let mut updated_count = 0;
+        let mut readable = Vec::new();
+        let mut calls = Vec::new();
 
         for protocol in protocols {
             let adapter_address = protocol.address.as_deref().unwrap_or("");
@@ -246,42 +227,58 @@ impl ProtocolService {
                 continue;
             }
 
-            match Self::fetch_apy_from_blockchain(&provider, adapter_address).await {
-                Ok(apy_str) => {
-                    let apy_decimal =
-                        BigDecimal::from_str(&apy_str).unwrap_or_else(|_| BigDecimal::from(0));
-
-                    match sqlx::query!(
-                        r#"
-                        UPDATE protocols
-                        SET "baseApy" = $1, "updatedAt" = CURRENT_TIMESTAMP
-                        WHERE id = $2
-                        "#,
-                        apy_decimal,
-                        protocol.id
-                    )
-                    .execute(self.db.pool())
-                    .await
-                    {
-                        Ok(_) => {
-                            info!("✅ Updated {} APY: {}%", protocol.name, apy_str);
-                            updated_count += 1;
-                        }
-                        Err(e) => {
-                            error!("❌ Failed to update APY in DB for {}: {}", protocol.name, e);
-                        }
-                    }
+            match adapter_address.parse::<Address>() {
+                Ok(address) => {
+                    calls.push(IYieldProtocol::new(address, client.clone()).get_current_apy());
+                    readable.push(protocol);
                 }
                 Err(e) => {
-                    let base_apy = protocol.baseApy;
                     warn!(
-                        "⚠️  Failed to fetch APY for {} from blockchain: {}. Keeping current: {:?}%",
-                        protocol.name, e, base_apy
+                        "⚠️  Protocol {} has an invalid adapter address: {}",
+                        protocol.name, e
                     );
                 }
             }
         }
 
+        let apys = MulticallReader::new(client, config)?.read(calls).await;
+        let mut updated_count = 0;
+
+        for (protocol, apy) in readable.into_iter().zip(apys) {
+            let Some(apy_basis_points) = apy else {
+                warn!(
+                    "⚠️  Failed to fetch APY for {} from blockchain. Keeping current: {:?}%",
+                    protocol.name, protocol.baseApy
+                );
+                continue;
+            };
+
+            let apy_str = format!("{:.2}", apy_basis_points.as_u64() as f64 / 100.0);
+            let apy_decimal =
+                BigDecimal::from_str(&apy_str).unwrap_or_else(|_| BigDecimal::from(0));
+
+            match sqlx::query!(
+                r#"
+                UPDATE protocols
+                SET "baseApy" = $1, "updatedAt" = CURRENT_TIMESTAMP
+                WHERE id = $2
+                "#,
+                apy_decimal,
+                protocol.id
+            )
+            .execute(self.db.pool())
+            .await
+            {
+                Ok(_) => {
+                    info!("✅ Updated {} APY: {}%", protocol.name, apy_str);
+                    updated_count += 1;
+                }
+                Err(e) => {
+                    error!("❌ Failed to update APY in DB for {}: {}", protocol.name, e);
+                }
+            }
+        }
+
         info!("✅ Updated {} protocol APYs from blockchain", updated_count);
         Ok(updated_count)
     }
@@ -0,0 +1,639 @@
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use ethers::{prelude::*, types::transaction::eip2718::TypedTransaction};
+use serde::Serialize;
+use std::str::FromStr;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+use crate::{
+    config::Config,
+    constants::USDC_UNIT,
+    db::Database,
+    error::{AppError, Result},
+    models::{Protocol, RebalanceDecision},
+    services::{
+        indexer::IRebalancerDelegation,
+        outbox::{self, ContractCall},
+        rpc, ProtocolService, SignerService,
+    },
+};
+
+const DECISION_COLUMNS: &str = r#"id, "user", "riskProfile", "currentProtocol", "currentApy", "targetProtocol", "targetApy", balance, "expectedGain", "gasCost", "netGain", action, reason, "outboxId", "createdAt""#;
+
+pub const DECISION_ACTIONS: [&str; 3] = ["submitted", "dry_run", "skipped"];
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceRunSummary {
+    pub dry_run: bool,
+    pub evaluated: usize,
+    pub submitted: usize,
+    pub dry_run_rebalances: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+struct Candidate {
+    user: String,
+    risk_profile: i32,
+    balance: BigDecimal,
+}
+
+struct LastDecision {
+    action: String,
+    reason: String,
+    target_protocol: Option<String>,
+}
+
+struct Decision<'a> {
+    candidate: &'a Candidate,
+    current_protocol: Option<String>,
+    current_apy: Option<BigDecimal>,
+    target: Option<&'a Protocol>,
+    expected_gain: BigDecimal,
+    gas_cost: &'a BigDecimal,
+    net_gain: BigDecimal,
+    action: &'static str,
+    reason: String,
+}
+
+pub struct RebalancerService {
+    db: Database,
+}
+
+impl RebalancerService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn list_decisions(
+        &self,
+        user: Option<&str>,
+        action: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<RebalanceDecision>> {
+        let decisions = sqlx::query_as::<_, RebalanceDecision>(&format!(
+            r#"
+            SELECT {}
+            FROM rebalance_decisions
+            WHERE ($1::text IS NULL OR lower("user") = lower($1))
+              AND ($2::text IS NULL OR action = $2)
+            ORDER BY "createdAt" DESC
+            LIMIT $3
+            "#,
+            DECISION_COLUMNS
+        ))
+        .bind(user)
+        .bind(action)
+        .bind(limit.clamp(1, 500))
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(decisions)
+    }
+
+    pub async fn run(&self, config: &Config, dry_run: bool) -> Result<RebalanceRunSummary> {
+        if config.rebalancer_delegation_addr.is_none() {
+            return Err(AppError::BadRequest(
+                "REBALANCER_DELEGATION_ADDR is not configured".to_string(),
+            ));
+        }
+
+        if !dry_run {
+            let operator = ethers::utils::to_checksum(&SignerService::shared()?.address(), None);
+            if !self.is_active_operator(&operator).await? {
+                return Err(AppError::BadRequest(format!(
+                    "Signer {} is not a registered rebalancer operator",
+                    operator
+                )));
+            }
+        }
+
+        let gas_cost = self.estimate_gas_cost(config).await?;
+        let min_net_gain = decimal(config.rebalancer_min_net_gain);
+        let protocol_service = ProtocolService::new(self.db.clone());
+
+        let candidates = sqlx::query!(
+            r#"
+            SELECT a."user" as "user!", a.risk_profile as "risk_profile!",
+                   (SELECT COALESCE(SUM(amount), 0) FROM depositeds d WHERE d."user" = a."user")
+                       - (SELECT COALESCE(SUM(amount), 0) FROM withdrawns w WHERE w."user" = a."user")
+                       as "balance!"
+            FROM active_auto_rebalance_users a
+            WHERE a.is_enabled
+            ORDER BY a."user"
+            "#
+        )
+        .fetch_all(self.db.pool())
+        .await?
+        .into_iter()
+        .map(|row| Candidate {
+            user: row.user,
+            risk_profile: row.risk_profile,
+            balance: row.balance / BigDecimal::from(USDC_UNIT),
+        })
+        .collect::<Vec<_>>();
+
+        let mut summary = RebalanceRunSummary {
+            dry_run,
+            ..Default::default()
+        };
+
+        for candidate in &candidates {
+            summary.evaluated += 1;
+
+            let result = match protocol_service
+                .get_best_protocol_by_risk(candidate.risk_profile)
+                .await
+            {
+                Ok(target) => {
+                    self.decide(
+                        config,
+                        candidate,
+                        target.as_ref(),
+                        &gas_cost,
+                        &min_net_gain,
+                        dry_run,
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok("submitted") => summary.submitted += 1,
+                Ok("dry_run") => summary.dry_run_rebalances += 1,
+                Ok(_) => summary.skipped += 1,
+                Err(e) => {
+                    error!(
+                        "❌ [Rebalancer] Failed to evaluate {}: {}",
+                        candidate.user, e
+                    );
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        if summary.submitted + summary.dry_run_rebalances + summary.failed > 0 {
+            info!(
+                "⚖️ [Rebalancer] {} users evaluated: {} submitted, {} dry-run, {} skipped, {} failed",
+                summary.evaluated,
+                summary.submitted,
+                summary.dry_run_rebalances,
+                summary.skipped,
+                summary.failed
+            );
+        }
+
+        Ok(summary)
+    }
+
+    pub fn rebalance_call(
+        signer: &SignerService,
+        contract_address: &str,
+        user: &str,
+        protocol: &str,
+    ) -> Result<TypedTransaction> {
+        let address: Address = contract_address
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
+        let user: Address = user
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid user address: {}", e)))?;
+        let protocol: Address = protocol
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid protocol address: {}", e)))?;
+
+        Ok(IRebalancerDelegation::new(address, signer.client())
+            .rebalance(user, protocol)
+            .tx)
+    }
+
+    async fn decide(
+        &self,
+        config: &Config,
+        candidate: &Candidate,
+        target: Option<&Protocol>,
+        gas_cost: &BigDecimal,
+        min_net_gain: &BigDecimal,
+        dry_run: bool,
+    ) -> Result<&'static str> {
+        let (current_protocol, current_apy) = self.current_protocol(&candidate.user).await?;
+        let target_protocol = target.and_then(|protocol| protocol.address.as_deref());
+
+        let expected_gain = match (target, &current_apy) {
+            (Some(target), current_apy) => expected_gain(
+                &candidate.balance,
+                current_apy.as_ref().unwrap_or(&BigDecimal::zero()),
+                &target.base_apy,
+                config.rebalancer_gain_horizon_days,
+            ),
+            (None, _) => BigDecimal::zero(),
+        };
+        let net_gain = &expected_gain - gas_cost;
+
+        let skip = if target_protocol.is_none() {
+            Some(format!(
+                "No active protocol with an adapter for risk profile {}",
+                candidate.risk_profile
+            ))
+        } else if self.has_rebalance_in_flight(&candidate.user).await? {
+            Some("Previous rebalance is still in flight".to_string())
+        } else {
+            skip_reason(
+                &candidate.balance,
+                current_protocol.as_deref(),
+                target_protocol.unwrap_or_default(),
+                &net_gain,
+                min_net_gain,
+            )
+        };
+
+        let (action, reason) = match skip {
+            Some(reason) => ("skipped", reason),
+            None => {
+                let reason = format!(
+                    "Net gain {} over {} days (APY {}% -> {}%)",
+                    round(&net_gain),
+                    config.rebalancer_gain_horizon_days,
+                    current_apy
+                        .as_ref()
+                        .map(|apy| apy.normalized().to_string())
+                        .unwrap_or_else(|| "0".to_string()),
+                    target
+                        .map(|target| target.base_apy.normalized().to_string())
+                        .unwrap_or_default()
+                );
+                if dry_run {
+                    ("dry_run", reason)
+                } else {
+                    ("submitted", reason)
+                }
+            }
+        };
+
+        if action == "skipped" {
+            let last = sqlx::query_as!(
+                LastDecision,
+                r#"
+                SELECT action, reason, "targetProtocol" as target_protocol
+                FROM rebalance_decisions
+                WHERE "user" = $1
+                ORDER BY "createdAt" DESC
+                LIMIT 1
+                "#,
+                candidate.user
+            )
+            .fetch_optional(self.db.pool())
+            .await?;
+
+            if repeats_last_skip(last.as_ref(), &reason, target_protocol) {
+                debug!(
+                    "⏭️ [Rebalancer] Still skipping {}: {}",
+                    candidate.user, reason
+                );
+                return Ok(action);
+            }
+        }
+
+        let decision = Decision {
+            candidate,
+            current_protocol,
+            current_apy,
+            target,
+            expected_gain,
+            gas_cost,
+            net_gain,
+            action,
+            reason,
+        };
+
+        let mut tx = self.db.pool().begin().await?;
+
+        let outbox_id = match (action, target_protocol) {
+            ("submitted", Some(protocol)) => {
+                outbox::enqueue(
+                    &mut tx,
+                    &ContractCall::Rebalance {
+                        user: candidate.user.clone(),
+                        protocol: protocol.to_string(),
+                    },
+                    None,
+                    None,
+                    config.outbox_max_attempts,
+                )
+                .await?
+            }
+            _ => None,
+        };
+
+        record_decision(&mut tx, &decision, outbox_id.as_deref()).await?;
+        tx.commit().await?;
+
+        match action {
+            "submitted" => info!(
+                "⚖️ [Rebalancer] Queued rebalance of {} to {}: {}",
+                candidate.user,
+                target_protocol.unwrap_or_default(),
+                decision.reason
+            ),
+            "dry_run" => info!(
+                "🧪 [Rebalancer] Would rebalance {} to {}: {}",
+                candidate.user,
+                target_protocol.unwrap_or_default(),
+                decision.reason
+            ),
+            _ => {}
+        }
+
+        Ok(action)
+    }
+
+    async fn current_protocol(&self, user: &str) -> Result<(Option<String>, Option<BigDecimal>)> {
+        let current = sqlx::query!(
+            r#"
+            SELECT c.protocol as "protocol!", p."baseApy" as "base_apy?"
+            FROM (
+                SELECT d."targetProtocol" as protocol, d."createdAt" as at
+                FROM rebalance_decisions d
+                JOIN outbox o ON o.id = d."outboxId"
+                WHERE d."user" = $1 AND d.action = 'submitted' AND o.status = 'completed'
+                UNION ALL
+                SELECT e.protocol, to_timestamp(e.block_timestamp)::timestamp as at
+                FROM auto_deposit_executeds e
+                WHERE e."user" = $1 AND e.success
+            ) c
+            LEFT JOIN protocols p ON lower(p.address) = lower(c.protocol)
+            WHERE c.protocol IS NOT NULL
+            ORDER BY c.at DESC
+            LIMIT 1
+            "#,
+            user
+        )
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        Ok(match current {
+            Some(current) => (Some(current.protocol), current.base_apy),
+            None => (None, None),
+        })
+    }
+
+    async fn has_rebalance_in_flight(&self, user: &str) -> Result<bool> {
+        let in_flight = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM rebalance_decisions d
+                JOIN outbox o ON o.id = d."outboxId"
+                WHERE d."user" = $1 AND o.status IN ('pending', 'processing', 'submitted')
+            ) as "in_flight!"
+            "#,
+            user
+        )
+        .fetch_one(self.db.pool())
+        .await?;
+
+        Ok(in_flight)
+    }
+
+    async fn is_active_operator(&self, operator: &str) -> Result<bool> {
+        let active = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM operator_addeds a
+                WHERE a.operator = $1
+                  AND NOT EXISTS (
+                      SELECT 1 FROM operator_removeds r
+                      WHERE r.operator = a.operator AND r.block_number > a.block_number
+                  )
+            ) as "active!"
+            "#,
+            operator
+        )
+        .fetch_one(self.db.pool())
+        .await?;
+
+        Ok(active)
+    }
+
+    async fn estimate_gas_cost(&self, config: &Config) -> Result<BigDecimal> {
+        let gas_price = rpc::provider(config)?
+            .get_gas_price()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch gas price: {}", e)))?;
+
+        let gas_price = BigDecimal::from_str(&gas_price.to_string())
+            .map_err(|e| AppError::Internal(format!("Invalid gas price: {}", e)))?;
+
+        Ok(gas_cost(
+            &gas_price,
+            config.rebalancer_gas_limit,
+            &decimal(config.rebalancer_native_price),
+        ))
+    }
+}
+
+async fn record_decision(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    decision: &Decision<'_>,
+    outbox_id: Option<&str>,
+) -> Result<()> {
+    let target = decision.target;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO rebalance_decisions (
+            id, "user", "riskProfile", "currentProtocol", "currentApy", "targetProtocol",
+            "targetApy", balance, "expectedGain", "gasCost", "netGain", action, reason, "outboxId"
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        "#,
+        Uuid::new_v4().to_string(),
+        decision.candidate.user,
+        decision.candidate.risk_profile,
+        decision.current_protocol,
+        decision.current_apy,
+        target.and_then(|target| target.address.clone()),
+        target.map(|target| target.base_apy.clone()),
+        decision.candidate.balance,
+        round(&decision.expected_gain),
+        round(decision.gas_cost),
+        round(&decision.net_gain),
+        decision.action,
+        decision.reason,
+        outbox_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    if decision.action == "skipped" {
+        debug!(
+            "⏭️ [Rebalancer] Skipped {}: {}",
+            decision.candidate.user, decision.reason
+        );
+    }
+
+    Ok(())
+}
+
+fn repeats_last_skip(
+    last: Option<&LastDecision>,
+    reason: &str,
+    target_protocol: Option<&str>,
+) -> bool {
+    last.is_some_and(|last| {
+        last.action == "skipped"
+            && last.reason == reason
+            && last.target_protocol.as_deref() == target_protocol
+    })
+}
+
+pub fn expected_gain(
+    balance: &BigDecimal,
+    current_apy: &BigDecimal,
+    target_apy: &BigDecimal,
+    horizon_days: u64,
+) -> BigDecimal {
+    balance * (target_apy - current_apy) * BigDecimal::from(horizon_days)
+        / BigDecimal::from(100 * 365)
+}
+
+pub fn gas_cost(gas_price: &BigDecimal, gas_limit: u64, native_price: &BigDecimal) -> BigDecimal {
+    gas_price * BigDecimal::from(gas_limit) * native_price / BigDecimal::from(10u64.pow(18))
+}
+
+pub fn skip_reason(
+    balance: &BigDecimal,
+    current_protocol: Option<&str>,
+    target_protocol: &str,
+    net_gain: &BigDecimal,
+    min_net_gain: &BigDecimal,
+) -> Option<String> {
+    if *balance <= BigDecimal::zero() {
+        return Some("No delegated balance".to_string());
+    }
+    if current_protocol.is_some_and(|current| current.eq_ignore_ascii_case(target_protocol)) {
+        return Some("Already in the best protocol for the risk profile".to_string());
+    }
+    if net_gain < min_net_gain {
+        return Some(format!(
+            "Net gain is below the threshold of {}",
+            min_net_gain.normalized()
+        ));
+    }
+
+    None
+}
+
+fn decimal(value: f64) -> BigDecimal {
+    BigDecimal::from_str(&value.to_string()).unwrap_or_default()
+}
+
+fn round(value: &BigDecimal) -> BigDecimal {
+    value.with_scale_round(6, RoundingMode::HalfUp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> BigDecimal {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_expected_gain_is_apy_difference_over_horizon() {
+        assert_eq!(
+            expected_gain(&dec("10000"), &dec("3.5"), &dec("7.15"), 30),
+            dec("30")
+        );
+        assert_eq!(
+            expected_gain(&dec("10000"), &dec("5"), &dec("4"), 365),
+            dec("-100")
+        );
+    }
+
+    #[test]
+    fn test_gas_cost_converts_native_fee_to_token_amount() {
+        assert_eq!(
+            gas_cost(&dec("400000000000"), 500_000, &dec("0.05")),
+            dec("0.01")
+        );
+    }
+
+    #[test]
+    fn test_only_changed_skips_are_recorded() {
+        let protocol = "0x98A593E804C70a3fe039f91fF26f31B26A181960";
+        let target = Some(protocol);
+        let below = skip_reason(&dec("100"), None, protocol, &dec("0.5"), &dec("1")).unwrap();
+        assert_eq!(
+            below,
+            skip_reason(&dec("100"), None, protocol, &dec("0.7"), &dec("1")).unwrap()
+        );
+
+        let last = |action: &str, reason: &str, target: Option<&str>| LastDecision {
+            action: action.to_string(),
+            reason: reason.to_string(),
+            target_protocol: target.map(str::to_string),
+        };
+
+        assert!(!repeats_last_skip(None, &below, target));
+        assert!(repeats_last_skip(
+            Some(&last("skipped", &below, target)),
+            &below,
+            target
+        ));
+        assert!(!repeats_last_skip(
+            Some(&last(
+                "skipped",
+                "Previous rebalance is still in flight",
+                target
+            )),
+            &below,
+            target
+        ));
+        assert!(!repeats_last_skip(
+            Some(&last("skipped", &below, Some("0xabc"))),
+            &below,
+            target
+        ));
+        assert!(!repeats_last_skip(
+            Some(&last("submitted", &below, target)),
+            &below,
+            target
+        ));
+    }
+
+    #[test]
+    fn test_rebalances_only_when_net_gain_clears_threshold() {
+        let target = "0x98A593E804C70a3fe039f91fF26f31B26A181960";
+
+        assert_eq!(
+            skip_reason(&dec("100"), Some("0xabc"), target, &dec("1.5"), &dec("1")),
+            None
+        );
+        assert_eq!(
+            skip_reason(&dec("100"), None, target, &dec("1"), &dec("1")),
+            None
+        );
+        assert!(
+            skip_reason(&dec("100"), None, target, &dec("0.99"), &dec("1"))
+                .unwrap()
+                .contains("below the threshold")
+        );
+        assert!(skip_reason(&dec("0"), None, target, &dec("5"), &dec("1"))
+            .unwrap()
+            .contains("No delegated balance"));
+        assert!(skip_reason(
+            &dec("100"),
+            Some(&target.to_lowercase()),
+            target,
+            &dec("5"),
+            &dec("1")
+        )
+        .unwrap()
+        .contains("Already in the best protocol"));
+    }
+}
@@ -0,0 +1,807 @@
+use bigdecimal::BigDecimal;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::info;
+
+use crate::{
+    config::Config,
+    db::Database,
+    error::{AppError, Result},
+    services::{
+        indexer::{IndexSummary, IndexedContractKind, IndexerService},
+        projection::{self, MarketAggregate},
+    },
+};
+
+const EVENT_BET_ID_PATTERN: &str = "^0x[0-9a-f]{64}-[0-9]+$";
+
+#[derive(Debug, Clone)]
+struct BetEvent {
+    id: String,
+    market_id: i64,
+    user: String,
+    position: bool,
+    amount: BigDecimal,
+    shares: BigDecimal,
+    block_timestamp: i64,
+    transaction_hash: String,
+}
+
+#[derive(Debug, Clone)]
+struct ClaimEvent {
+    market_id: i64,
+    user: String,
+    winning_amount: BigDecimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ProjectedBet {
+    id: String,
+    market_id: i64,
+    user: String,
+    position: bool,
+    amount: BigDecimal,
+    shares: BigDecimal,
+    status: &'static str,
+    payout: Option<BigDecimal>,
+    block_timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectionChange {
+    pub entity: &'static str,
+    pub id: String,
+    pub field: &'static str,
+    pub current: Option<String>,
+    pub rebuilt: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebuildReport {
+    pub dry_run: bool,
+    pub markets_created: Vec<i64>,
+    pub bets_inserted: Vec<String>,
+    pub bets_deleted: Vec<String>,
+    pub changes: Vec<ProjectionChange>,
+}
+
+struct StoredMarket {
+    id: String,
+    status: String,
+    result: Option<bool>,
+    probability: i32,
+    totals: MarketAggregate,
+}
+
+struct StoredBet {
+    market_id: Option<i64>,
+    user: String,
+    position: Option<bool>,
+    amount: Option<BigDecimal>,
+    shares: Option<BigDecimal>,
+    status: String,
+    payout: Option<BigDecimal>,
+}
+
+pub struct ReplayService {
+    db: Database,
+}
+
+impl ReplayService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn replay_events(
+        &self,
+        config: &Config,
+        contracts: &[String],
+        events: &[String],
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<IndexSummary> {
+        if from_block > to_block {
+            return Err(AppError::BadRequest(format!(
+                "Invalid block range {}..{}",
+                from_block, to_block
+            )));
+        }
+
+        let kinds = contracts
+            .iter()
+            .map(|name| {
+                IndexedContractKind::parse(name)
+                    .ok_or_else(|| AppError::BadRequest(format!("Unknown contract: {}", name)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let searched = if kinds.is_empty() {
+            vec![
+                IndexedContractKind::PredictionMarket,
+                IndexedContractKind::ProtocolSelector,
+                IndexedContractKind::RebalancerDelegation,
+            ]
+        } else {
+            kinds.clone()
+        };
+        let tables = events
+            .iter()
+            .map(|event| {
+                let table = event_table(event);
+                if searched
+                    .iter()
+                    .any(|kind| kind.tables().contains(&table.as_str()))
+                {
+                    Ok(table)
+                } else {
+                    Err(AppError::BadRequest(format!("Unknown event: {}", event)))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        IndexerService::new(self.db.clone(), config)?
+            .replay(&kinds, &tables, from_block, to_block)
+            .await
+    }
+
+    pub async fn rebuild(&self, dry_run: bool) -> Result<RebuildReport> {
+        let created = sqlx::query!(
+            r#"
+            SELECT DISTINCT ON (mc.market_id)
+                   mc.market_id::bigint as "market_id!", mc.question,
+                   mc.end_time::bigint as "end_time!", mc.vault_address,
+                   mc.block_timestamp::bigint as "block_timestamp!"
+            FROM market_createds mc
+            ORDER BY mc.market_id, mc.block_number, mc.id
+            "#
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let bet_events = sqlx::query!(
+            r#"
+            SELECT id, market_id::bigint as "market_id!", "user", position, amount,
+                   COALESCE(shares, 0) as "shares!", block_timestamp::bigint as "block_timestamp!",
+                   transaction_hash
+            FROM bet_placeds
+            ORDER BY block_number, id
+            "#
+        )
+        .fetch_all(self.db.pool())
+        .await?
+        .into_iter()
+        .map(|row| BetEvent {
+            id: row.id,
+            market_id: row.market_id,
+            user: row.user,
+            position: row.position,
+            amount: row.amount,
+            shares: row.shares,
+            block_timestamp: row.block_timestamp,
+            transaction_hash: row.transaction_hash,
+        })
+        .collect::<Vec<_>>();
+
+        let server_bets = sqlx::query!(
+            r#"
+            SELECT t.hash, t."betId" as "bet_id!"
+            FROM transactions t
+            JOIN bet_placeds bp ON bp.transaction_hash = t.hash
+            WHERE t."betId" IS NOT NULL
+            "#
+        )
+        .fetch_all(self.db.pool())
+        .await?
+        .into_iter()
+        .map(|row| (row.hash, row.bet_id))
+        .collect::<HashMap<_, _>>();
+
+        let resolution_events = sqlx::query!(
+            r#"
+            SELECT market_id::bigint as "market_id!", outcome,
+                   block_timestamp::bigint as "block_timestamp!"
+            FROM market_resolveds
+            ORDER BY block_number, id
+            "#
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let claims = sqlx::query!(
+            r#"
+            SELECT market_id::bigint as "market_id!", "user", winning_amount
+            FROM winnings_claimeds
+            ORDER BY block_number, id
+            "#
+        )
+        .fetch_all(self.db.pool())
+        .await?
+        .into_iter()
+        .map(|row| ClaimEvent {
+            market_id: row.market_id,
+            user: row.user,
+            winning_amount: row.winning_amount,
+        })
+        .collect::<Vec<_>>();
+
+        let stored_markets = sqlx::query!(
+            r#"
+            SELECT id, "blockchainMarketId" as "blockchain_market_id!", status, result,
+                   probability, "yesPoolSize", "noPoolSize", "countYes", "countNo",
+                   "totalYesShares", "totalNoShares"
+            FROM markets_extended
+            WHERE "blockchainMarketId" IS NOT NULL
+            "#
+        )
+        .fetch_all(self.db.pool())
+        .await?
+        .into_iter()
+        .map(|row| {
+            (
+                row.blockchain_market_id,
+                StoredMarket {
+                    id: row.id,
+                    status: row.status,
+                    result: row.result,
+                    probability: row.probability,
+                    totals: MarketAggregate {
+                        yes_pool: row.yesPoolSize,
+                        no_pool: row.noPoolSize,
+                        count_yes: row.countYes,
+                        count_no: row.countNo,
+                        yes_shares: row.totalYesShares,
+                        no_shares: row.totalNoShares,
+                    },
+                },
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+
+        let stored_bets = sqlx::query!(
+            r#"
+            SELECT b.id, m."blockchainMarketId" as blockchain_market_id, u.address,
+                   b.position, b.amount, b.shares, b.status, b.payout
+            FROM bets_extended b
+            JOIN users u ON u.id = b."userId"
+            LEFT JOIN markets_extended m ON m.id = b."marketId"
+            WHERE b.id ~ $1 OR b.id IN (SELECT id FROM bet_placeds) OR b.id = ANY($2)
+            "#,
+            EVENT_BET_ID_PATTERN,
+            &server_bets.values().cloned().collect::<Vec<_>>()
+        )
+        .fetch_all(self.db.pool())
+        .await?
+        .into_iter()
+        .map(|row| {
+            (
+                row.id,
+                StoredBet {
+                    market_id: row.blockchain_market_id,
+                    user: row.address,
+                    position: row.position,
+                    amount: row.amount,
+                    shares: row.shares,
+                    status: row.status,
+                    payout: row.payout,
+                },
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+        let mut outcomes = stored_markets
+            .iter()
+            .filter(|(_, market)| market.status == "resolved")
+            .filter_map(|(id, market)| market.result.map(|result| (*id, result)))
+            .collect::<HashMap<_, _>>();
+        let mut resolved_at = HashMap::new();
+        for resolution in &resolution_events {
+            outcomes.insert(resolution.market_id, resolution.outcome);
+            resolved_at.insert(resolution.market_id, resolution.block_timestamp);
+        }
+
+        let projected = project_bets(&bet_events, &server_bets, &outcomes, &claims);
+        let mut totals = market_totals(&projected);
+
+        let mut report = RebuildReport {
+            dry_run,
+            ..Default::default()
+        };
+
+        for market in &created {
+            if !stored_markets.contains_key(&market.market_id) {
+                report.markets_created.push(market.market_id);
+                totals.entry(market.market_id).or_default();
+            }
+        }
+
+        let projected_ids = projected
+            .iter()
+            .map(|bet| bet.id.as_str())
+            .collect::<HashSet<_>>();
+        report.bets_deleted = stored_bets
+            .keys()
+            .filter(|id| !projected_ids.contains(id.as_str()))
+            .cloned()
+            .collect();
+        report.bets_deleted.sort();
+
+        let mut upserts = Vec::new();
+        for bet in &projected {
+            match stored_bets.get(&bet.id) {
+                None => {
+                    report.bets_inserted.push(bet.id.clone());
+                    upserts.push(bet);
+                }
+                Some(stored) => {
+                    let changes = diff_bet(stored, bet);
+                    if !changes.is_empty() {
+                        report.changes.extend(changes);
+                        upserts.push(bet);
+                    }
+                }
+            }
+        }
+
+        let mut market_updates = Vec::new();
+        for (blockchain_id, market) in &stored_markets {
+            let rebuilt = totals.remove(blockchain_id).unwrap_or_default();
+            let mut changes = diff_totals(&market.id, &market.totals, &rebuilt);
+            if market.probability != rebuilt.probability() {
+                changes.push(change(
+                    "market",
+                    &market.id,
+                    "probability",
+                    Some(market.probability),
+                    Some(rebuilt.probability()),
+                ));
+            }
+            let resolution = resolved_at
+                .get(blockchain_id)
+                .map(|at| (outcomes[blockchain_id], *at))
+                .filter(|(outcome, _)| {
+                    market.status != "resolved" || market.result != Some(*outcome)
+                });
+            if let Some((outcome, _)) = resolution {
+                changes.push(ProjectionChange {
+                    entity: "market",
+                    id: market.id.clone(),
+                    field: "status",
+                    current: Some(format!("{} ({:?})", market.status, market.result)),
+                    rebuilt: Some(format!("resolved (Some({}))", outcome)),
+                });
+            }
+            if !changes.is_empty() {
+                report.changes.extend(changes);
+                market_updates.push((market, resolution));
+            }
+        }
+
+        info!(
+            "🧮 [Rebuild] {} markets to create, {} bets to insert, {} to delete, {} field changes{}",
+            report.markets_created.len(),
+            report.bets_inserted.len(),
+            report.bets_deleted.len(),
+            report.changes.len(),
+            if dry_run { " (dry run)" } else { "" }
+        );
+
+        if dry_run {
+            return Ok(report);
+        }
+
+        let mut tx = self.db.pool().begin().await?;
+
+        for market in created
+            .iter()
+            .filter(|market| report.markets_created.contains(&market.market_id))
+        {
+            sqlx::query!(
+                r#"
+                INSERT INTO markets_extended (
+                    id, "blockchainMarketId", question, "endDate", "vaultAddress", status,
+                    "createdAt", "updatedAt"
+                )
+                VALUES (
+                    gen_random_uuid()::text, $1, $2, to_timestamp($3)::timestamp, $4, 'active',
+                    to_timestamp($5)::timestamp, NOW()
+                )
+                ON CONFLICT ("blockchainMarketId") DO NOTHING
+                "#,
+                market.market_id,
+                market.question,
+                market.end_time as f64,
+                market.vault_address,
+                market.block_timestamp as f64
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for bet_id in &report.bets_deleted {
+            sqlx::query!("DELETE FROM bets_extended WHERE id = $1", bet_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        for bet in upserts {
+            sqlx::query!(
+                r#"
+                INSERT INTO users (id, address, "createdAt", "updatedAt")
+                VALUES (gen_random_uuid()::text, $1, NOW(), NOW())
+                ON CONFLICT (address) DO NOTHING
+                "#,
+                bet.user
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO bets_extended (
+                    id, "userId", "marketId", position, amount, shares, odds, status, payout,
+                    "createdAt", "updatedAt"
+                )
+                SELECT $1, u.id,
+                       (SELECT id FROM markets_extended WHERE "blockchainMarketId" = $3),
+                       $4, $5, $6, 1.0, $7, $8, to_timestamp($9)::timestamp, NOW()
+                FROM users u
+                WHERE u.address = $2
+                LIMIT 1
+                ON CONFLICT (id) DO UPDATE
+                SET "userId" = EXCLUDED."userId", "marketId" = EXCLUDED."marketId",
+                    position = EXCLUDED.position, amount = EXCLUDED.amount,
+                    shares = EXCLUDED.shares, status = EXCLUDED.status, payout = EXCLUDED.payout
+                "#,
+                bet.id,
+                bet.user,
+                bet.market_id,
+                bet.position,
+                bet.amount,
+                bet.shares,
+                bet.status,
+                bet.payout,
+                bet.block_timestamp as f64
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for blockchain_id in &report.markets_created {
+            let market_id = sqlx::query_scalar!(
+                r#"SELECT id FROM markets_extended WHERE "blockchainMarketId" = $1"#,
+                blockchain_id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            projection::refresh_market(&mut tx, &market_id).await?;
+        }
+
+        for (market, resolution) in market_updates {
+            projection::refresh_market(&mut tx, &market.id).await?;
+
+            if let Some((outcome, block_timestamp)) = resolution {
+                sqlx::query!(
+                    r#"
+                    UPDATE markets_extended
+                    SET status = 'resolved', result = $2,
+                        "resolutionDate" = to_timestamp($3)::timestamp, "updatedAt" = NOW()
+                    WHERE id = $1
+                    "#,
+                    market.id,
+                    outcome,
+                    block_timestamp as f64
+                )
+                .execute(&mut *tx)
+                .await?;
+
+                sqlx::query!(
+                    r#"
+                    INSERT INTO market_status_history ("marketId", "fromStatus", "toStatus", actor, reason)
+                    VALUES ($1, $2, 'resolved', 'replay', 'rebuilt from MarketResolved event')
+                    "#,
+                    market.id,
+                    market.status
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        info!("✅ [Rebuild] Projections rebuilt from raw events");
+
+        Ok(report)
+    }
+}
+
+pub fn event_table(event: &str) -> String {
+    if event.contains('_') || event.chars().all(|c| !c.is_ascii_uppercase()) {
+        return event.to_string();
+    }
+
+    let mut table = String::new();
+    for (index, c) in event.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if index > 0 {
+                table.push('_');
+            }
+            table.push(c.to_ascii_lowercase());
+        } else {
+            table.push(c);
+        }
+    }
+    table.push('s');
+    table
+}
+
+fn project_bets(
+    bets: &[BetEvent],
+    server_bets: &HashMap<String, String>,
+    outcomes: &HashMap<i64, bool>,
+    claims: &[ClaimEvent],
+) -> Vec<ProjectedBet> {
+    let mut projected = bets
+        .iter()
+        .map(|bet| ProjectedBet {
+            id: server_bets
+                .get(&bet.transaction_hash)
+                .unwrap_or(&bet.id)
+                .clone(),
+            market_id: bet.market_id,
+            user: bet.user.clone(),
+            position: bet.position,
+            amount: bet.amount.clone(),
+            shares: bet.shares.clone(),
+            status: outcomes
+                .get(&bet.market_id)
+                .and_then(|outcome| {
+                    projection::resolved_bet_status("active", bet.position, *outcome)
+                })
+                .unwrap_or("active"),
+            payout: None,
+            block_timestamp: bet.block_timestamp,
+        })
+        .collect::<Vec<_>>();
+
+    for claim in claims {
+        for bet in projected.iter_mut().filter(|bet| {
+            bet.market_id == claim.market_id
+                && bet.user == claim.user
+                && projection::is_claimable(bet.status)
+        }) {
+            bet.status = "claimed";
+            bet.payout = Some(claim.winning_amount.clone());
+        }
+    }
+
+    projected
+}
+
+fn market_totals(bets: &[ProjectedBet]) -> BTreeMap<i64, MarketAggregate> {
+    let mut totals = BTreeMap::<i64, MarketAggregate>::new();
+
+    for bet in bets {
+        totals
+            .entry(bet.market_id)
+            .or_default()
+            .add(bet.position, &bet.amount, Some(&bet.shares));
+    }
+
+    totals
+}
+
+fn change<T: ToString>(
+    entity: &'static str,
+    id: &str,
+    field: &'static str,
+    current: Option<T>,
+    rebuilt: Option<T>,
+) -> ProjectionChange {
+    ProjectionChange {
+        entity,
+        id: id.to_string(),
+        field,
+        current: current.map(|value| value.to_string()),
+        rebuilt: rebuilt.map(|value| value.to_string()),
+    }
+}
+
+fn diff_totals(
+    market_id: &str,
+    current: &MarketAggregate,
+    rebuilt: &MarketAggregate,
+) -> Vec<ProjectionChange> {
+    let mut changes = Vec::new();
+
+    for (field, current, rebuilt) in [
+        ("yesPoolSize", &current.yes_pool, &rebuilt.yes_pool),
+        ("noPoolSize", &current.no_pool, &rebuilt.no_pool),
+        ("totalYesShares", &current.yes_shares, &rebuilt.yes_shares),
+        ("totalNoShares", &current.no_shares, &rebuilt.no_shares),
+    ] {
+        if current != rebuilt {
+            changes.push(change(
+                "market",
+                market_id,
+                field,
+                Some(current.normalized()),
+                Some(rebuilt.normalized()),
+            ));
+        }
+    }
+
+    for (field, current, rebuilt) in [
+        ("countYes", current.count_yes, rebuilt.count_yes),
+        ("countNo", current.count_no, rebuilt.count_no),
+    ] {
+        if current != rebuilt {
+            changes.push(change(
+                "market",
+                market_id,
+                field,
+                Some(current),
+                Some(rebuilt),
+            ));
+        }
+    }
+
+    changes
+}
+
+fn diff_bet(stored: &StoredBet, rebuilt: &ProjectedBet) -> Vec<ProjectionChange> {
+    let mut changes = Vec::new();
+    let id = rebuilt.id.as_str();
+
+    if stored.market_id != Some(rebuilt.market_id) {
+        changes.push(change(
+            "bet",
+            id,
+            "marketId",
+            stored.market_id,
+            Some(rebuilt.market_id),
+        ));
+    }
+    if stored.user != rebuilt.user {
+        changes.push(change(
+            "bet",
+            id,
+            "user",
+            Some(stored.user.as_str()),
+            Some(rebuilt.user.as_str()),
+        ));
+    }
+    if stored.position != Some(rebuilt.position) {
+        changes.push(change(
+            "bet",
+            id,
+            "position",
+            stored.position,
+            Some(rebuilt.position),
+        ));
+    }
+    for (field, current, rebuilt) in [
+        ("amount", stored.amount.as_ref(), Some(&rebuilt.amount)),
+        ("shares", stored.shares.as_ref(), Some(&rebuilt.shares)),
+        ("payout", stored.payout.as_ref(), rebuilt.payout.as_ref()),
+    ] {
+        if current != rebuilt {
+            changes.push(change(
+                "bet",
+                id,
+                field,
+                current.map(|value| value.normalized()),
+                rebuilt.map(|value| value.normalized()),
+            ));
+        }
+    }
+    if stored.status != rebuilt.status {
+        changes.push(change(
+            "bet",
+            id,
+            "status",
+            Some(stored.status.as_str()),
+            Some(rebuilt.status),
+        ));
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bet(id: &str, market_id: i64, user: &str, position: bool, amount: i64) -> BetEvent {
+        BetEvent {
+            id: id.to_string(),
+            market_id,
+            user: user.to_string(),
+            position,
+            amount: BigDecimal::from(amount),
+            shares: BigDecimal::from(amount * 2),
+            block_timestamp: 1_700_000_000,
+            transaction_hash: format!("0x{}", id),
+        }
+    }
+
+    #[test]
+    fn test_event_names_map_to_raw_tables() {
+        assert_eq!(event_table("BetPlaced"), "bet_placeds");
+        assert_eq!(
+            event_table("AutoRebalanceEnabled"),
+            "auto_rebalance_enableds"
+        );
+        assert_eq!(event_table("winnings_claimeds"), "winnings_claimeds");
+    }
+
+    #[test]
+    fn test_projects_resolution_and_claims_onto_bets() {
+        let bets = vec![
+            bet("a", 1, "0xaa", true, 100),
+            bet("b", 1, "0xbb", false, 50),
+            bet("c", 2, "0xaa", true, 10),
+        ];
+        let outcomes = HashMap::from([(1, true)]);
+        let claims = vec![ClaimEvent {
+            market_id: 1,
+            user: "0xaa".to_string(),
+            winning_amount: BigDecimal::from(150),
+        }];
+
+        let projected = project_bets(&bets, &HashMap::new(), &outcomes, &claims);
+
+        assert_eq!(projected[0].status, "claimed");
+        assert_eq!(projected[0].payout, Some(BigDecimal::from(150)));
+        assert_eq!(projected[1].status, "lost");
+        assert_eq!(projected[1].payout, None);
+        assert_eq!(projected[2].status, "active");
+    }
+
+    #[test]
+    fn test_server_placed_bets_are_counted_once_under_their_own_id() {
+        let server_bets = HashMap::from([("0xa".to_string(), "server-bet".to_string())]);
+        let projected = project_bets(
+            &[
+                bet("a", 1, "0xaa", true, 100),
+                bet("b", 1, "0xbb", false, 50),
+            ],
+            &server_bets,
+            &HashMap::new(),
+            &[],
+        );
+
+        assert_eq!(projected[0].id, "server-bet");
+        assert_eq!(projected[1].id, "b");
+
+        let totals = market_totals(&projected);
+
+        let market = &totals[&1];
+        assert_eq!(market.yes_pool, BigDecimal::from(100));
+        assert_eq!(market.no_pool, BigDecimal::from(50));
+        assert_eq!((market.count_yes, market.count_no), (1, 1));
+        assert_eq!(market.yes_shares, BigDecimal::from(200));
+        assert_eq!(market.no_shares, BigDecimal::from(100));
+    }
+
+    #[test]
+    fn test_diff_reports_only_changed_fields() {
+        let current = MarketAggregate {
+            yes_pool: "125.000000000000000000".parse().unwrap(),
+            count_yes: 1,
+            ..Default::default()
+        };
+        let rebuilt = MarketAggregate {
+            yes_pool: BigDecimal::from(125),
+            count_yes: 2,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            diff_totals("m1", &current, &rebuilt),
+            vec![change("market", "m1", "countYes", Some(1), Some(2))]
+        );
+    }
+}
@@ -0,0 +1,560 @@
+use bigdecimal::BigDecimal;
+use tracing::info;
+
+use crate::{
+    db::Database,
+    error::{AppError, Result},
+    models::{ResolutionAuditEntry, ResolutionDispute, ResolutionProposal},
+    services::market::apply_transition,
+    utils::{settle_bets, BetStake, MarketState},
+};
+
+const MAX_EVIDENCE_LINKS: usize = 10;
+
+const PROPOSAL_COLUMNS: &str = r#"id, "marketId", outcome, evidence, "proposedBy", status, "challengeEndsAt", "finalOutcome", "decidedBy", "decisionReason", "decidedAt", "createdAt""#;
+
+pub struct MarketResolution {
+    pub proposals: Vec<ResolutionProposal>,
+    pub disputes: Vec<ResolutionDispute>,
+    pub audit: Vec<ResolutionAuditEntry>,
+}
+
+pub struct ResolutionService {
+    db: Database,
+}
+
+impl ResolutionService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn propose(
+        &self,
+        market_id: &str,
+        outcome: bool,
+        evidence: &[String],
+        proposed_by: &str,
+        challenge_window_secs: u64,
+    ) -> Result<ResolutionProposal> {
+        validate_evidence(evidence, true)?;
+
+        let mut tx = self.db.pool().begin().await?;
+
+        let market = sqlx::query!(
+            r#"
+            SELECT id, status, "endDate" <= NOW() as "expired!"
+            FROM markets_extended
+            WHERE id = $1 OR "marketId" = $1
+            FOR UPDATE
+            "#,
+            market_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Market with id {} not found", market_id)))?;
+
+        let mut state = market.status.parse::<MarketState>()?;
+        if state == MarketState::Active && market.expired {
+            apply_transition(
+                &mut tx,
+                &market.id,
+                state,
+                MarketState::Closed,
+                proposed_by,
+                Some("end date reached"),
+            )
+            .await?;
+            state = MarketState::Closed;
+        }
+        if state != MarketState::Closed {
+            return Err(AppError::BadRequest(format!(
+                "Only closed markets can be resolved; market is {}",
+                state
+            )));
+        }
+
+        apply_transition(
+            &mut tx,
+            &market.id,
+            state,
+            MarketState::PendingResolution,
+            proposed_by,
+            Some("outcome proposed"),
+        )
+        .await?;
+
+        let proposal = sqlx::query_as::<_, ResolutionProposal>(&format!(
+            r#"
+            INSERT INTO market_resolution_proposals
+                (id, "marketId", outcome, evidence, "proposedBy", "challengeEndsAt")
+            VALUES ($1, $2, $3, $4, $5, NOW() + make_interval(secs => $6))
+            RETURNING {}
+            "#,
+            PROPOSAL_COLUMNS
+        ))
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&market.id)
+        .bind(outcome)
+        .bind(evidence)
+        .bind(proposed_by)
+        .bind(challenge_window_secs as f64)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        insert_audit(
+            &mut tx,
+            &market.id,
+            &proposal.id,
+            "proposed",
+            proposed_by,
+            Some(outcome),
+            None,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        info!(
+            "⚖️ Outcome {} proposed for market {} (challenge window ends {})",
+            outcome, market.id, proposal.challenge_ends_at
+        );
+
+        Ok(proposal)
+    }
+
+    pub async fn dispute(
+        &self,
+        proposal_id: &str,
+        disputed_by: &str,
+        reason: &str,
+        evidence: &[String],
+    ) -> Result<ResolutionDispute> {
+        if reason.trim().is_empty() {
+            return Err(AppError::BadRequest("A dispute needs a reason".to_string()));
+        }
+        validate_evidence(evidence, false)?;
+
+        let mut tx = self.db.pool().begin().await?;
+
+        let proposal = lock_proposal(&mut tx, proposal_id).await?;
+        ensure_open(&proposal)?;
+
+        if proposal.challenge_ends_at <= chrono::Utc::now().naive_utc() {
+            return Err(AppError::BadRequest(
+                "The challenge window for this proposal has ended".to_string(),
+            ));
+        }
+
+        let dispute = sqlx::query_as::<_, ResolutionDispute>(
+            r#"
+            INSERT INTO market_resolution_disputes (id, "proposalId", "disputedBy", reason, evidence)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT ("proposalId", "disputedBy") DO NOTHING
+            RETURNING id, "proposalId", "disputedBy", reason, evidence, "createdAt"
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&proposal.id)
+        .bind(disputed_by)
+        .bind(reason.trim())
+        .bind(evidence)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            AppError::BadRequest("You have already disputed this proposal".to_string())
+        })?;
+
+        sqlx::query!(
+            r#"UPDATE market_resolution_proposals SET status = 'disputed' WHERE id = $1"#,
+            proposal.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        insert_audit(
+            &mut tx,
+            &proposal.market_id,
+            &proposal.id,
+            "disputed",
+            disputed_by,
+            None,
+            Some(reason.trim()),
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        info!(
+            "⚠️ Proposal {} for market {} disputed by {}",
+            proposal.id, proposal.market_id, disputed_by
+        );
+
+        Ok(dispute)
+    }
+
+    pub async fn finalize(
+        &self,
+        proposal_id: &str,
+        decided_by: &str,
+        outcome: Option<bool>,
+        reason: Option<&str>,
+    ) -> Result<(ResolutionProposal, usize)> {
+        let mut tx = self.db.pool().begin().await?;
+
+        let proposal = lock_proposal(&mut tx, proposal_id).await?;
+        ensure_open(&proposal)?;
+
+        let final_outcome = if proposal.status == "disputed" {
+            outcome.ok_or_else(|| {
+                AppError::BadRequest(
+                    "Disputed proposals need an explicit outcome to finalize".to_string(),
+                )
+            })?
+        } else {
+            if proposal.challenge_ends_at > chrono::Utc::now().naive_utc() {
+                return Err(AppError::BadRequest(format!(
+                    "The challenge window is open until {}",
+                    proposal.challenge_ends_at
+                )));
+            }
+            if outcome.is_some_and(|outcome| outcome != proposal.outcome) {
+                return Err(AppError::BadRequest(
+                    "Undisputed proposals finalize with the proposed outcome; reject it instead"
+                        .to_string(),
+                ));
+            }
+            proposal.outcome
+        };
+
+        let finalized = sqlx::query_as::<_, ResolutionProposal>(&format!(
+            r#"
+            UPDATE market_resolution_proposals
+            SET status = 'finalized', "finalOutcome" = $2, "decidedBy" = $3,
+                "decisionReason" = $4, "decidedAt" = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            PROPOSAL_COLUMNS
+        ))
+        .bind(&proposal.id)
+        .bind(final_outcome)
+        .bind(decided_by)
+        .bind(reason)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let market = sqlx::query!(
+            r#"SELECT status FROM markets_extended WHERE id = $1 FOR UPDATE"#,
+            proposal.market_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        apply_transition(
+            &mut tx,
+            &proposal.market_id,
+            market.status.parse::<MarketState>()?,
+            MarketState::Resolved,
+            decided_by,
+            Some("resolution finalized"),
+        )
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE markets_extended
+            SET result = $2, "resolutionDate" = NOW(), "updatedAt" = NOW()
+            WHERE id = $1
+            "#,
+            proposal.market_id,
+            final_outcome
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let settled = settle_market_bets(&mut tx, &proposal.market_id, final_outcome).await?;
+
+        insert_audit(
+            &mut tx,
+            &proposal.market_id,
+            &proposal.id,
+            "finalized",
+            decided_by,
+            Some(final_outcome),
+            reason,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        info!(
+            "✅ Market {} resolved to {} ({} bets settled)",
+            proposal.market_id, final_outcome, settled
+        );
+
+        Ok((finalized, settled))
+    }
+
+    pub async fn reject(
+        &self,
+        proposal_id: &str,
+        decided_by: &str,
+        reason: &str,
+    ) -> Result<ResolutionProposal> {
+        if reason.trim().is_empty() {
+            return Err(AppError::BadRequest(
+                "Rejecting a proposal needs a reason".to_string(),
+            ));
+        }
+
+        let mut tx = self.db.pool().begin().await?;
+
+        let proposal = lock_proposal(&mut tx, proposal_id).await?;
+        ensure_open(&proposal)?;
+
+        let rejected = sqlx::query_as::<_, ResolutionProposal>(&format!(
+            r#"
+            UPDATE market_resolution_proposals
+            SET status = 'rejected', "decidedBy" = $2, "decisionReason" = $3, "decidedAt" = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            PROPOSAL_COLUMNS
+        ))
+        .bind(&proposal.id)
+        .bind(decided_by)
+        .bind(reason.trim())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let market = sqlx::query!(
+            r#"SELECT status FROM markets_extended WHERE id = $1 FOR UPDATE"#,
+            proposal.market_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        apply_transition(
+            &mut tx,
+            &proposal.market_id,
+            market.status.parse::<MarketState>()?,
+            MarketState::Closed,
+            decided_by,
+            Some("resolution proposal rejected"),
+        )
+        .await?;
+
+        insert_audit(
+            &mut tx,
+            &proposal.market_id,
+            &proposal.id,
+            "rejected",
+            decided_by,
+            None,
+            Some(reason.trim()),
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        info!(
+            "🚫 Proposal {} for market {} rejected",
+            proposal.id, proposal.market_id
+        );
+
+        Ok(rejected)
+    }
+
+    pub async fn get_market_resolution(&self, market_id: &str) -> Result<MarketResolution> {
+        let market = sqlx::query!(
+            r#"SELECT id FROM markets_extended WHERE id = $1 OR "marketId" = $1"#,
+            market_id
+        )
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Market with id {} not found", market_id)))?;
+
+        let proposals = sqlx::query_as::<_, ResolutionProposal>(&format!(
+            r#"
+            SELECT {}
+            FROM market_resolution_proposals
+            WHERE "marketId" = $1
+            ORDER BY "createdAt" DESC
+            "#,
+            PROPOSAL_COLUMNS
+        ))
+        .bind(&market.id)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let disputes = sqlx::query_as::<_, ResolutionDispute>(
+            r#"
+            SELECT d.id, d."proposalId", d."disputedBy", d.reason, d.evidence, d."createdAt"
+            FROM market_resolution_disputes d
+            JOIN market_resolution_proposals p ON d."proposalId" = p.id
+            WHERE p."marketId" = $1
+            ORDER BY d."createdAt" ASC
+            "#,
+        )
+        .bind(&market.id)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let audit = sqlx::query_as::<_, ResolutionAuditEntry>(
+            r#"
+            SELECT id, "proposalId", action, actor, outcome, note, "createdAt"
+            FROM market_resolution_audit
+            WHERE "marketId" = $1
+            ORDER BY "createdAt" ASC, id ASC
+            "#,
+        )
+        .bind(&market.id)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(MarketResolution {
+            proposals,
+            disputes,
+            audit,
+        })
+    }
+}
+
+pub(crate) async fn settle_market_bets(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    market_id: &str,
+    outcome: bool,
+) -> Result<usize> {
+    let yield_pool = sqlx::query!(
+        r#"SELECT "totalYieldEarned" as total_yield_earned FROM markets_extended WHERE id = $1"#,
+        market_id
+    )
+    .fetch_one(&mut **tx)
+    .await?
+    .total_yield_earned;
+
+    let bets: Vec<BetStake> = sqlx::query!(
+        r#"
+        SELECT id, position as "position!", amount as "amount!", shares
+        FROM bets_extended
+        WHERE "marketId" = $1 AND status = 'active'
+          AND position IS NOT NULL AND amount IS NOT NULL
+        FOR UPDATE
+        "#,
+        market_id
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(|row| BetStake {
+        id: row.id,
+        position: row.position,
+        amount: row.amount,
+        shares: row.shares,
+    })
+    .collect();
+
+    if bets.is_empty() {
+        return Ok(0);
+    }
+
+    let settled = settle_bets(&bets, outcome, &yield_pool);
+
+    let ids: Vec<String> = settled.iter().map(|bet| bet.id.clone()).collect();
+    let statuses: Vec<String> = settled
+        .iter()
+        .map(|bet| bet.settlement.as_str().to_string())
+        .collect();
+    let payouts: Vec<BigDecimal> = settled.iter().map(|bet| bet.payout.clone()).collect();
+
+    sqlx::query!(
+        r#"
+        UPDATE bets_extended b
+        SET status = s.status, payout = s.payout, "updatedAt" = NOW()
+        FROM UNNEST($1::text[], $2::text[], $3::numeric[]) AS s(id, status, payout)
+        WHERE b.id = s.id
+        "#,
+        &ids,
+        &statuses,
+        &payouts
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(settled.len())
+}
+
+async fn lock_proposal(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    proposal_id: &str,
+) -> Result<ResolutionProposal> {
+    sqlx::query_as::<_, ResolutionProposal>(&format!(
+        r#"SELECT {} FROM market_resolution_proposals WHERE id = $1 FOR UPDATE"#,
+        PROPOSAL_COLUMNS
+    ))
+    .bind(proposal_id)
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Resolution proposal {} not found", proposal_id)))
+}
+
+fn ensure_open(proposal: &ResolutionProposal) -> Result<()> {
+    if proposal.status != "proposed" && proposal.status != "disputed" {
+        return Err(AppError::BadRequest(format!(
+            "Proposal {} is already {}",
+            proposal.id, proposal.status
+        )));
+    }
+    Ok(())
+}
+
+async fn insert_audit(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    market_id: &str,
+    proposal_id: &str,
+    action: &str,
+    actor: &str,
+    outcome: Option<bool>,
+    note: Option<&str>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO market_resolution_audit ("marketId", "proposalId", action, actor, outcome, note)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        market_id,
+        proposal_id,
+        action,
+        actor,
+        outcome,
+        note
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+fn validate_evidence(evidence: &[String], required: bool) -> Result<()> {
+    if required && evidence.is_empty() {
+        return Err(AppError::BadRequest(
+            "At least one evidence link is required".to_string(),
+        ));
+    }
+    if evidence.len() > MAX_EVIDENCE_LINKS {
+        return Err(AppError::BadRequest(format!(
+            "At most {} evidence links are allowed",
+            MAX_EVIDENCE_LINKS
+        )));
+    }
+    if let Some(link) = evidence
+        .iter()
+        .find(|link| !link.starts_with("https://") && !link.starts_with("http://"))
+    {
+        return Err(AppError::BadRequest(format!(
+            "Evidence must be http(s) links, got '{}'",
+            link
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,401 @@
+use async_trait::async_trait;
+use ethers::providers::{
+    Http, HttpClientError, JsonRpcClient, JsonRpcError, Provider, ProviderError, RpcError,
+};
+use once_cell::sync::OnceCell;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::time;
+use tracing::warn;
+
+use crate::{
+    config::Config,
+    error::{AppError, Result},
+};
+
+static POOL: OnceCell<RpcPool> = OnceCell::new();
+
+pub type RpcProvider = Provider<RpcPool>;
+
+#[derive(Debug, Clone)]
+pub struct RpcPoolSettings {
+    pub max_retries: u32,
+    pub retry_backoff_ms: u64,
+    pub requests_per_second: u32,
+    pub timeout_secs: u64,
+    pub failure_threshold: u32,
+    pub cooldown_secs: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointStatus {
+    pub url: String,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    pub requests: u64,
+    pub failures: u64,
+    pub last_latency_ms: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RpcPoolError {
+    #[error(transparent)]
+    Http(#[from] HttpClientError),
+    #[error("RPC request to {0} timed out")]
+    Timeout(String),
+    #[error("Failed to serialize RPC params: {0}")]
+    Params(serde_json::Error),
+    #[error("No RPC endpoints configured")]
+    NoEndpoints,
+}
+
+impl RpcError for RpcPoolError {
+    fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            RpcPoolError::Http(e) => e.as_error_response(),
+            _ => None,
+        }
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            RpcPoolError::Http(e) => e.as_serde_error(),
+            RpcPoolError::Params(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<RpcPoolError> for ProviderError {
+    fn from(src: RpcPoolError) -> Self {
+        match src {
+            RpcPoolError::Http(e) => e.into(),
+            other => ProviderError::JsonRpcClientError(Box::new(other)),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+    next_slot: Option<Instant>,
+    requests: u64,
+    failures: u64,
+    last_latency_ms: Option<u64>,
+    last_error: Option<String>,
+}
+
+impl EndpointState {
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.unhealthy_until.is_none_or(|until| until <= now)
+    }
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    client: Http,
+    state: Mutex<EndpointState>,
+}
+
+#[derive(Debug)]
+struct RpcPoolInner {
+    endpoints: Vec<Endpoint>,
+    settings: RpcPoolSettings,
+}
+
+#[derive(Debug, Clone)]
+pub struct RpcPool {
+    inner: Arc<RpcPoolInner>,
+}
+
+impl RpcPool {
+    pub fn new(urls: &[String], settings: RpcPoolSettings) -> Result<Self> {
+        let endpoints =
+            urls.iter()
+                .map(|url| {
+                    let client = url.parse::<reqwest::Url>().map(Http::new).map_err(|e| {
+                        AppError::Internal(format!("Invalid RPC URL {}: {}", url, e))
+                    })?;
+                    Ok(Endpoint {
+                        url: url.clone(),
+                        client,
+                        state: Mutex::new(EndpointState::default()),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+        if endpoints.is_empty() {
+            return Err(AppError::Internal(
+                "No RPC endpoints configured".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            inner: Arc::new(RpcPoolInner {
+                endpoints,
+                settings,
+            }),
+        })
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut urls = vec![config.base_rpc_url.clone()];
+        for url in &config.rpc_fallback_urls {
+            if !urls.contains(url) {
+                urls.push(url.clone());
+            }
+        }
+
+        Self::new(
+            &urls,
+            RpcPoolSettings {
+                max_retries: config.rpc_max_retries,
+                retry_backoff_ms: config.rpc_retry_backoff_ms,
+                requests_per_second: config.rpc_rate_limit_per_sec,
+                timeout_secs: config.rpc_timeout_secs,
+                failure_threshold: config.rpc_failure_threshold,
+                cooldown_secs: config.rpc_cooldown_secs,
+            },
+        )
+    }
+
+    pub fn shared(config: &Config) -> Result<RpcPool> {
+        POOL.get_or_try_init(|| Self::from_config(config)).cloned()
+    }
+
+    pub fn statuses(&self) -> Vec<EndpointStatus> {
+        let now = Instant::now();
+        self.inner
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                let state = endpoint.state.lock().unwrap();
+                EndpointStatus {
+                    url: display_url(&endpoint.url),
+                    healthy: state.is_healthy(now),
+                    consecutive_failures: state.consecutive_failures,
+                    requests: state.requests,
+                    failures: state.failures,
+                    last_latency_ms: state.last_latency_ms,
+                    last_error: state.last_error.clone(),
+                }
+            })
+            .collect()
+    }
+
+    fn candidates(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let states = self
+            .inner
+            .endpoints
+            .iter()
+            .map(|endpoint| endpoint.state.lock().unwrap().unhealthy_until)
+            .collect::<Vec<_>>();
+        endpoint_order(&states, now)
+    }
+
+    async fn throttle(&self, endpoint: &Endpoint) {
+        let per_second = self.inner.settings.requests_per_second;
+        if per_second == 0 {
+            return;
+        }
+
+        let slot = {
+            let mut state = endpoint.state.lock().unwrap();
+            let now = Instant::now();
+            let slot = state.next_slot.map_or(now, |next| next.max(now));
+            state.next_slot = Some(slot + Duration::from_secs(1) / per_second);
+            slot
+        };
+        time::sleep_until(slot.into()).await;
+    }
+
+    fn record_success(&self, endpoint: &Endpoint, latency: Duration) {
+        let mut state = endpoint.state.lock().unwrap();
+        state.requests += 1;
+        state.consecutive_failures = 0;
+        state.unhealthy_until = None;
+        state.last_latency_ms = Some(latency.as_millis() as u64);
+    }
+
+    fn record_failure(&self, endpoint: &Endpoint, error: String) {
+        let settings = &self.inner.settings;
+        let mut state = endpoint.state.lock().unwrap();
+        state.requests += 1;
+        state.failures += 1;
+        state.consecutive_failures += 1;
+        state.last_error = Some(error.clone());
+
+        if state.consecutive_failures >= settings.failure_threshold.max(1) {
+            if state.is_healthy(Instant::now()) {
+                warn!(
+                    "⚠️ [RPC] {} marked unhealthy for {}s after {} failures: {}",
+                    display_url(&endpoint.url),
+                    settings.cooldown_secs,
+                    state.consecutive_failures,
+                    error
+                );
+            }
+            state.unhealthy_until =
+                Some(Instant::now() + Duration::from_secs(settings.cooldown_secs));
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for RpcPool {
+    type Error = RpcPoolError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> std::result::Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let params = serde_json::to_value(params).map_err(RpcPoolError::Params)?;
+        let settings = &self.inner.settings;
+        let timeout = Duration::from_secs(settings.timeout_secs.max(1));
+        let mut last_error = None;
+
+        for attempt in 0..=settings.max_retries {
+            if attempt > 0 {
+                let backoff = settings.retry_backoff_ms << (attempt - 1).min(6);
+                time::sleep(Duration::from_millis(backoff)).await;
+            }
+
+            for index in self.candidates() {
+                let endpoint = &self.inner.endpoints[index];
+                self.throttle(endpoint).await;
+
+                let started = Instant::now();
+                let error =
+                    match time::timeout(timeout, endpoint.client.request::<_, R>(method, &params))
+                        .await
+                    {
+                        Ok(Ok(response)) => {
+                            self.record_success(endpoint, started.elapsed());
+                            return Ok(response);
+                        }
+                        Ok(Err(e)) if !is_endpoint_failure(&e) => {
+                            self.record_success(endpoint, started.elapsed());
+                            return Err(e.into());
+                        }
+                        Ok(Err(e)) => RpcPoolError::Http(e),
+                        Err(_) => RpcPoolError::Timeout(display_url(&endpoint.url)),
+                    };
+
+                self.record_failure(endpoint, error.to_string());
+                warn!(
+                    "⚠️ [RPC] {} failed on {} (attempt {}): {}",
+                    method,
+                    display_url(&endpoint.url),
+                    attempt + 1,
+                    error
+                );
+
+                if method == "eth_sendRawTransaction" && matches!(error, RpcPoolError::Timeout(_)) {
+                    return Err(error);
+                }
+                last_error = Some(error);
+            }
+        }
+
+        Err(last_error.unwrap_or(RpcPoolError::NoEndpoints))
+    }
+}
+
+pub fn provider(config: &Config) -> Result<RpcProvider> {
+    Ok(Provider::new(RpcPool::shared(config)?))
+}
+
+fn is_endpoint_failure(error: &HttpClientError) -> bool {
+    match error {
+        HttpClientError::JsonRpcError(e) => is_rate_limited(e),
+        HttpClientError::ReqwestError(_) | HttpClientError::SerdeJson { .. } => true,
+    }
+}
+
+fn is_rate_limited(error: &JsonRpcError) -> bool {
+    let message = error.message.to_lowercase();
+    error.code == 429
+        || error.code == -32005
+        || message.contains("rate limit")
+        || message.contains("too many requests")
+}
+
+fn endpoint_order(unhealthy_until: &[Option<Instant>], now: Instant) -> Vec<usize> {
+    let (mut healthy, mut cooling): (Vec<usize>, Vec<usize>) = (0..unhealthy_until.len())
+        .partition(|&index| unhealthy_until[index].is_none_or(|until| until <= now));
+    cooling.sort_by_key(|&index| unhealthy_until[index]);
+    healthy.append(&mut cooling);
+    healthy
+}
+
+fn display_url(url: &str) -> String {
+    match url.parse::<reqwest::Url>() {
+        Ok(parsed) => parsed.origin().ascii_serialization(),
+        Err(_) => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_rpc_error(code: i64, message: &str) -> HttpClientError {
+        HttpClientError::JsonRpcError(JsonRpcError {
+            code,
+            message: message.to_string(),
+            data: None,
+        })
+    }
+
+    #[test]
+    fn test_only_transport_and_rate_limit_errors_fail_over() {
+        assert!(is_endpoint_failure(&json_rpc_error(
+            429,
+            "Too Many Requests"
+        )));
+        assert!(is_endpoint_failure(&json_rpc_error(
+            -32000,
+            "daily request rate limit exceeded"
+        )));
+        assert!(!is_endpoint_failure(&json_rpc_error(
+            3,
+            "execution reverted"
+        )));
+        assert!(!is_endpoint_failure(&json_rpc_error(
+            -32000,
+            "nonce too low"
+        )));
+    }
+
+    #[test]
+    fn test_unhealthy_endpoints_are_tried_last() {
+        let now = Instant::now();
+        let order = endpoint_order(
+            &[
+                Some(now + Duration::from_secs(20)),
+                None,
+                Some(now + Duration::from_secs(5)),
+                Some(now - Duration::from_secs(1)),
+            ],
+            now,
+        );
+
+        assert_eq!(order, vec![1, 3, 2, 0]);
+    }
+
+    #[test]
+    fn test_endpoint_status_hides_url_path() {
+        assert_eq!(
+            display_url("https://rpc.example.com/v1/secret-key"),
+            "https://rpc.example.com"
+        );
+    }
+}
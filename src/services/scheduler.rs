@@ -4,13 +4,28 @@ use std::time::Duration;
 use tokio::time;
 use tracing::{error, info, warn};
 
-use super::bet::BetService;
+use super::auto_rebalance::AutoRebalanceService;
 use super::blockchain_sync::BlockchainSyncService;
+use super::indexer::{self, IndexerService};
+use super::market::MarketService;
+use super::outbox::OutboxService;
+use super::projection::ProjectionService;
 use super::protocol::ProtocolService;
+use super::rebalancer::RebalancerService;
+use super::signer::SignerService;
+use super::transaction::TransactionService;
 
 #[derive(Debug, Clone)]
 pub struct SchedulerConfig {
     pub scheduler_interval_secs: u64,
+    pub market_lifecycle_interval_secs: u64,
+    pub transaction_watcher_interval_secs: u64,
+    pub transaction_confirmations: u64,
+    pub transaction_reorg_window: u64,
+    pub outbox_interval_secs: u64,
+    pub indexer_interval_secs: u64,
+    pub auto_rebalance_refresh_interval_secs: u64,
+    pub rebalancer_interval_secs: u64,
     pub enable_scheduler: bool,
 }
 
@@ -21,6 +36,40 @@ impl Default for SchedulerConfig {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(300),
+            market_lifecycle_interval_secs: std::env::var("MARKET_LIFECYCLE_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(60),
+            transaction_watcher_interval_secs: std::env::var("TX_WATCHER_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(10),
+            transaction_confirmations: std::env::var("TX_CONFIRMATIONS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(1),
+            transaction_reorg_window: std::env::var("TX_REORG_WINDOW_BLOCKS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(12),
+            outbox_interval_secs: std::env::var("OUTBOX_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(5),
+            indexer_interval_secs: std::env::var("INDEXER_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(15),
+            auto_rebalance_refresh_interval_secs: std::env::var(
+                "AUTO_REBALANCE_REFRESH_INTERVAL_SECS",
+            )
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(60),
+            rebalancer_interval_secs: std::env::var("REBALANCER_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(300),
             enable_scheduler: std::env::var("ENABLE_SCHEDULER")
                 .unwrap_or_else(|_| "true".to_string())
                 .parse()
@@ -57,6 +106,13 @@ impl Scheduler {
             let interval_secs = self.config.scheduler_interval_secs;
             let scheduler = Arc::clone(&self);
             tokio::spawn(async move {
+                let config = match crate::config::Config::from_env() {
+                    Ok(config) => config,
+                    Err(e) => {
+                        error!("❌ [Processing Job] Failed to load configuration: {}", e);
+                        return;
+                    }
+                };
                 let mut interval = time::interval(Duration::from_secs(interval_secs));
                 let mut sync_count = 0u64;
 
@@ -71,11 +127,9 @@ impl Scheduler {
                     let db = crate::db::Database::from_pool(scheduler.pool.clone());
 
                     let protocol_service = ProtocolService::new(db.clone());
-                    let rpc_url = std::env::var("HEDERA_RPC_URL")
-                        .unwrap_or_else(|_| "https://hashscan.io/testnet".to_string());
 
                     match protocol_service
-                        .update_all_apys_from_blockchain(&rpc_url)
+                        .update_all_apys_from_blockchain(&config)
                         .await
                     {
                         Ok(count) => {
@@ -93,11 +147,22 @@ impl Scheduler {
                     }
 
                     let sync_service = BlockchainSyncService::new(db.clone());
-                    match sync_service.run_full_sync().await {
-                        Ok(_) => {
+                    match sync_service.run_full_sync(&config).await {
+                        Ok(run) if run.status == "failed" => {
+                            error!(
+                                "❌ [Processing Job #{}] Reconciliation failed: {}",
+                                sync_count,
+                                run.error.unwrap_or_default()
+                            );
+                        }
+                        Ok(run) => {
                             info!(
-                                "✅ [Processing Job #{}] Blockchain sync completed",
-                                sync_count
+                                "✅ [Processing Job #{}] Reconciled {} markets: {} drifted, {} fields healed, {} unreadable",
+                                sync_count,
+                                run.markets_checked,
+                                run.markets_drifted,
+                                run.fields_healed,
+                                run.markets_unreadable
                             );
                         }
                         Err(e) => {
@@ -108,19 +173,25 @@ impl Scheduler {
                         }
                     }
 
-                    let bet_service = BetService::new(db.clone());
-                    match bet_service.sync_bets_from_indexer().await {
-                        Ok(count) => {
-                            if count > 0 {
+                    let projection_service = ProjectionService::new(db.clone());
+                    match projection_service.process_pending(&config).await {
+                        Ok(summary) => {
+                            if summary.markets + summary.bets + summary.resolutions + summary.claims
+                                > 0
+                            {
                                 info!(
-                                    "✅ [Processing Job #{}] Synced {} bets from indexer and updated markets",
-                                    sync_count, count
+                                    "✅ [Processing Job #{}] Projected {} markets, {} bets, {} resolutions, {} claims",
+                                    sync_count,
+                                    summary.markets,
+                                    summary.bets,
+                                    summary.resolutions,
+                                    summary.claims
                                 );
                             }
                         }
                         Err(e) => {
                             error!(
-                                "❌ [Processing Job #{}] Failed to sync bets from indexer: {}",
+                                "❌ [Processing Job #{}] Failed to project indexed events: {}",
                                 sync_count, e
                             );
                         }
@@ -133,6 +204,302 @@ impl Scheduler {
                 "✅ Background processing job started (every {}s)",
                 interval_secs
             );
+
+            let lifecycle_interval_secs = self.config.market_lifecycle_interval_secs;
+            let scheduler = Arc::clone(&self);
+            tokio::spawn(async move {
+                let mut interval = time::interval(Duration::from_secs(lifecycle_interval_secs));
+
+                loop {
+                    interval.tick().await;
+
+                    let db = crate::db::Database::from_pool(scheduler.pool.clone());
+                    let market_service = MarketService::new(db);
+
+                    match market_service.advance_lifecycle().await {
+                        Ok((opened, closed)) => {
+                            if opened > 0 || closed > 0 {
+                                info!(
+                                    "✅ [Market Lifecycle] Opened {} scheduled markets, closed {} expired markets",
+                                    opened, closed
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            error!("❌ [Market Lifecycle] Failed to advance markets: {}", e);
+                        }
+                    }
+                }
+            });
+            info!(
+                "✅ Market lifecycle job started (every {}s)",
+                lifecycle_interval_secs
+            );
+
+            let auto_rebalance_interval_secs = self.config.auto_rebalance_refresh_interval_secs;
+            let scheduler = Arc::clone(&self);
+            tokio::spawn(async move {
+                let mut interval =
+                    time::interval(Duration::from_secs(auto_rebalance_interval_secs));
+
+                loop {
+                    interval.tick().await;
+
+                    let db = crate::db::Database::from_pool(scheduler.pool.clone());
+                    let auto_rebalance_service = AutoRebalanceService::new(db);
+
+                    if let Err(e) = auto_rebalance_service.refresh_active_users().await {
+                        error!(
+                            "❌ [Auto-Rebalance] Failed to refresh active_auto_rebalance_users: {}",
+                            e
+                        );
+                    }
+                }
+            });
+            info!(
+                "✅ Auto-rebalance refresh job started (every {}s)",
+                auto_rebalance_interval_secs
+            );
+
+            let rebalancer_interval_secs = self.config.rebalancer_interval_secs;
+            let scheduler = Arc::clone(&self);
+            tokio::spawn(async move {
+                let config = match crate::config::Config::from_env() {
+                    Ok(config) => config,
+                    Err(e) => {
+                        error!("❌ [Rebalancer] Failed to load configuration: {}", e);
+                        return;
+                    }
+                };
+                if !config.rebalancer_enabled {
+                    info!("⏸️ [Rebalancer] Disabled (set REBALANCER_ENABLED=true to enable)");
+                    return;
+                }
+                let mut interval = time::interval(Duration::from_secs(rebalancer_interval_secs));
+
+                loop {
+                    interval.tick().await;
+
+                    let db = crate::db::Database::from_pool(scheduler.pool.clone());
+                    let rebalancer_service = RebalancerService::new(db);
+
+                    if let Err(e) = rebalancer_service
+                        .run(&config, config.rebalancer_dry_run)
+                        .await
+                    {
+                        error!("❌ [Rebalancer] Failed to evaluate users: {}", e);
+                    }
+                }
+            });
+            info!(
+                "✅ Rebalancer worker started (every {}s)",
+                rebalancer_interval_secs
+            );
+
+            let watcher_interval_secs = self.config.transaction_watcher_interval_secs;
+            let scheduler = Arc::clone(&self);
+            tokio::spawn(async move {
+                let config = match crate::config::Config::from_env() {
+                    Ok(config) => config,
+                    Err(e) => {
+                        error!(
+                            "❌ [Transaction Watcher] Failed to load configuration: {}",
+                            e
+                        );
+                        return;
+                    }
+                };
+                let mut interval = time::interval(Duration::from_secs(watcher_interval_secs));
+
+                loop {
+                    interval.tick().await;
+
+                    let db = crate::db::Database::from_pool(scheduler.pool.clone());
+                    let transaction_service = TransactionService::new(db.clone());
+
+                    match transaction_service
+                        .watch_transactions(
+                            &config,
+                            scheduler.config.transaction_confirmations,
+                            scheduler.config.transaction_reorg_window,
+                        )
+                        .await
+                    {
+                        Ok(summary) => {
+                            if summary.confirmed > 0 || summary.failed > 0 || summary.reorged > 0 {
+                                info!(
+                                    "✅ [Transaction Watcher] {} confirmed, {} failed, {} reorged",
+                                    summary.confirmed, summary.failed, summary.reorged
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            error!(
+                                "❌ [Transaction Watcher] Failed to check transactions: {}",
+                                e
+                            );
+                        }
+                    }
+
+                    if let Ok(signer) = SignerService::shared() {
+                        match signer.bump_stuck_transactions(&db).await {
+                            Ok(bumped) if bumped > 0 => {
+                                info!(
+                                    "⛽ [Transaction Watcher] Replaced {} stuck transactions",
+                                    bumped
+                                );
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                error!(
+                                    "❌ [Transaction Watcher] Failed to bump stuck transactions: {}",
+                                    e
+                                );
+                            }
+                        }
+                    }
+                }
+            });
+            info!(
+                "✅ Transaction watcher started (every {}s)",
+                watcher_interval_secs
+            );
+
+            let outbox_interval_secs = self.config.outbox_interval_secs;
+            let scheduler = Arc::clone(&self);
+            tokio::spawn(async move {
+                let config = match crate::config::Config::from_env() {
+                    Ok(config) => config,
+                    Err(e) => {
+                        error!("❌ [Outbox] Failed to load configuration: {}", e);
+                        return;
+                    }
+                };
+                let mut interval = time::interval(Duration::from_secs(outbox_interval_secs));
+
+                loop {
+                    interval.tick().await;
+
+                    if SignerService::shared().is_err() {
+                        continue;
+                    }
+
+                    let db = crate::db::Database::from_pool(scheduler.pool.clone());
+                    let outbox_service = OutboxService::new(db);
+
+                    match outbox_service.process(&config).await {
+                        Ok(summary) => {
+                            if summary.submitted > 0
+                                || summary.completed > 0
+                                || summary.retried > 0
+                                || summary.failed > 0
+                            {
+                                info!(
+                                    "✅ [Outbox] {} submitted, {} completed, {} retried, {} failed",
+                                    summary.submitted,
+                                    summary.completed,
+                                    summary.retried,
+                                    summary.failed
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            error!("❌ [Outbox] Failed to process entries: {}", e);
+                        }
+                    }
+                }
+            });
+            info!("✅ Outbox worker started (every {}s)", outbox_interval_secs);
+
+            let indexer_interval_secs = self.config.indexer_interval_secs;
+            let scheduler = Arc::clone(&self);
+            tokio::spawn(async move {
+                let config = match crate::config::Config::from_env() {
+                    Ok(config) => config,
+                    Err(e) => {
+                        error!("❌ [Indexer] Failed to load configuration: {}", e);
+                        return;
+                    }
+                };
+                let mut interval = time::interval(Duration::from_secs(indexer_interval_secs));
+
+                loop {
+                    interval.tick().await;
+
+                    if indexer::is_streaming() {
+                        continue;
+                    }
+
+                    let db = crate::db::Database::from_pool(scheduler.pool.clone());
+                    let indexer_service = match IndexerService::new(db, &config) {
+                        Ok(service) => service,
+                        Err(e) => {
+                            error!("❌ [Indexer] Failed to initialize indexer: {}", e);
+                            continue;
+                        }
+                    };
+
+                    match indexer_service.run(10).await {
+                        Ok(summary) => {
+                            if summary.events > 0 {
+                                info!(
+                                    "✅ [Indexer] Indexed {} events across {} blocks",
+                                    summary.events, summary.blocks
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            error!("❌ [Indexer] Failed to index events: {}", e);
+                        }
+                    }
+                }
+            });
+            info!(
+                "✅ Event indexer started (every {}s)",
+                indexer_interval_secs
+            );
+
+            let scheduler = Arc::clone(&self);
+            tokio::spawn(async move {
+                let config = match crate::config::Config::from_env() {
+                    Ok(config) => config,
+                    Err(e) => {
+                        error!("❌ [Indexer] Failed to load configuration: {}", e);
+                        return;
+                    }
+                };
+                let Some(ws_url) = config.ws_rpc_url.clone() else {
+                    return;
+                };
+                let mut backoff_secs = 1;
+
+                loop {
+                    let started = time::Instant::now();
+                    let db = crate::db::Database::from_pool(scheduler.pool.clone());
+
+                    let result = match IndexerService::new(db, &config) {
+                        Ok(indexer_service) => {
+                            indexer_service
+                                .stream(&ws_url, Duration::from_secs(indexer_interval_secs))
+                                .await
+                        }
+                        Err(e) => Err(e),
+                    };
+
+                    if started.elapsed() > Duration::from_secs(60) {
+                        backoff_secs = 1;
+                    }
+                    if let Err(e) = result {
+                        warn!(
+                            "⚠️ [Indexer] Log subscription unavailable ({}), polling until reconnect in {}s",
+                            e, backoff_secs
+                        );
+                    }
+
+                    time::sleep(Duration::from_secs(backoff_secs)).await;
+                    backoff_secs = (backoff_secs * 2).min(60);
+                }
+            });
         } else {
             warn!("⚠️  Background processing is disabled");
         }
@@ -0,0 +1,425 @@
+use rand::{distributions::Alphanumeric, Rng};
+use tracing::warn;
+
+use crate::{
+    db::Database,
+    error::{AppError, Result},
+    models::Session,
+};
+
+const REFRESH_TOKEN_LENGTH: usize = 48;
+
+pub struct IssuedSession {
+    pub session_id: String,
+    pub refresh_token: String,
+}
+
+pub struct RotatedSession {
+    pub session_id: String,
+    pub user_id: String,
+    pub address: String,
+    pub refresh_token: String,
+}
+
+#[derive(Debug, PartialEq)]
+enum RefreshDecision {
+    Rotate,
+    Reused,
+    Expired,
+}
+
+struct StoredRefreshToken {
+    used_at: Option<chrono::NaiveDateTime>,
+    expires_at: chrono::NaiveDateTime,
+    session_revoked: bool,
+    session_expires_at: chrono::NaiveDateTime,
+}
+
+pub struct SessionService {
+    db: Database,
+}
+
+impl SessionService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn create_session(
+        &self,
+        user_id: &str,
+        device_label: Option<String>,
+        refresh_ttl_secs: u64,
+    ) -> Result<IssuedSession> {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let refresh_token = generate_refresh_token();
+
+        let mut tx = self.db.pool().begin().await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO auth_sessions (id, "userId", "deviceLabel", "expiresAt")
+            VALUES ($1, $2, $3, NOW() + make_interval(secs => $4))
+            "#,
+            session_id,
+            user_id,
+            device_label,
+            refresh_ttl_secs as f64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        insert_refresh_token(&mut tx, &session_id, &refresh_token, refresh_ttl_secs).await?;
+
+        tx.commit().await?;
+
+        Ok(IssuedSession {
+            session_id,
+            refresh_token,
+        })
+    }
+
+    pub async fn rotate_refresh_token(
+        &self,
+        refresh_token: &str,
+        refresh_ttl_secs: u64,
+    ) -> Result<RotatedSession> {
+        let token_hash = hash_refresh_token(refresh_token);
+
+        let existing = sqlx::query!(
+            r#"
+            SELECT
+                t.id,
+                t."sessionId" as session_id,
+                t."usedAt" as used_at,
+                t."expiresAt" as expires_at,
+                s.revoked,
+                s."expiresAt" as session_expires_at,
+                s."userId" as user_id,
+                u.address,
+                NOW()::timestamp as "now!"
+            FROM auth_refresh_tokens t
+            JOIN auth_sessions s ON t."sessionId" = s.id
+            JOIN users u ON s."userId" = u.id
+            WHERE t."tokenHash" = $1
+            "#,
+            token_hash
+        )
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("Invalid refresh token".to_string()))?;
+
+        let decision = refresh_decision(
+            &StoredRefreshToken {
+                used_at: existing.used_at,
+                expires_at: existing.expires_at,
+                session_revoked: existing.revoked,
+                session_expires_at: existing.session_expires_at,
+            },
+            existing.now,
+        );
+        match decision {
+            RefreshDecision::Rotate => {}
+            RefreshDecision::Reused => {
+                warn!(
+                    "⚠️ Refresh token reuse detected for session {}, revoking it",
+                    existing.session_id
+                );
+                self.mark_revoked(&existing.session_id, "refresh_token_reuse")
+                    .await?;
+                return Err(AppError::Unauthorized(
+                    "Refresh token has already been used; session revoked".to_string(),
+                ));
+            }
+            RefreshDecision::Expired => {
+                return Err(AppError::Unauthorized(
+                    "Session has been revoked or expired".to_string(),
+                ));
+            }
+        }
+
+        let mut tx = self.db.pool().begin().await?;
+
+        let claimed = sqlx::query!(
+            r#"
+            UPDATE auth_refresh_tokens
+            SET "usedAt" = NOW()
+            WHERE id = $1 AND "usedAt" IS NULL
+            RETURNING id
+            "#,
+            existing.id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if claimed.is_none() {
+            tx.rollback().await?;
+            warn!(
+                "⚠️ Concurrent refresh token reuse detected for session {}, revoking it",
+                existing.session_id
+            );
+            self.mark_revoked(&existing.session_id, "refresh_token_reuse")
+                .await?;
+            return Err(AppError::Unauthorized(
+                "Refresh token has already been used; session revoked".to_string(),
+            ));
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE auth_sessions
+            SET "lastUsedAt" = NOW(),
+                "expiresAt" = NOW() + make_interval(secs => $2)
+            WHERE id = $1
+            "#,
+            existing.session_id,
+            refresh_ttl_secs as f64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let new_refresh_token = generate_refresh_token();
+        insert_refresh_token(
+            &mut tx,
+            &existing.session_id,
+            &new_refresh_token,
+            refresh_ttl_secs,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(RotatedSession {
+            session_id: existing.session_id,
+            user_id: existing.user_id,
+            address: existing.address,
+            refresh_token: new_refresh_token,
+        })
+    }
+
+    pub async fn touch_session(&self, session_id: &str, user_id: &str) -> Result<bool> {
+        let active = sqlx::query!(
+            r#"
+            UPDATE auth_sessions
+            SET "lastUsedAt" = NOW()
+            WHERE id = $1 AND "userId" = $2 AND revoked = false AND "expiresAt" > NOW()
+            RETURNING id
+            "#,
+            session_id,
+            user_id
+        )
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        Ok(active.is_some())
+    }
+
+    pub async fn list_sessions(&self, user_id: &str) -> Result<Vec<Session>> {
+        let sessions = sqlx::query_as::<_, Session>(
+            r#"
+            SELECT id, "deviceLabel", revoked, "revokedAt", "expiresAt", "lastUsedAt", "createdAt"
+            FROM auth_sessions
+            WHERE "userId" = $1
+            ORDER BY "lastUsedAt" DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(sessions)
+    }
+
+    pub async fn revoke_session(
+        &self,
+        user_id: &str,
+        session_id: &str,
+        reason: &str,
+    ) -> Result<()> {
+        let revoked = sqlx::query!(
+            r#"
+            UPDATE auth_sessions
+            SET revoked = true,
+                "revokedAt" = COALESCE("revokedAt", NOW()),
+                "revokedReason" = COALESCE("revokedReason", $3)
+            WHERE id = $1 AND "userId" = $2
+            RETURNING id
+            "#,
+            session_id,
+            user_id,
+            reason
+        )
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        if revoked.is_none() {
+            return Err(AppError::NotFound(format!(
+                "Session {} not found",
+                session_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn mark_revoked(&self, session_id: &str, reason: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE auth_sessions
+            SET revoked = true,
+                "revokedAt" = COALESCE("revokedAt", NOW()),
+                "revokedReason" = COALESCE("revokedReason", $2)
+            WHERE id = $1
+            "#,
+            session_id,
+            reason
+        )
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+}
+
+async fn insert_refresh_token(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    session_id: &str,
+    refresh_token: &str,
+    refresh_ttl_secs: u64,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO auth_refresh_tokens (id, "sessionId", "tokenHash", "expiresAt")
+        VALUES ($1, $2, $3, NOW() + make_interval(secs => $4))
+        "#,
+        uuid::Uuid::new_v4().to_string(),
+        session_id,
+        hash_refresh_token(refresh_token),
+        refresh_ttl_secs as f64
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+fn refresh_decision(token: &StoredRefreshToken, now: chrono::NaiveDateTime) -> RefreshDecision {
+    if token.used_at.is_some() {
+        return RefreshDecision::Reused;
+    }
+    if token.session_revoked || token.session_expires_at <= now || token.expires_at <= now {
+        return RefreshDecision::Expired;
+    }
+    RefreshDecision::Rotate
+}
+
+fn generate_refresh_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(REFRESH_TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+fn hash_refresh_token(refresh_token: &str) -> String {
+    hex::encode(ethers::utils::keccak256(refresh_token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored_token(now: chrono::NaiveDateTime) -> StoredRefreshToken {
+        StoredRefreshToken {
+            used_at: None,
+            expires_at: now + chrono::Duration::hours(1),
+            session_revoked: false,
+            session_expires_at: now + chrono::Duration::hours(1),
+        }
+    }
+
+    #[test]
+    fn test_refresh_tokens_are_stored_as_keccak_hashes() {
+        let token = generate_refresh_token();
+        assert_eq!(token.len(), REFRESH_TOKEN_LENGTH);
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_ne!(token, generate_refresh_token());
+
+        let hash = hash_refresh_token(&token);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_refresh_token(&token));
+        assert_ne!(hash, hash_refresh_token(&generate_refresh_token()));
+        assert_eq!(
+            hash_refresh_token(""),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+
+    #[test]
+    fn test_refresh_decision() {
+        let now = chrono::Utc::now().naive_utc();
+        assert_eq!(
+            refresh_decision(&stored_token(now), now),
+            RefreshDecision::Rotate
+        );
+
+        let mut expired = stored_token(now);
+        expired.expires_at = now;
+        assert_eq!(refresh_decision(&expired, now), RefreshDecision::Expired);
+
+        let mut session_expired = stored_token(now);
+        session_expired.session_expires_at = now - chrono::Duration::seconds(1);
+        assert_eq!(
+            refresh_decision(&session_expired, now),
+            RefreshDecision::Expired
+        );
+
+        let mut revoked = stored_token(now);
+        revoked.session_revoked = true;
+        assert_eq!(refresh_decision(&revoked, now), RefreshDecision::Expired);
+
+        let mut reused = stored_token(now);
+        reused.used_at = Some(now);
+        reused.expires_at = now;
+        assert_eq!(refresh_decision(&reused, now), RefreshDecision::Reused);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_refresh_token_reuse_revokes_the_session(pool: sqlx::PgPool) {
+        sqlx::query("INSERT INTO users (id, address) VALUES ('user-1', $1)")
+            .bind("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let sessions = SessionService::new(Database::from_pool(pool.clone()));
+
+        let issued = sessions.create_session("user-1", None, 3600).await.unwrap();
+        let rotated = sessions
+            .rotate_refresh_token(&issued.refresh_token, 3600)
+            .await
+            .unwrap();
+        assert_eq!(rotated.session_id, issued.session_id);
+        assert_ne!(rotated.refresh_token, issued.refresh_token);
+
+        assert!(sessions
+            .rotate_refresh_token(&issued.refresh_token, 3600)
+            .await
+            .is_err());
+        assert!(sessions
+            .rotate_refresh_token(&rotated.refresh_token, 3600)
+            .await
+            .is_err());
+
+        let session = sqlx::query!(
+            r#"SELECT revoked, "revokedReason" as revoked_reason FROM auth_sessions WHERE id = $1"#,
+            issued.session_id
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert!(session.revoked);
+        assert_eq!(
+            session.revoked_reason.as_deref(),
+            Some("refresh_token_reuse")
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use ethers::{prelude::*, types::transaction::eip2718::TypedTransaction};
+use once_cell::sync::OnceCell;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+use crate::{
+    config::Config,
+    db::Database,
+    error::{AppError, Result},
+    services::{
+        rpc::{self, RpcProvider},
+        transaction::{record_transaction, SubmittedTransaction},
+    },
+};
+
+static SIGNER: OnceCell<Arc<SignerService>> = OnceCell::new();
+
+pub type SignerClient = SignerMiddleware<RpcProvider, LocalWallet>;
+
+pub struct SignerService {
+    client: Arc<SignerClient>,
+    next_nonce: Mutex<Option<U256>>,
+    stuck_after_secs: u64,
+    gas_bump_percent: u64,
+    max_gas_bumps: i64,
+}
+
+impl SignerService {
+    pub fn init(config: &Config) -> Result<()> {
+        let Some(private_key) = config.private_key.as_deref() else {
+            warn!("⚠️ PRIVATE_KEY not set, on-chain writes are disabled");
+            return Ok(());
+        };
+
+        let signer = Self::new(config, private_key)?;
+        info!("🔑 Server signer {:?} ready", signer.address());
+
+        SIGNER
+            .set(Arc::new(signer))
+            .map_err(|_| AppError::Internal("Signer already initialized".to_string()))
+    }
+
+    pub fn new(config: &Config, private_key: &str) -> Result<Self> {
+        let provider = rpc::provider(config)?;
+
+        let wallet: LocalWallet = private_key
+            .parse::<LocalWallet>()
+            .map_err(|e| AppError::Internal(format!("Invalid private key: {}", e)))?
+            .with_chain_id(config.base_chain_id);
+
+        Ok(Self {
+            client: Arc::new(SignerMiddleware::new(provider, wallet)),
+            next_nonce: Mutex::new(None),
+            stuck_after_secs: config.tx_stuck_after_secs,
+            gas_bump_percent: config.tx_gas_bump_percent,
+            max_gas_bumps: config.tx_max_gas_bumps as i64,
+        })
+    }
+
+    pub fn shared() -> Result<Arc<SignerService>> {
+        SIGNER
+            .get()
+            .cloned()
+            .ok_or_else(|| AppError::Internal("PRIVATE_KEY is not configured".to_string()))
+    }
+
+    pub fn address(&self) -> Address {
+        self.client.address()
+    }
+
+    pub fn client(&self) -> Arc<SignerClient> {
+        self.client.clone()
+    }
+
+    async fn reserve_nonce(&self, next_nonce: &mut Option<U256>) -> Result<U256> {
+        if let Some(nonce) = *next_nonce {
+            return Ok(nonce);
+        }
+
+        let nonce = self
+            .client
+            .get_transaction_count(self.address(), Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch nonce: {}", e)))?;
+        *next_nonce = Some(nonce);
+        Ok(nonce)
+    }
+
+    pub async fn send(&self, mut tx: TypedTransaction) -> Result<SubmittedTransaction> {
+        let mut next_nonce = self.next_nonce.lock().await;
+
+        for attempt in 0..2 {
+            let nonce = self.reserve_nonce(&mut next_nonce).await?;
+
+            tx.set_from(self.address());
+            tx.set_nonce(nonce);
+            self.client
+                .fill_transaction(&mut tx, None)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to prepare transaction: {}", e)))?;
+
+            match self.client.send_transaction(tx.clone(), None).await {
+                Ok(pending) => {
+                    *next_nonce = Some(nonce + 1);
+                    info!("📤 Sent {:?} with nonce {}", pending.tx_hash(), nonce);
+                    return Ok(SubmittedTransaction::from_request(
+                        pending.tx_hash(),
+                        self.address(),
+                        &tx,
+                    ));
+                }
+                Err(e) if is_already_known(&e.to_string()) => {
+                    let signature =
+                        self.client
+                            .signer()
+                            .sign_transaction(&tx)
+                            .await
+                            .map_err(|e| {
+                                AppError::Internal(format!("Failed to sign transaction: {}", e))
+                            })?;
+                    let hash = tx.hash(&signature);
+                    *next_nonce = Some(nonce + 1);
+                    info!(
+                        "📤 {:?} with nonce {} was already known to the node",
+                        hash, nonce
+                    );
+                    return Ok(SubmittedTransaction::from_request(
+                        hash,
+                        self.address(),
+                        &tx,
+                    ));
+                }
+                Err(e) => {
+                    *next_nonce = None;
+                    if attempt == 0 && is_nonce_error(&e.to_string()) {
+                        warn!("⚠️ Nonce {} rejected ({}), resyncing from chain", nonce, e);
+                        continue;
+                    }
+                    return Err(AppError::Internal(format!(
+                        "Failed to send transaction: {}",
+                        e
+                    )));
+                }
+            }
+        }
+
+        Err(AppError::Internal(
+            "Failed to send transaction after resyncing nonce".to_string(),
+        ))
+    }
+
+    pub async fn sign(&self, mut tx: TypedTransaction) -> Result<(SubmittedTransaction, Bytes)> {
+        let mut next_nonce = self.next_nonce.lock().await;
+        let nonce = self.reserve_nonce(&mut next_nonce).await?;
+
+        tx.set_from(self.address());
+        tx.set_nonce(nonce);
+        self.client
+            .fill_transaction(&mut tx, None)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to prepare transaction: {}", e)))?;
+
+        let signature = self
+            .client
+            .signer()
+            .sign_transaction(&tx)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to sign transaction: {}", e)))?;
+        *next_nonce = Some(nonce + 1);
+
+        Ok((
+            SubmittedTransaction::from_request(tx.hash(&signature), self.address(), &tx),
+            tx.rlp_signed(&signature),
+        ))
+    }
+
+    pub async fn broadcast(&self, signed_transaction: Bytes) -> Result<()> {
+        match self
+            .client
+            .provider()
+            .send_raw_transaction(signed_transaction)
+            .await
+        {
+            Ok(pending) => {
+                info!("📤 Broadcast {:?}", pending.tx_hash());
+                Ok(())
+            }
+            Err(e) if is_already_known(&e.to_string()) => Ok(()),
+            Err(e) => {
+                *self.next_nonce.lock().await = None;
+                Err(AppError::Internal(format!(
+                    "Failed to broadcast transaction: {}",
+                    e
+                )))
+            }
+        }
+    }
+
+    pub async fn bump_stuck_transactions(&self, db: &Database) -> Result<usize> {
+        let stuck = sqlx::query!(
+            r#"
+            SELECT t.hash, t.kind, t."marketId" as market_id, t."betId" as bet_id, t.request,
+                   (SELECT COUNT(*) FROM transactions r
+                    WHERE r."fromAddress" = t."fromAddress" AND r.nonce = t.nonce
+                      AND r.status = 'replaced') as "bumps!"
+            FROM transactions t
+            WHERE t.status = 'pending'
+              AND t."blockHash" IS NULL
+              AND t.request IS NOT NULL
+              AND t."fromAddress" = $1
+              AND t."createdAt" < NOW() - make_interval(secs => $2)
+            ORDER BY t.nonce
+            "#,
+            ethers::utils::to_checksum(&self.address(), None),
+            self.stuck_after_secs as f64
+        )
+        .fetch_all(db.pool())
+        .await?;
+
+        let mut bumped = 0;
+
+        for transaction in stuck {
+            if transaction.bumps >= self.max_gas_bumps {
+                continue;
+            }
+
+            let hash: H256 = transaction
+                .hash
+                .parse()
+                .map_err(|_| AppError::Internal(format!("Invalid hash {}", transaction.hash)))?;
+            let mined = self
+                .client
+                .get_transaction_receipt(hash)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to fetch receipt: {}", e)))?;
+            if mined.is_some() {
+                continue;
+            }
+
+            let Some(mut request) = transaction
+                .request
+                .as_deref()
+                .and_then(|request| serde_json::from_str::<TypedTransaction>(request).ok())
+            else {
+                continue;
+            };
+            bump_fees(&mut request, self.gas_bump_percent);
+
+            let replacement = {
+                let _guard = self.next_nonce.lock().await;
+                self.client.send_transaction(request.clone(), None).await
+            };
+
+            let replacement = match replacement {
+                Ok(pending) => {
+                    SubmittedTransaction::from_request(pending.tx_hash(), self.address(), &request)
+                }
+                Err(e) => {
+                    error!("❌ Failed to replace stuck transaction {}: {}", hash, e);
+                    continue;
+                }
+            };
+
+            let mut tx = db.pool().begin().await?;
+
+            record_transaction(
+                &mut tx,
+                &transaction.kind,
+                &replacement,
+                transaction.market_id.as_deref(),
+                transaction.bet_id.as_deref(),
+            )
+            .await?;
+
+            sqlx::query!(
+                r#"UPDATE transactions SET status = 'replaced', "replacedBy" = $2 WHERE hash = $1"#,
+                transaction.hash,
+                format!("{:?}", replacement.hash)
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+
+            warn!(
+                "⛽ Replaced stuck transaction {} with {:?} (bump {}/{})",
+                transaction.hash,
+                replacement.hash,
+                transaction.bumps + 1,
+                self.max_gas_bumps
+            );
+            bumped += 1;
+        }
+
+        Ok(bumped)
+    }
+}
+
+pub(crate) fn is_nonce_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("nonce too low")
+        || message.contains("nonce too high")
+        || message.contains("replacement transaction underpriced")
+}
+
+pub(crate) fn is_already_known(message: &str) -> bool {
+    message.to_lowercase().contains("already known")
+}
+
+fn bump(value: U256, percent: u64) -> U256 {
+    value * U256::from(100 + percent) / U256::from(100) + U256::one()
+}
+
+pub(crate) fn bump_fees(tx: &mut TypedTransaction, percent: u64) {
+    match tx {
+        TypedTransaction::Eip1559(request) => {
+            request.max_fee_per_gas = request.max_fee_per_gas.map(|fee| bump(fee, percent));
+            request.max_priority_fee_per_gas = request
+                .max_priority_fee_per_gas
+                .map(|fee| bump(fee, percent));
+        }
+        _ => {
+            if let Some(gas_price) = tx.gas_price() {
+                tx.set_gas_price(bump(gas_price, percent));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_fees_raises_legacy_gas_price() {
+        let mut tx: TypedTransaction = TransactionRequest::new().gas_price(100).nonce(7).into();
+        bump_fees(&mut tx, 20);
+
+        assert_eq!(tx.gas_price(), Some(U256::from(121)));
+        assert_eq!(tx.nonce(), Some(&U256::from(7)));
+    }
+
+    #[test]
+    fn test_bump_fees_raises_eip1559_fees() {
+        let mut tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .max_fee_per_gas(1_000)
+            .max_priority_fee_per_gas(10)
+            .into();
+        bump_fees(&mut tx, 10);
+
+        let TypedTransaction::Eip1559(request) = tx else {
+            panic!("expected an EIP-1559 request");
+        };
+        assert_eq!(request.max_fee_per_gas, Some(U256::from(1_101)));
+        assert_eq!(request.max_priority_fee_per_gas, Some(U256::from(12)));
+    }
+
+    #[test]
+    fn test_nonce_errors_are_detected() {
+        assert!(is_nonce_error("(code: -32000, message: nonce too low)"));
+        assert!(is_nonce_error("Replacement transaction underpriced"));
+        assert!(!is_nonce_error("execution reverted"));
+        assert!(!is_nonce_error("(code: -32000, message: already known)"));
+    }
+
+    #[test]
+    fn test_already_known_is_not_retried_with_a_new_nonce() {
+        assert!(is_already_known("(code: -32000, message: already known)"));
+        assert!(is_already_known("ALREADY KNOWN"));
+        assert!(!is_already_known("nonce too low"));
+    }
+}
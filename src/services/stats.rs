@@ -63,7 +63,7 @@ impl StatsService {
         let yes_volume: bigdecimal::BigDecimal = stats.try_get("yes_volume")?;
         let no_volume: bigdecimal::BigDecimal = stats.try_get("no_volume")?;
 
-        let yes_percentage = if total_volume > bigdecimal::BigDecimal::from(0) {
+        let yes_percentage = if total_volume > 0 {
             (&yes_volume / &total_volume * bigdecimal::BigDecimal::from(100))
                 .to_string()
                 .parse::<f64>()
@@ -72,7 +72,7 @@ impl StatsService {
             0.0
         };
 
-        let no_percentage = if total_volume > bigdecimal::BigDecimal::from(0) {
+        let no_percentage = if total_volume > 0 {
             (&no_volume / &total_volume * bigdecimal::BigDecimal::from(100))
                 .to_string()
                 .parse::<f64>()
@@ -1,8 +1,12 @@
 use crate::{
+    config::Config,
     db::Database,
     error::{AppError, Result},
-    models::SyncStatusResponse,
-    services::{BetService, MarketService, ProtocolService},
+    models::{ReconciliationRun, SyncStatusResponse},
+    services::{
+        blockchain_yield::IWhizyPredictionMarket, rpc, BetService, BlockchainSyncService,
+        IndexerService, MarketService, MulticallReader, ProjectionService, ProtocolService,
+    },
 };
 use ethers::prelude::*;
 use sqlx::Row;
@@ -12,11 +16,20 @@ use tracing::{error, info, warn};
 abigen!(
     WhizyPredictionMarket,
     r#"[
-        function markets(uint256) external view returns (uint256 id, string question, string description, uint256 bettingDeadline, uint256 resolutionDeadline, address tokenAddress, uint256 minBet, uint256 maxBet, bool resolved, bool outcome, uint256 totalYesAmount, uint256 totalNoAmount, uint256 totalYieldEarned, address yieldProtocolAddr, address protocolSelectorAddr, uint8 status)
+        function markets(uint256) external view returns (uint256 id, string question, uint256 endTime, address token, address vault, uint256 totalYesShares, uint256 totalNoShares, bool resolved, bool outcome, uint8 status)
         function nextMarketId() external view returns (uint256)
     ]"#,
 );
 
+#[derive(Debug, Default)]
+pub struct IncrementalSyncSummary {
+    pub blocks: u64,
+    pub events: usize,
+    pub reorgs: usize,
+    pub bets: usize,
+    pub resolutions: usize,
+}
+
 pub struct SyncService {
     db: Database,
     market_service: MarketService,
@@ -39,7 +52,11 @@ impl SyncService {
     }
 
     pub async fn get_sync_status(&self) -> Result<SyncStatusResponse> {
-        let last_synced_block = 0;
+        let last_synced_block: i64 =
+            sqlx::query("SELECT COALESCE(MIN(last_block), 0)::bigint as block FROM sync_states")
+                .fetch_one(self.db.pool())
+                .await?
+                .try_get("block")?;
 
         let markets_synced: i64 = sqlx::query("SELECT COUNT(*) as count FROM markets_extended")
             .fetch_one(self.db.pool())
@@ -66,7 +83,7 @@ impl SyncService {
         })
     }
 
-    pub async fn full_sync(&self) -> Result<()> {
+    pub async fn full_sync(&self, config: &Config) -> Result<()> {
         info!("Starting full sync from indexer to backend database");
 
         self.sync_protocols().await?;
@@ -75,7 +92,9 @@ impl SyncService {
 
         self.sync_bets().await?;
 
-        self.sync_market_resolutions().await?;
+        ProjectionService::new(self.db.clone())
+            .process_pending(config)
+            .await?;
 
         info!("Full sync completed successfully");
         Ok(())
@@ -169,37 +188,123 @@ impl SyncService {
         Ok(())
     }
 
-    async fn sync_market_resolutions(&self) -> Result<()> {
-        info!("Syncing market resolutions - no indexer DB available");
-        Ok(())
-    }
+    pub async fn incremental_sync(&self, config: &Config) -> Result<IncrementalSyncSummary> {
+        info!("Starting incremental sync from the last indexed checkpoint");
 
-    pub async fn incremental_sync(&self) -> Result<()> {
-        info!("Starting incremental sync - no indexer DB available");
-        Ok(())
-    }
+        let indexer_service = IndexerService::new(self.db.clone(), config)?;
+        let indexed = indexer_service.run(usize::MAX).await?;
 
-    pub async fn sync_market_by_id(&self, _blockchain_market_id: &str) -> Result<()> {
-        info!("Sync specific market - no indexer DB available");
-        Ok(())
+        let projected = ProjectionService::new(self.db.clone())
+            .process_pending(config)
+            .await?;
+        let (bets, resolutions) = (projected.bets, projected.resolutions);
+
+        info!(
+            "Incremental sync completed: {} events over {} blocks, {} bets, {} resolutions",
+            indexed.events, indexed.blocks, bets, resolutions
+        );
+
+        Ok(IncrementalSyncSummary {
+            blocks: indexed.blocks,
+            events: indexed.events,
+            reorgs: indexed.reorgs,
+            bets,
+            resolutions,
+        })
     }
 
-    pub async fn sync_from_blockchain(
+    pub async fn sync_market_by_id(
         &self,
-        contract_address: &str,
-        rpc_url: &str,
-    ) -> Result<usize> {
+        config: &Config,
+        market_id: &str,
+    ) -> Result<(String, ReconciliationRun)> {
+        let market = sqlx::query!(
+            r#"
+            SELECT id, "blockchainMarketId" as blockchain_market_id
+            FROM markets_extended
+            WHERE id = $1 OR "blockchainMarketId" = $2
+            LIMIT 1
+            "#,
+            market_id,
+            market_id.parse::<i64>().ok()
+        )
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Market {} not found", market_id)))?;
+
+        let blockchain_market_id = market.blockchain_market_id.ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Market {} has not been created on-chain yet",
+                market.id
+            ))
+        })?;
+
+        let provider = rpc::provider(config)?;
+        let address: Address = config
+            .whizy_prediction_market_addr
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
+        let contract = IWhizyPredictionMarket::new(address, Arc::new(provider));
+
+        let (on_chain, _total_assets, current_yield, yield_withdrawn) = contract
+            .get_market_info(U256::from(blockchain_market_id))
+            .call()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch market info: {}", e)))?;
+
+        if on_chain.0 != U256::from(blockchain_market_id) {
+            return Err(AppError::NotFound(format!(
+                "Market {} not found on blockchain",
+                blockchain_market_id
+            )));
+        }
+
+        let end_date = chrono::DateTime::from_timestamp(on_chain.2.low_u64() as i64, 0)
+            .map(|date| date.naive_utc());
+
+        sqlx::query(
+            r#"
+            UPDATE markets_extended
+            SET "endDate" = COALESCE($2, "endDate"),
+                "vaultAddress" = $3,
+                "currentYield" = $4::numeric,
+                "yieldWithdrawn" = $5::numeric,
+                "updatedAt" = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(&market.id)
+        .bind(end_date)
+        .bind(ethers::utils::to_checksum(&on_chain.4, None))
+        .bind(current_yield.to_string())
+        .bind(yield_withdrawn.to_string())
+        .execute(self.db.pool())
+        .await?;
+
+        let reconciliation = BlockchainSyncService::new(self.db.clone())
+            .reconcile_market(config, &market.id)
+            .await?;
+
+        info!(
+            "🔄 Refreshed market {} from blockchain market {} (reconciliation run {})",
+            market.id, blockchain_market_id, reconciliation.id
+        );
+
+        Ok((market.id, reconciliation))
+    }
+
+    pub async fn sync_from_blockchain(&self, config: &Config) -> Result<usize> {
         info!("🔄 Starting sync from blockchain to database...");
 
-        let provider = Provider::<Http>::try_from(rpc_url)
-            .map_err(|e| AppError::Internal(format!("Failed to connect to RPC: {}", e)))?;
+        let provider = rpc::provider(config)?;
         let client = Arc::new(provider);
 
-        let address: Address = contract_address
+        let address: Address = config
+            .whizy_prediction_market_addr
             .parse()
             .map_err(|e| AppError::Internal(format!("Invalid contract address: {}", e)))?;
 
-        let contract = WhizyPredictionMarket::new(address, client);
+        let contract = WhizyPredictionMarket::new(address, client.clone());
 
         let next_market_id = contract
             .next_market_id()
@@ -218,11 +323,19 @@ impl SyncService {
             return Ok(0);
         }
 
+        let blockchain_markets = MulticallReader::new(client, config)?
+            .read(
+                (0..total_blockchain_markets)
+                    .map(|blockchain_id| contract.markets(U256::from(blockchain_id)))
+                    .collect(),
+            )
+            .await;
+
         let mut synced_count = 0;
 
-        for blockchain_id in 0..total_blockchain_markets {
-            match contract.markets(U256::from(blockchain_id)).call().await {
-                Ok(blockchain_market) => {
+        for (blockchain_id, blockchain_market) in blockchain_markets.into_iter().enumerate() {
+            match blockchain_market {
+                Some(blockchain_market) => {
                     let blockchain_question = blockchain_market.1.trim();
 
                     info!(
@@ -370,11 +483,8 @@ impl SyncService {
                         }
                     }
                 }
-                Err(e) => {
-                    error!(
-                        "❌ Failed to read blockchain market {}: {}",
-                        blockchain_id, e
-                    );
+                None => {
+                    error!("❌ Failed to read blockchain market {}", blockchain_id);
                 }
             }
         }
@@ -0,0 +1,648 @@
+use bigdecimal::BigDecimal;
+use ethers::{prelude::*, types::transaction::eip2718::TypedTransaction};
+use tracing::{error, info, warn};
+
+use crate::{
+    config::Config,
+    db::Database,
+    error::{AppError, Result},
+    models::ChainTransaction,
+    services::{
+        projection,
+        rpc::{self, RpcProvider},
+    },
+};
+
+const DROPPED_AFTER_SECS: i64 = 600;
+
+const TRANSACTION_COLUMNS: &str = r#"hash, kind, "fromAddress", "toAddress", "marketId", "betId", nonce, "gasLimit", "gasPrice", "gasUsed", status, confirmations, "blockNumber", "blockHash", error, "replacedBy", "confirmedAt", "createdAt", "updatedAt""#;
+
+#[derive(Debug, Clone)]
+pub struct SubmittedTransaction {
+    pub hash: H256,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub nonce: Option<U256>,
+    pub gas_limit: Option<U256>,
+    pub gas_price: Option<U256>,
+    pub request: TypedTransaction,
+}
+
+impl SubmittedTransaction {
+    pub fn from_request(hash: H256, from: Address, tx: &TypedTransaction) -> Self {
+        Self {
+            hash,
+            from,
+            to: tx.to().and_then(|to| to.as_address().copied()),
+            nonce: tx.nonce().copied(),
+            gas_limit: tx.gas().copied(),
+            gas_price: tx.gas_price(),
+            request: tx.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Observation {
+    Unmined { age_secs: i64 },
+    Reverted,
+    Mined { block_number: u64 },
+}
+
+#[derive(Debug, PartialEq)]
+enum Transition {
+    Wait,
+    CheckDropped,
+    Reorged,
+    Failed,
+    Pending {
+        confirmations: u64,
+    },
+    Confirmed {
+        confirmations: u64,
+        newly_confirmed: bool,
+    },
+}
+
+#[derive(Debug, Default)]
+pub struct WatchSummary {
+    pub confirmed: usize,
+    pub failed: usize,
+    pub reorged: usize,
+}
+
+pub struct TransactionService {
+    db: Database,
+}
+
+impl TransactionService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn get_transaction(&self, hash: &str) -> Result<ChainTransaction> {
+        sqlx::query_as::<_, ChainTransaction>(&format!(
+            "SELECT {} FROM transactions WHERE LOWER(hash) = LOWER($1)",
+            TRANSACTION_COLUMNS
+        ))
+        .bind(hash)
+        .fetch_optional(self.db.pool())
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Transaction {} not found", hash)))
+    }
+
+    pub async fn watch_transactions(
+        &self,
+        config: &Config,
+        required_confirmations: u64,
+        reorg_window: u64,
+    ) -> Result<WatchSummary> {
+        let watched = sqlx::query_as::<_, ChainTransaction>(&format!(
+            r#"
+            SELECT {}
+            FROM transactions
+            WHERE status IN ('pending', 'reorged')
+               OR (status = 'confirmed' AND confirmations < $1)
+            ORDER BY "createdAt"
+            "#,
+            TRANSACTION_COLUMNS
+        ))
+        .bind(reorg_window.max(required_confirmations) as i64)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut summary = WatchSummary::default();
+        if watched.is_empty() {
+            return Ok(summary);
+        }
+
+        let provider = rpc::provider(config)?;
+        let head = provider
+            .get_block_number()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch block number: {}", e)))?
+            .as_u64();
+
+        for transaction in watched {
+            match self
+                .advance(&provider, &transaction, head, required_confirmations)
+                .await
+            {
+                Ok(Some(status)) => match status {
+                    "confirmed" => summary.confirmed += 1,
+                    "failed" => summary.failed += 1,
+                    "reorged" => summary.reorged += 1,
+                    _ => {}
+                },
+                Ok(None) => {}
+                Err(e) => warn!("⚠️ Could not check transaction {}: {}", transaction.hash, e),
+            }
+        }
+
+        Ok(summary)
+    }
+
+    async fn advance(
+        &self,
+        provider: &RpcProvider,
+        transaction: &ChainTransaction,
+        head: u64,
+        required_confirmations: u64,
+    ) -> Result<Option<&'static str>> {
+        let hash: H256 = transaction
+            .hash
+            .parse()
+            .map_err(|_| AppError::Internal(format!("Invalid hash {}", transaction.hash)))?;
+
+        let receipt = provider
+            .get_transaction_receipt(hash)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to fetch receipt: {}", e)))?;
+
+        if receipt.is_none() && self.promote_mined_sibling(provider, transaction).await? {
+            return Ok(None);
+        }
+
+        let observed = match &receipt {
+            None => Observation::Unmined {
+                age_secs: (chrono::Utc::now().naive_utc() - transaction.created_at).num_seconds(),
+            },
+            Some(receipt) if receipt.status != Some(U64::from(1)) => Observation::Reverted,
+            Some(receipt) => Observation::Mined {
+                block_number: receipt.block_number.unwrap_or_default().as_u64(),
+            },
+        };
+
+        let (confirmations, confirmed, newly_confirmed) = match next_status(
+            &transaction.status,
+            transaction.block_hash.is_some(),
+            observed,
+            head,
+            required_confirmations,
+        ) {
+            Transition::Wait => return Ok(None),
+            Transition::CheckDropped => {
+                let known = provider.get_transaction(hash).await.map_err(|e| {
+                    AppError::Internal(format!("Failed to fetch transaction: {}", e))
+                })?;
+                if known.is_some() {
+                    return Ok(None);
+                }
+                self.mark_failed(
+                    transaction,
+                    "Transaction was dropped from the mempool",
+                    None,
+                )
+                .await?;
+                return Ok(Some("failed"));
+            }
+            Transition::Reorged => {
+                warn!(
+                    "🔀 Transaction {} left block {:?}; marking reorged",
+                    transaction.hash, transaction.block_number
+                );
+                self.mark_reorged(transaction).await?;
+                return Ok(Some("reorged"));
+            }
+            Transition::Failed => {
+                self.mark_failed(transaction, "Transaction reverted", receipt.as_ref())
+                    .await?;
+                return Ok(Some("failed"));
+            }
+            Transition::Pending { confirmations } => (confirmations, false, false),
+            Transition::Confirmed {
+                confirmations,
+                newly_confirmed,
+            } => (confirmations, true, newly_confirmed),
+        };
+
+        let Some(receipt) = receipt else {
+            return Ok(None);
+        };
+        let block_number = receipt.block_number.unwrap_or_default().as_u64();
+        let block_hash = receipt.block_hash.map(|hash| format!("{:?}", hash));
+
+        if transaction.block_hash.is_some() && transaction.block_hash != block_hash {
+            warn!(
+                "🔀 Transaction {} moved from block {:?} to {}",
+                transaction.hash, transaction.block_number, block_number
+            );
+        }
+
+        let mut tx = self.db.pool().begin().await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET status = $2, confirmations = $3, "blockNumber" = $4, "blockHash" = $5,
+                "gasUsed" = $6, error = NULL,
+                "confirmedAt" = CASE WHEN $2 = 'confirmed' THEN COALESCE("confirmedAt", NOW()) END
+            WHERE hash = $1
+            "#,
+            transaction.hash,
+            if confirmed { "confirmed" } else { "pending" },
+            confirmations as i64,
+            block_number as i64,
+            block_hash,
+            receipt.gas_used.map(to_numeric)
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if newly_confirmed {
+            if let Some(bet_id) = transaction.bet_id.as_deref() {
+                activate_bet(&mut tx, bet_id).await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        if newly_confirmed {
+            info!(
+                "✅ Transaction {} ({}) confirmed in block {}",
+                transaction.hash, transaction.kind, block_number
+            );
+            return Ok(Some("confirmed"));
+        }
+
+        Ok(None)
+    }
+
+    async fn promote_mined_sibling(
+        &self,
+        provider: &RpcProvider,
+        transaction: &ChainTransaction,
+    ) -> Result<bool> {
+        let Some(nonce) = transaction.nonce else {
+            return Ok(false);
+        };
+
+        let siblings = sqlx::query!(
+            r#"
+            SELECT hash FROM transactions
+            WHERE "fromAddress" = $1 AND nonce = $2 AND hash <> $3 AND status = 'replaced'
+            "#,
+            transaction.from_address,
+            nonce,
+            transaction.hash
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        for sibling in siblings {
+            let Ok(hash) = sibling.hash.parse::<H256>() else {
+                continue;
+            };
+            let receipt = provider
+                .get_transaction_receipt(hash)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to fetch receipt: {}", e)))?;
+            if receipt.is_none() {
+                continue;
+            }
+
+            let mut tx = self.db.pool().begin().await?;
+
+            sqlx::query!(
+                r#"UPDATE transactions SET status = 'pending', "replacedBy" = NULL WHERE hash = $1"#,
+                sibling.hash
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query!(
+                r#"UPDATE transactions SET status = 'replaced', "replacedBy" = $2 WHERE hash = $1"#,
+                transaction.hash,
+                sibling.hash
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+
+            info!(
+                "🔁 Transaction {} was mined instead of its replacement {}",
+                sibling.hash, transaction.hash
+            );
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    async fn mark_reorged(&self, transaction: &ChainTransaction) -> Result<()> {
+        let mut tx = self.db.pool().begin().await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET status = 'reorged', confirmations = 0, "confirmedAt" = NULL
+            WHERE hash = $1
+            "#,
+            transaction.hash
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if let Some(bet_id) = transaction.bet_id.as_deref() {
+            settle_bet(&mut tx, bet_id, "reorged").await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        transaction: &ChainTransaction,
+        reason: &str,
+        receipt: Option<&TransactionReceipt>,
+    ) -> Result<()> {
+        error!("❌ Transaction {} failed: {}", transaction.hash, reason);
+
+        let mut tx = self.db.pool().begin().await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET status = 'failed', error = $2, confirmations = 0, "confirmedAt" = NULL,
+                "blockNumber" = COALESCE($3, "blockNumber"),
+                "gasUsed" = COALESCE($4, "gasUsed")
+            WHERE hash = $1
+            "#,
+            transaction.hash,
+            reason,
+            receipt
+                .and_then(|receipt| receipt.block_number)
+                .map(|block| block.as_u64() as i64),
+            receipt.and_then(|receipt| receipt.gas_used).map(to_numeric)
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if let Some(bet_id) = transaction.bet_id.as_deref() {
+            settle_bet(&mut tx, bet_id, "failed").await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+pub(crate) async fn record_transaction(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    kind: &str,
+    submitted: &SubmittedTransaction,
+    market_id: Option<&str>,
+    bet_id: Option<&str>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO transactions (
+            hash, kind, "fromAddress", "toAddress", "marketId", "betId", nonce, "gasLimit",
+            "gasPrice", request
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (hash) DO UPDATE
+        SET status = 'pending', error = NULL, confirmations = 0, "blockNumber" = NULL,
+            "blockHash" = NULL, "confirmedAt" = NULL
+        WHERE transactions.status = 'failed'
+        "#,
+        format!("{:?}", submitted.hash),
+        kind,
+        ethers::utils::to_checksum(&submitted.from, None),
+        submitted.to.map(|to| ethers::utils::to_checksum(&to, None)),
+        market_id,
+        bet_id,
+        submitted.nonce.map(|nonce| nonce.as_u64() as i64),
+        submitted.gas_limit.map(to_numeric),
+        submitted.gas_price.map(to_numeric),
+        serde_json::to_string(&submitted.request).ok()
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+fn next_status(
+    status: &str,
+    included: bool,
+    observed: Observation,
+    head: u64,
+    required_confirmations: u64,
+) -> Transition {
+    match observed {
+        Observation::Unmined { .. } if included && status != "reorged" => Transition::Reorged,
+        Observation::Unmined { age_secs } if age_secs > DROPPED_AFTER_SECS => {
+            Transition::CheckDropped
+        }
+        Observation::Unmined { .. } => Transition::Wait,
+        Observation::Reverted => Transition::Failed,
+        Observation::Mined { block_number } => {
+            let confirmations = head.saturating_sub(block_number) + 1;
+            if confirmations >= required_confirmations {
+                Transition::Confirmed {
+                    confirmations,
+                    newly_confirmed: status != "confirmed",
+                }
+            } else {
+                Transition::Pending { confirmations }
+            }
+        }
+    }
+}
+
+fn to_numeric(value: U256) -> BigDecimal {
+    value.to_string().parse().unwrap_or_default()
+}
+
+async fn activate_bet(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, bet_id: &str) -> Result<()> {
+    let activated = sqlx::query!(
+        r#"
+        UPDATE bets_extended
+        SET status = 'active'
+        WHERE id = $1 AND status IN ('pending', 'reorged')
+        RETURNING "marketId" as market_id
+        "#,
+        bet_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    if let Some(market_id) = activated.and_then(|bet| bet.market_id) {
+        projection::refresh_market(tx, &market_id).await?;
+    }
+
+    Ok(())
+}
+
+async fn settle_bet(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    bet_id: &str,
+    status: &str,
+) -> Result<()> {
+    let previous = sqlx::query!(
+        r#"
+        UPDATE bets_extended b
+        SET status = $2
+        FROM (SELECT id, status FROM bets_extended WHERE id = $1 FOR UPDATE) prev
+        WHERE b.id = prev.id AND prev.status IN ('pending', 'active', 'reorged')
+        RETURNING prev.status as previous_status, b."marketId" as market_id
+        "#,
+        bet_id,
+        status
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    if let Some(market_id) = previous
+        .filter(|bet| projection::counts_towards_pools(&bet.previous_status))
+        .and_then(|bet| bet.market_id)
+    {
+        projection::refresh_market(tx, &market_id).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mined_transaction_confirms_at_required_depth() {
+        let mined = Observation::Mined { block_number: 100 };
+
+        assert_eq!(
+            next_status("pending", false, mined, 101, 3),
+            Transition::Pending { confirmations: 2 }
+        );
+        assert_eq!(
+            next_status("pending", true, mined, 102, 3),
+            Transition::Confirmed {
+                confirmations: 3,
+                newly_confirmed: true
+            }
+        );
+        assert_eq!(
+            next_status("confirmed", true, mined, 110, 3),
+            Transition::Confirmed {
+                confirmations: 11,
+                newly_confirmed: false
+            }
+        );
+        assert_eq!(
+            next_status("reorged", false, mined, 99, 2),
+            Transition::Pending { confirmations: 1 }
+        );
+    }
+
+    #[test]
+    fn test_reverted_transaction_fails() {
+        assert_eq!(
+            next_status("pending", false, Observation::Reverted, 100, 1),
+            Transition::Failed
+        );
+        assert_eq!(
+            next_status("confirmed", true, Observation::Reverted, 100, 1),
+            Transition::Failed
+        );
+    }
+
+    #[test]
+    fn test_unmined_transaction_is_reorged_or_checked_for_drop() {
+        let fresh = Observation::Unmined { age_secs: 30 };
+        let old = Observation::Unmined {
+            age_secs: DROPPED_AFTER_SECS + 1,
+        };
+
+        assert_eq!(
+            next_status("confirmed", true, fresh, 100, 1),
+            Transition::Reorged
+        );
+        assert_eq!(
+            next_status("pending", true, old, 100, 1),
+            Transition::Reorged
+        );
+        assert_eq!(
+            next_status("reorged", true, fresh, 100, 1),
+            Transition::Wait
+        );
+        assert_eq!(
+            next_status("pending", false, fresh, 100, 1),
+            Transition::Wait
+        );
+        assert_eq!(
+            next_status("pending", false, old, 100, 1),
+            Transition::CheckDropped
+        );
+        assert_eq!(
+            next_status("reorged", true, old, 100, 1),
+            Transition::CheckDropped
+        );
+    }
+
+    async fn seed_bets(pool: &sqlx::PgPool) {
+        sqlx::query("INSERT INTO users (id, address) VALUES ('user-1', $1)")
+            .bind("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf")
+            .execute(pool)
+            .await
+            .unwrap();
+        for query in [
+            r#"INSERT INTO markets_extended (id, "blockchainMarketId", question, "endDate")
+               VALUES ('market-1', 7, 'Will it rain?', NOW() + INTERVAL '1 day')"#,
+            r#"INSERT INTO bets_extended (id, "userId", "marketId", position, amount, odds, status)
+               VALUES ('active-bet', 'user-1', 'market-1', true, 30, 1, 'active'),
+                      ('pending-bet', 'user-1', 'market-1', true, 20, 1, 'pending'),
+                      ('lost-bet', 'user-1', 'market-1', false, 10, 1, 'lost')"#,
+        ] {
+            sqlx::query(query).execute(pool).await.unwrap();
+        }
+        let mut tx = pool.begin().await.unwrap();
+        projection::refresh_market(&mut tx, "market-1")
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+    }
+
+    async fn yes_pool(pool: &sqlx::PgPool) -> BigDecimal {
+        sqlx::query_scalar!(r#"SELECT "yesPoolSize" FROM markets_extended WHERE id = 'market-1'"#)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn bet_status(pool: &sqlx::PgPool, bet_id: &str) -> String {
+        sqlx::query_scalar!("SELECT status FROM bets_extended WHERE id = $1", bet_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_settling_bets_moves_pools_only_for_counted_bets(pool: sqlx::PgPool) {
+        seed_bets(&pool).await;
+        assert_eq!(yes_pool(&pool).await, BigDecimal::from(30));
+
+        let mut tx = pool.begin().await.unwrap();
+        settle_bet(&mut tx, "pending-bet", "failed").await.unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(bet_status(&pool, "pending-bet").await, "failed");
+        assert_eq!(yes_pool(&pool).await, BigDecimal::from(30));
+
+        let mut tx = pool.begin().await.unwrap();
+        settle_bet(&mut tx, "active-bet", "reorged").await.unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(bet_status(&pool, "active-bet").await, "reorged");
+        assert_eq!(yes_pool(&pool).await, BigDecimal::from(0));
+
+        let mut tx = pool.begin().await.unwrap();
+        activate_bet(&mut tx, "active-bet").await.unwrap();
+        settle_bet(&mut tx, "lost-bet", "failed").await.unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(bet_status(&pool, "active-bet").await, "active");
+        assert_eq!(bet_status(&pool, "lost-bet").await, "lost");
+        assert_eq!(yes_pool(&pool).await, BigDecimal::from(30));
+    }
+}
@@ -2,6 +2,7 @@ use crate::{
     db::Database,
     error::{AppError, Result},
     models::*,
+    utils::Role,
 };
 use sqlx::Row;
 
@@ -182,4 +183,56 @@ impl UserService {
             total_bets,
         })
     }
+
+    pub async fn get_user_roles(&self, user_id: &str) -> Result<Vec<Role>> {
+        let rows = sqlx::query!(
+            r#"SELECT role FROM user_roles WHERE "userId" = $1 ORDER BY role"#,
+            user_id
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        rows.iter().map(|row| row.role.parse::<Role>()).collect()
+    }
+
+    pub async fn grant_role(
+        &self,
+        user_id: &str,
+        role: Role,
+        granted_by: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_roles ("userId", role, "grantedBy")
+            VALUES ($1, $2, $3)
+            ON CONFLICT ("userId", role) DO NOTHING
+            "#,
+            user_id,
+            role.as_str(),
+            granted_by
+        )
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn revoke_role(&self, user_id: &str, role: Role) -> Result<()> {
+        let removed = sqlx::query!(
+            r#"DELETE FROM user_roles WHERE "userId" = $1 AND role = $2 RETURNING role"#,
+            user_id,
+            role.as_str()
+        )
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        if removed.is_none() {
+            return Err(AppError::NotFound(format!(
+                "User {} does not have role {}",
+                user_id, role
+            )));
+        }
+
+        Ok(())
+    }
 }
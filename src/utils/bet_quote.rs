@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+pub const BET_QUOTE_TOKEN_TYPE: &str = "bet_quote";
+pub const DEFAULT_SLIPPAGE_BPS: u32 = 100;
+pub const MAX_SLIPPAGE_BPS: u32 = 5_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BetQuote {
+    pub odds: f64,
+    pub implied_probability: f64,
+    pub shares: u64,
+    pub potential_payout: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BetQuoteClaims {
+    pub typ: String,
+    pub qid: String,
+    pub market_id: String,
+    pub position: bool,
+    pub amount: u64,
+    pub odds: f64,
+    pub min_odds: f64,
+    pub exp: i64,
+    pub iat: i64,
+}
+
+impl BetQuoteClaims {
+    pub fn ensure_matches(
+        &self,
+        market_id: &str,
+        position: bool,
+        amount: u64,
+    ) -> Result<(), AppError> {
+        if self.typ != BET_QUOTE_TOKEN_TYPE {
+            return Err(AppError::BadRequest("Invalid bet quote".to_string()));
+        }
+        if self.market_id != market_id || self.position != position || self.amount != amount {
+            return Err(AppError::BadRequest(
+                "Bet does not match the quoted market, position and amount".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn ensure_within_slippage(&self, odds: f64) -> Result<(), AppError> {
+        if odds < self.min_odds {
+            return Err(AppError::BadRequest(format!(
+                "Odds moved to {:.4}, below the quoted minimum of {:.4}; request a new quote",
+                odds, self.min_odds
+            )));
+        }
+        Ok(())
+    }
+}
+
+pub fn quote_bet(
+    yes_pool: u64,
+    no_pool: u64,
+    position: bool,
+    amount: u64,
+    yield_pool: u64,
+    vault_assets: u64,
+    vault_shares: u64,
+) -> BetQuote {
+    let (side_pool, opposing_pool) = if position {
+        (yes_pool, no_pool)
+    } else {
+        (no_pool, yes_pool)
+    };
+
+    let side_after = side_pool as u128 + amount as u128;
+    let total_after = side_after + opposing_pool as u128;
+
+    let odds = if side_after > 0 {
+        (total_after as f64 / side_after as f64).max(1.0)
+    } else {
+        1.0
+    };
+
+    let implied_probability = if total_after > 0 {
+        side_after as f64 / total_after as f64
+    } else {
+        0.0
+    };
+
+    let winnings = ((opposing_pool as u128 + yield_pool as u128) * amount as u128)
+        .checked_div(side_after)
+        .unwrap_or(0);
+
+    BetQuote {
+        odds,
+        implied_probability,
+        shares: vault_shares_for(amount, vault_assets, vault_shares),
+        potential_payout: (amount as u128 + winnings).min(u64::MAX as u128) as u64,
+    }
+}
+
+pub fn vault_shares_for(amount: u64, vault_assets: u64, vault_shares: u64) -> u64 {
+    if vault_assets == 0 || vault_shares == 0 {
+        return amount;
+    }
+
+    (amount as u128 * vault_shares as u128 / vault_assets as u128).min(u64::MAX as u128) as u64
+}
+
+pub fn project_yield(current_yield: u64, elapsed_secs: i64, remaining_secs: i64) -> u64 {
+    if elapsed_secs <= 0 || remaining_secs <= 0 {
+        return current_yield;
+    }
+
+    let projected = current_yield as u128
+        + current_yield as u128 * remaining_secs as u128 / elapsed_secs as u128;
+    projected.min(u64::MAX as u128) as u64
+}
+
+pub fn min_odds(odds: f64, slippage_bps: u32) -> f64 {
+    (odds * (1.0 - slippage_bps as f64 / 10_000.0)).max(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_matches_pool_odds() {
+        let quote = quote_bet(3_000_000, 1_000_000, false, 1_000_000, 0, 0, 0);
+
+        assert_eq!(quote.odds, 2.5);
+        assert_eq!(quote.implied_probability, 0.4);
+        assert_eq!(quote.shares, 1_000_000);
+        assert_eq!(quote.potential_payout, 2_500_000);
+    }
+
+    #[test]
+    fn test_quote_includes_yield_in_payout() {
+        let quote = quote_bet(1_000_000, 1_000_000, true, 1_000_000, 200_000, 0, 0);
+
+        assert_eq!(quote.potential_payout, 1_600_000);
+    }
+
+    #[test]
+    fn test_quote_on_empty_market() {
+        let quote = quote_bet(0, 0, true, 1_000_000, 0, 0, 0);
+
+        assert_eq!(quote.odds, 1.0);
+        assert_eq!(quote.implied_probability, 1.0);
+        assert_eq!(quote.potential_payout, 1_000_000);
+    }
+
+    #[test]
+    fn test_quote_converts_amount_at_vault_share_price() {
+        let quote = quote_bet(
+            3_000_000, 1_000_000, true, 1_000_000, 400_000, 4_400_000, 4_000_000,
+        );
+
+        assert_eq!(quote.shares, 909_090);
+        assert_eq!(vault_shares_for(1_000_000, 0, 0), 1_000_000);
+        assert_eq!(vault_shares_for(1_000_000, 4_000_000, 0), 1_000_000);
+    }
+
+    #[test]
+    fn test_project_yield_extrapolates_linearly() {
+        assert_eq!(project_yield(100, 10, 30), 400);
+        assert_eq!(project_yield(100, 0, 30), 100);
+        assert_eq!(project_yield(100, 10, -5), 100);
+    }
+
+    #[test]
+    fn test_slippage_bounds() {
+        assert!((min_odds(2.0, 100) - 1.98).abs() < 1e-9);
+        assert_eq!(min_odds(1.01, 500), 1.0);
+
+        let claims = BetQuoteClaims {
+            typ: BET_QUOTE_TOKEN_TYPE.to_string(),
+            qid: "q".to_string(),
+            market_id: "m".to_string(),
+            position: true,
+            amount: 1_000_000,
+            odds: 2.0,
+            min_odds: 1.98,
+            exp: 0,
+            iat: 0,
+        };
+        assert!(claims.ensure_within_slippage(1.99).is_ok());
+        assert!(claims.ensure_within_slippage(1.97).is_err());
+        assert!(claims.ensure_matches("m", true, 1_000_000).is_ok());
+        assert!(claims.ensure_matches("m", false, 1_000_000).is_err());
+    }
+}
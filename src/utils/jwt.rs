@@ -1,62 +1,556 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
-use serde::{Deserialize, Serialize};
+use jsonwebtoken::{
+    decode, decode_header, encode,
+    jwk::{
+        AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+        OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+    },
+    Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+use once_cell::sync::OnceCell;
+use ring::{
+    rand::SystemRandom,
+    signature::{Ed25519KeyPair, KeyPair, RsaKeyPair, RsaPublicKeyComponents},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use simple_asn1::{oid, ASN1Block};
+use std::{collections::HashMap, path::Path, sync::Arc};
+use tracing::{info, warn};
 
-use crate::error::{AppError, Result};
+use crate::{
+    error::{AppError, Result},
+    utils::rbac::Role,
+};
+
+static KEY_SET: OnceCell<Arc<KeySet>> = OnceCell::new();
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: String,
     pub address: String,
+    pub jti: String,
+    #[serde(default)]
+    pub roles: Vec<Role>,
     pub exp: i64,
     pub iat: i64,
 }
 
-pub struct JwtService {
-    encoding_key: EncodingKey,
+struct SigningKey {
+    algorithm: Algorithm,
+    encoding_key: Option<EncodingKey>,
     decoding_key: DecodingKey,
+    jwk: Jwk,
 }
 
-impl JwtService {
-    pub fn new() -> Self {
-        let secret = std::env::var("JWT_SECRET")
-            .unwrap_or_else(|_| "dev-jwt-secret-change-this-in-production".to_string());
+pub struct KeySet {
+    active_kid: String,
+    keys: HashMap<String, SigningKey>,
+}
+
+impl SigningKey {
+    fn from_pem(kid: &str, pem_bytes: &[u8]) -> Result<Self> {
+        let parsed = pem::parse(pem_bytes)
+            .map_err(|e| AppError::Internal(format!("Invalid PEM for JWT key {}: {}", kid, e)))?;
+
+        match parsed.tag() {
+            "PRIVATE KEY" => {
+                if Ed25519KeyPair::from_pkcs8_maybe_unchecked(parsed.contents()).is_ok() {
+                    Self::from_ed25519_pkcs8(kid, parsed.contents())
+                } else {
+                    let key_pair = RsaKeyPair::from_pkcs8(parsed.contents()).map_err(|e| {
+                        AppError::Internal(format!("Unsupported JWT key {}: {}", kid, e))
+                    })?;
+                    Self::from_rsa(kid, &key_pair, pem_bytes)
+                }
+            }
+            "RSA PRIVATE KEY" => {
+                let key_pair = RsaKeyPair::from_der(parsed.contents()).map_err(|e| {
+                    AppError::Internal(format!("Invalid RSA JWT key {}: {}", kid, e))
+                })?;
+                Self::from_rsa(kid, &key_pair, pem_bytes)
+            }
+            "PUBLIC KEY" => Self::from_public_key_der(kid, parsed.contents()),
+            "RSA PUBLIC KEY" => Self::from_rsa_public_key_der(kid, parsed.contents()),
+            tag => Err(AppError::Internal(format!(
+                "JWT key {} must be an Ed25519 or RSA private or public key, got {}",
+                kid, tag
+            ))),
+        }
+    }
+
+    fn from_public_key_der(kid: &str, der: &[u8]) -> Result<Self> {
+        let invalid = || AppError::Internal(format!("Invalid public JWT key {}", kid));
+
+        let blocks = simple_asn1::from_der(der).map_err(|_| invalid())?;
+        let Some(ASN1Block::Sequence(_, info)) = blocks.first() else {
+            return Err(invalid());
+        };
+        let (Some(ASN1Block::Sequence(_, algorithm)), Some(ASN1Block::BitString(_, _, key))) =
+            (info.first(), info.get(1))
+        else {
+            return Err(invalid());
+        };
+        let Some(ASN1Block::ObjectIdentifier(_, algorithm)) = algorithm.first() else {
+            return Err(invalid());
+        };
+
+        if *algorithm == oid!(1, 3, 101, 112) {
+            Self::from_ed25519_public(kid, key, None)
+        } else if *algorithm == oid!(1, 2, 840, 113549, 1, 1, 1) {
+            Self::from_rsa_public_key_der(kid, key)
+        } else {
+            Err(AppError::Internal(format!(
+                "JWT key {} must be an Ed25519 or RSA public key",
+                kid
+            )))
+        }
+    }
+
+    fn from_rsa_public_key_der(kid: &str, der: &[u8]) -> Result<Self> {
+        let invalid = || AppError::Internal(format!("Invalid RSA public JWT key {}", kid));
+
+        let blocks = simple_asn1::from_der(der).map_err(|_| invalid())?;
+        let Some(ASN1Block::Sequence(_, components)) = blocks.first() else {
+            return Err(invalid());
+        };
+        let (Some(ASN1Block::Integer(_, n)), Some(ASN1Block::Integer(_, e))) =
+            (components.first(), components.get(1))
+        else {
+            return Err(invalid());
+        };
+
+        Self::from_rsa_components(kid, &n.to_bytes_be().1, &e.to_bytes_be().1, None)
+    }
+
+    fn from_ed25519_pkcs8(kid: &str, der: &[u8]) -> Result<Self> {
+        let key_pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(der)
+            .map_err(|e| AppError::Internal(format!("Invalid Ed25519 JWT key {}: {}", kid, e)))?;
+
+        Self::from_ed25519_public(
+            kid,
+            key_pair.public_key().as_ref(),
+            Some(EncodingKey::from_ed_der(der)),
+        )
+    }
+
+    fn from_ed25519_public(
+        kid: &str,
+        public_key: &[u8],
+        encoding_key: Option<EncodingKey>,
+    ) -> Result<Self> {
+        let x = URL_SAFE_NO_PAD.encode(public_key);
+
+        Ok(Self {
+            algorithm: Algorithm::EdDSA,
+            encoding_key,
+            decoding_key: DecodingKey::from_ed_components(&x).map_err(|e| {
+                AppError::Internal(format!("Invalid Ed25519 JWT key {}: {}", kid, e))
+            })?,
+            jwk: Jwk {
+                common: public_jwk_parameters(kid, KeyAlgorithm::EdDSA),
+                algorithm: AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x,
+                }),
+            },
+        })
+    }
+
+    fn from_rsa(kid: &str, key_pair: &RsaKeyPair, pem_bytes: &[u8]) -> Result<Self> {
+        let components = RsaPublicKeyComponents::<Vec<u8>>::from(key_pair.public());
+        let encoding_key = EncodingKey::from_rsa_pem(pem_bytes)
+            .map_err(|e| AppError::Internal(format!("Invalid RSA JWT key {}: {}", kid, e)))?;
+
+        Self::from_rsa_components(kid, &components.n, &components.e, Some(encoding_key))
+    }
+
+    fn from_rsa_components(
+        kid: &str,
+        n: &[u8],
+        e: &[u8],
+        encoding_key: Option<EncodingKey>,
+    ) -> Result<Self> {
+        let n = URL_SAFE_NO_PAD.encode(n);
+        let e = URL_SAFE_NO_PAD.encode(e);
+
+        Ok(Self {
+            algorithm: Algorithm::RS256,
+            encoding_key,
+            decoding_key: DecodingKey::from_rsa_components(&n, &e)
+                .map_err(|e| AppError::Internal(format!("Invalid RSA JWT key {}: {}", kid, e)))?,
+            jwk: Jwk {
+                common: public_jwk_parameters(kid, KeyAlgorithm::RS256),
+                algorithm: AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n,
+                    e,
+                }),
+            },
+        })
+    }
+}
+
+fn public_jwk_parameters(kid: &str, algorithm: KeyAlgorithm) -> CommonParameters {
+    CommonParameters {
+        public_key_use: Some(PublicKeyUse::Signature),
+        key_algorithm: Some(algorithm),
+        key_id: Some(kid.to_string()),
+        ..Default::default()
+    }
+}
+
+impl KeySet {
+    pub fn from_dir(dir: &str, active_kid: Option<&str>) -> Result<Self> {
+        let mut keys = HashMap::new();
+
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| AppError::Internal(format!("Cannot read JWT_KEYS_DIR {}: {}", dir, e)))?;
+
+        for entry in entries {
+            let path = entry
+                .map_err(|e| {
+                    AppError::Internal(format!("Cannot read JWT_KEYS_DIR {}: {}", dir, e))
+                })?
+                .path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("pem") {
+                continue;
+            }
 
-        Self {
-            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
-            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            let kid = key_id_from_path(&path)?;
+            let pem_bytes = std::fs::read(&path).map_err(|e| {
+                AppError::Internal(format!("Cannot read JWT key {}: {}", path.display(), e))
+            })?;
+            keys.insert(kid.clone(), SigningKey::from_pem(&kid, &pem_bytes)?);
         }
+
+        let private_kids: Vec<&String> = keys
+            .iter()
+            .filter(|(_, key)| key.encoding_key.is_some())
+            .map(|(kid, _)| kid)
+            .collect();
+
+        let active_kid = match active_kid {
+            Some(kid) => kid.to_string(),
+            None if private_kids.len() == 1 => private_kids[0].clone(),
+            None => {
+                return Err(AppError::Internal(format!(
+                    "JWT_ACTIVE_KID must be set when JWT_KEYS_DIR holds {} private keys",
+                    private_kids.len()
+                )))
+            }
+        };
+
+        match keys.get(&active_kid) {
+            None => {
+                return Err(AppError::Internal(format!(
+                    "Active JWT key {} not found in {}",
+                    active_kid, dir
+                )))
+            }
+            Some(key) if key.encoding_key.is_none() => {
+                return Err(AppError::Internal(format!(
+                    "Active JWT key {} is a public key and cannot sign tokens",
+                    active_kid
+                )))
+            }
+            Some(_) => {}
+        }
+
+        Ok(Self { active_kid, keys })
+    }
+
+    pub fn ephemeral() -> Result<Self> {
+        let document = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|e| AppError::Internal(format!("Failed to generate JWT key: {}", e)))?;
+        let kid = format!("ephemeral-{}", Utc::now().timestamp());
+        let key = SigningKey::from_ed25519_pkcs8(&kid, document.as_ref())?;
+
+        Ok(Self {
+            active_kid: kid.clone(),
+            keys: HashMap::from([(kid, key)]),
+        })
+    }
+
+    pub fn jwks(&self) -> JwkSet {
+        let mut keys: Vec<Jwk> = self.keys.values().map(|key| key.jwk.clone()).collect();
+        keys.sort_by(|a, b| a.common.key_id.cmp(&b.common.key_id));
+        JwkSet { keys }
     }
+}
+
+fn key_id_from_path(path: &Path) -> Result<String> {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(|stem| stem.to_string())
+        .ok_or_else(|| AppError::Internal(format!("Invalid JWT key file name {}", path.display())))
+}
 
-    pub fn generate_token(&self, user_id: String, address: String) -> Result<String> {
+pub struct JwtService {
+    keys: Arc<KeySet>,
+}
+
+impl JwtService {
+    pub fn init(
+        keys_dir: Option<&str>,
+        active_kid: Option<&str>,
+        allow_ephemeral_key: bool,
+    ) -> Result<()> {
+        let key_set = match keys_dir {
+            Some(dir) => KeySet::from_dir(dir, active_kid)?,
+            None if allow_ephemeral_key => {
+                warn!("⚠️ JWT_KEYS_DIR not set, signing tokens with an ephemeral Ed25519 key");
+                KeySet::ephemeral()?
+            }
+            None => {
+                return Err(AppError::Internal(
+                    "JWT_KEYS_DIR must be set unless JWT_ALLOW_EPHEMERAL_KEY is enabled"
+                        .to_string(),
+                ))
+            }
+        };
+
+        info!(
+            "🔑 JWT signing key {} active, {} verification key(s) loaded",
+            key_set.active_kid,
+            key_set.keys.len()
+        );
+
+        KEY_SET
+            .set(Arc::new(key_set))
+            .map_err(|_| AppError::Internal("JWT keys already initialized".to_string()))
+    }
+
+    pub fn new() -> Result<Self> {
+        let keys = KEY_SET
+            .get()
+            .cloned()
+            .ok_or_else(|| AppError::Internal("JWT keys have not been initialized".to_string()))?;
+
+        Ok(Self { keys })
+    }
+
+    pub fn with_keys(keys: Arc<KeySet>) -> Self {
+        Self { keys }
+    }
+
+    pub fn generate_token(
+        &self,
+        user_id: String,
+        address: String,
+        session_id: String,
+        roles: Vec<Role>,
+        ttl_secs: u64,
+    ) -> Result<String> {
         let now = Utc::now();
-        let expiration = now + Duration::days(7);
+        let expiration = now + Duration::seconds(ttl_secs as i64);
 
         let claims = Claims {
             sub: user_id,
             address,
+            jti: session_id,
+            roles,
             exp: expiration.timestamp(),
             iat: now.timestamp(),
         };
 
-        encode(&Header::default(), &claims, &self.encoding_key)
-            .map_err(|e| AppError::Internal(format!("Failed to generate token: {}", e)))
+        self.sign(&claims)
     }
 
     pub fn verify_token(&self, token: &str) -> Result<Claims> {
-        let token_data = decode::<Claims>(
+        self.verify(token)
+    }
+
+    pub fn sign<T: Serialize>(&self, claims: &T) -> Result<String> {
+        let signing_key = self
+            .keys
+            .keys
+            .get(&self.keys.active_kid)
+            .ok_or_else(|| AppError::Internal("Active JWT key is missing".to_string()))?;
+        let encoding_key = signing_key
+            .encoding_key
+            .as_ref()
+            .ok_or_else(|| AppError::Internal("Active JWT key cannot sign tokens".to_string()))?;
+
+        let mut header = Header::new(signing_key.algorithm);
+        header.kid = Some(self.keys.active_kid.clone());
+
+        encode(&header, claims, encoding_key)
+            .map_err(|e| AppError::Internal(format!("Failed to generate token: {}", e)))
+    }
+
+    pub fn verify<T: DeserializeOwned>(&self, token: &str) -> Result<T> {
+        let header = decode_header(token)
+            .map_err(|e| AppError::Unauthorized(format!("Invalid token: {}", e)))?;
+
+        let kid = header
+            .kid
+            .ok_or_else(|| AppError::Unauthorized("Invalid token: missing kid".to_string()))?;
+
+        let verification_key =
+            self.keys.keys.get(&kid).ok_or_else(|| {
+                AppError::Unauthorized(format!("Invalid token: unknown kid {}", kid))
+            })?;
+
+        let token_data = decode::<T>(
             token,
-            &self.decoding_key,
-            &Validation::new(Algorithm::HS256),
+            &verification_key.decoding_key,
+            &Validation::new(verification_key.algorithm),
         )
         .map_err(|e| AppError::Unauthorized(format!("Invalid token: {}", e)))?;
 
         Ok(token_data.claims)
     }
+
+    pub fn jwks(&self) -> JwkSet {
+        self.keys.jwks()
+    }
 }
 
-impl Default for JwtService {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(service: &JwtService) -> String {
+        service
+            .generate_token(
+                "user-1".to_string(),
+                "0xabc".to_string(),
+                "session-1".to_string(),
+                vec![Role::Analyst],
+                60,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sign_and_verify_with_kid() {
+        let service = JwtService::with_keys(Arc::new(KeySet::ephemeral().unwrap()));
+        let token = issue(&service);
+
+        let header = decode_header(&token).unwrap();
+        assert_eq!(header.alg, Algorithm::EdDSA);
+        assert_eq!(
+            header.kid.as_deref(),
+            Some(service.keys.active_kid.as_str())
+        );
+
+        let claims = service.verify_token(&token).unwrap();
+        assert_eq!(claims.sub, "user-1");
+        assert_eq!(claims.roles, vec![Role::Analyst]);
+    }
+
+    fn signing_key(kid: &str) -> SigningKey {
+        let document = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        SigningKey::from_ed25519_pkcs8(kid, document.as_ref()).unwrap()
+    }
+
+    #[test]
+    fn test_rotation_keeps_old_tokens_valid() {
+        let old_key = signing_key("2026-07");
+        let old_service = JwtService::with_keys(Arc::new(KeySet {
+            active_kid: "2026-07".to_string(),
+            keys: HashMap::from([("2026-07".to_string(), old_key)]),
+        }));
+        let old_token = issue(&old_service);
+
+        let mut rotated = Arc::into_inner(old_service.keys).unwrap();
+        rotated
+            .keys
+            .insert("2026-10".to_string(), signing_key("2026-10"));
+        rotated.active_kid = "2026-10".to_string();
+        let rotated_service = JwtService::with_keys(Arc::new(rotated));
+
+        assert!(rotated_service.verify_token(&old_token).is_ok());
+        let new_token = issue(&rotated_service);
+        assert_eq!(
+            decode_header(&new_token).unwrap().kid.as_deref(),
+            Some("2026-10")
+        );
+
+        let kids: Vec<_> = rotated_service
+            .jwks()
+            .keys
+            .into_iter()
+            .filter_map(|jwk| jwk.common.key_id)
+            .collect();
+        assert_eq!(kids, vec!["2026-07", "2026-10"]);
+
+        let stranger = JwtService::with_keys(Arc::new(KeySet::ephemeral().unwrap()));
+        assert!(stranger.verify_token(&new_token).is_err());
+    }
+
+    #[test]
+    fn test_missing_keys_dir_needs_explicit_dev_flag() {
+        assert!(JwtService::init(None, None, false).is_err());
+        assert!(KEY_SET.get().is_none());
+    }
+
+    #[test]
+    fn test_public_keys_verify_but_never_sign() {
+        let dir = std::env::temp_dir().join(format!("jwt-keys-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let signer = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        std::fs::write(
+            dir.join("2026-10.pem"),
+            pem::encode(&pem::Pem::new("PRIVATE KEY", signer.as_ref())),
+        )
+        .unwrap();
+
+        let partner_document = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let partner_key =
+            Ed25519KeyPair::from_pkcs8_maybe_unchecked(partner_document.as_ref()).unwrap();
+        let mut spki = hex::decode("302a300506032b6570032100").unwrap();
+        spki.extend_from_slice(partner_key.public_key().as_ref());
+        std::fs::write(
+            dir.join("partner.pem"),
+            pem::encode(&pem::Pem::new("PUBLIC KEY", spki)),
+        )
+        .unwrap();
+
+        let key_set = KeySet::from_dir(dir.to_str().unwrap(), None).unwrap();
+        assert_eq!(key_set.active_kid, "2026-10");
+        assert!(KeySet::from_dir(dir.to_str().unwrap(), Some("partner")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let service = JwtService::with_keys(Arc::new(key_set));
+        let kids: Vec<_> = service
+            .jwks()
+            .keys
+            .into_iter()
+            .filter_map(|jwk| jwk.common.key_id)
+            .collect();
+        assert_eq!(kids, vec!["2026-10", "partner"]);
+
+        let partner_service = JwtService::with_keys(Arc::new(KeySet {
+            active_kid: "partner".to_string(),
+            keys: HashMap::from([(
+                "partner".to_string(),
+                SigningKey::from_ed25519_pkcs8("partner", partner_document.as_ref()).unwrap(),
+            )]),
+        }));
+        assert!(service.verify_token(&issue(&partner_service)).is_ok());
+    }
+
+    #[test]
+    fn test_jwks_exposes_public_key_only() {
+        let service = JwtService::with_keys(Arc::new(KeySet::ephemeral().unwrap()));
+        let jwks = serde_json::to_value(service.jwks()).unwrap();
+        let key = &jwks["keys"][0];
+
+        assert_eq!(key["kty"], "OKP");
+        assert_eq!(key["crv"], "Ed25519");
+        assert_eq!(key["alg"], "EdDSA");
+        assert_eq!(key["use"], "sig");
+        assert!(key.get("d").is_none());
+
+        let jwk: Jwk = serde_json::from_value(key.clone()).unwrap();
+        let token = issue(&service);
+        let decoding_key = DecodingKey::from_jwk(&jwk).unwrap();
+        assert!(
+            decode::<Claims>(&token, &decoding_key, &Validation::new(Algorithm::EdDSA)).is_ok()
+        );
     }
 }
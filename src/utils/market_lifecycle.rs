@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketState {
+    Draft,
+    Scheduled,
+    Active,
+    Closed,
+    PendingResolution,
+    Resolved,
+    Cancelled,
+}
+
+impl MarketState {
+    pub const ALL: [MarketState; 7] = [
+        MarketState::Draft,
+        MarketState::Scheduled,
+        MarketState::Active,
+        MarketState::Closed,
+        MarketState::PendingResolution,
+        MarketState::Resolved,
+        MarketState::Cancelled,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketState::Draft => "draft",
+            MarketState::Scheduled => "scheduled",
+            MarketState::Active => "active",
+            MarketState::Closed => "closed",
+            MarketState::PendingResolution => "pending_resolution",
+            MarketState::Resolved => "resolved",
+            MarketState::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn next_states(&self) -> &'static [MarketState] {
+        match self {
+            MarketState::Draft => &[
+                MarketState::Scheduled,
+                MarketState::Active,
+                MarketState::Cancelled,
+            ],
+            MarketState::Scheduled => &[
+                MarketState::Draft,
+                MarketState::Active,
+                MarketState::Cancelled,
+            ],
+            MarketState::Active => &[MarketState::Closed, MarketState::Cancelled],
+            MarketState::Closed => &[
+                MarketState::PendingResolution,
+                MarketState::Resolved,
+                MarketState::Cancelled,
+            ],
+            MarketState::PendingResolution => &[
+                MarketState::Closed,
+                MarketState::Resolved,
+                MarketState::Cancelled,
+            ],
+            MarketState::Resolved | MarketState::Cancelled => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: MarketState) -> bool {
+        self.next_states().contains(&next)
+    }
+
+    pub fn transition_to(&self, next: MarketState) -> Result<MarketState, AppError> {
+        if self.can_transition_to(next) {
+            Ok(next)
+        } else {
+            Err(AppError::BadRequest(format!(
+                "Market cannot move from {} to {}",
+                self, next
+            )))
+        }
+    }
+
+    pub fn accepts_bets(&self) -> bool {
+        matches!(self, MarketState::Active)
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.next_states().is_empty()
+    }
+}
+
+impl fmt::Display for MarketState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MarketState {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MarketState::ALL
+            .into_iter()
+            .find(|state| state.as_str() == s)
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown market status '{}'", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_transitions() {
+        assert!(MarketState::Draft.can_transition_to(MarketState::Scheduled));
+        assert!(MarketState::Scheduled.can_transition_to(MarketState::Active));
+        assert!(MarketState::Active.can_transition_to(MarketState::Closed));
+        assert!(MarketState::Closed.can_transition_to(MarketState::PendingResolution));
+        assert!(MarketState::PendingResolution.can_transition_to(MarketState::Resolved));
+        assert!(MarketState::PendingResolution.can_transition_to(MarketState::Closed));
+        assert!(MarketState::Active.can_transition_to(MarketState::Cancelled));
+    }
+
+    #[test]
+    fn test_invalid_transitions() {
+        assert!(MarketState::Active
+            .transition_to(MarketState::Resolved)
+            .is_err());
+        assert!(MarketState::Closed
+            .transition_to(MarketState::Active)
+            .is_err());
+        assert!(MarketState::Draft
+            .transition_to(MarketState::Closed)
+            .is_err());
+        for state in MarketState::ALL {
+            assert!(!MarketState::Resolved.can_transition_to(state));
+            assert!(!MarketState::Cancelled.can_transition_to(state));
+        }
+        assert!(MarketState::Resolved.is_terminal());
+        assert!(!MarketState::Closed.is_terminal());
+    }
+
+    #[test]
+    fn test_only_active_accepts_bets() {
+        for state in MarketState::ALL {
+            assert_eq!(state.accepts_bets(), state == MarketState::Active);
+        }
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        for state in MarketState::ALL {
+            assert_eq!(state.as_str().parse::<MarketState>().unwrap(), state);
+        }
+        assert!("open".parse::<MarketState>().is_err());
+    }
+}
@@ -1,3 +1,16 @@
+pub mod bet_quote;
 pub mod jwt;
+pub mod market_lifecycle;
+pub mod payout;
+pub mod rbac;
+pub mod siwe;
 
+pub use bet_quote::{
+    min_odds, project_yield, quote_bet, BetQuote, BetQuoteClaims, BET_QUOTE_TOKEN_TYPE,
+    DEFAULT_SLIPPAGE_BPS, MAX_SLIPPAGE_BPS,
+};
 pub use jwt::{Claims, JwtService};
+pub use market_lifecycle::MarketState;
+pub use payout::{settle_bets, BetSettlement, BetStake, SettledBet};
+pub use rbac::{ApiKeyScope, Permission, Role};
+pub use siwe::SiweMessage;
@@ -0,0 +1,166 @@
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+
+#[derive(Debug, Clone)]
+pub struct BetStake {
+    pub id: String,
+    pub position: bool,
+    pub amount: BigDecimal,
+    pub shares: Option<BigDecimal>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BetSettlement {
+    Won,
+    Lost,
+    Refunded,
+}
+
+impl BetSettlement {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BetSettlement::Won => "won",
+            BetSettlement::Lost => "lost",
+            BetSettlement::Refunded => "refunded",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettledBet {
+    pub id: String,
+    pub settlement: BetSettlement,
+    pub payout: BigDecimal,
+}
+
+pub fn settle_bets(bets: &[BetStake], outcome: bool, yield_pool: &BigDecimal) -> Vec<SettledBet> {
+    let winners: Vec<&BetStake> = bets.iter().filter(|bet| bet.position == outcome).collect();
+
+    if winners.is_empty() {
+        return bets
+            .iter()
+            .map(|bet| SettledBet {
+                id: bet.id.clone(),
+                settlement: BetSettlement::Refunded,
+                payout: bet.amount.clone(),
+            })
+            .collect();
+    }
+
+    let use_shares = winners.iter().all(|bet| {
+        bet.shares
+            .as_ref()
+            .is_some_and(|shares| shares > &BigDecimal::zero())
+    });
+    let weight = |bet: &BetStake| -> BigDecimal {
+        if use_shares {
+            bet.shares.clone().unwrap_or_default()
+        } else {
+            bet.amount.clone()
+        }
+    };
+
+    let total_weight: BigDecimal = winners.iter().map(|bet| weight(bet)).sum();
+    let losing_pool: BigDecimal = bets
+        .iter()
+        .filter(|bet| bet.position != outcome)
+        .map(|bet| bet.amount.clone())
+        .sum();
+    let distributable = losing_pool + yield_pool;
+
+    bets.iter()
+        .map(|bet| {
+            if bet.position != outcome {
+                return SettledBet {
+                    id: bet.id.clone(),
+                    settlement: BetSettlement::Lost,
+                    payout: BigDecimal::zero(),
+                };
+            }
+
+            let share = if total_weight.is_zero() {
+                BigDecimal::zero()
+            } else {
+                (&distributable * weight(bet) / &total_weight)
+                    .with_scale_round(0, RoundingMode::Down)
+            };
+
+            SettledBet {
+                id: bet.id.clone(),
+                settlement: BetSettlement::Won,
+                payout: &bet.amount + share,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stake(id: &str, position: bool, amount: i64, shares: Option<i64>) -> BetStake {
+        BetStake {
+            id: id.to_string(),
+            position,
+            amount: BigDecimal::from(amount),
+            shares: shares.map(BigDecimal::from),
+        }
+    }
+
+    #[test]
+    fn test_winners_split_losing_pool_and_yield() {
+        let bets = vec![
+            stake("a", true, 3_000_000, None),
+            stake("b", true, 1_000_000, None),
+            stake("c", false, 4_000_000, None),
+        ];
+        let settled = settle_bets(&bets, true, &BigDecimal::from(400_000));
+
+        assert_eq!(settled[0].settlement, BetSettlement::Won);
+        assert_eq!(settled[0].payout, BigDecimal::from(6_300_000));
+        assert_eq!(settled[1].payout, BigDecimal::from(2_100_000));
+        assert_eq!(settled[2].settlement, BetSettlement::Lost);
+        assert_eq!(settled[2].payout, BigDecimal::zero());
+    }
+
+    #[test]
+    fn test_shares_weight_winnings_when_present() {
+        let bets = vec![
+            stake("a", false, 1_000_000, Some(3)),
+            stake("b", false, 1_000_000, Some(1)),
+            stake("c", true, 2_000_000, Some(2)),
+        ];
+        let settled = settle_bets(&bets, false, &BigDecimal::zero());
+
+        assert_eq!(settled[0].payout, BigDecimal::from(2_500_000));
+        assert_eq!(settled[1].payout, BigDecimal::from(1_500_000));
+    }
+
+    #[test]
+    fn test_refunds_when_nobody_won() {
+        let bets = vec![
+            stake("a", true, 1_000_000, None),
+            stake("b", true, 2_000_000, None),
+        ];
+        let settled = settle_bets(&bets, false, &BigDecimal::from(10));
+
+        assert!(settled
+            .iter()
+            .all(|bet| bet.settlement == BetSettlement::Refunded));
+        assert_eq!(settled[1].payout, BigDecimal::from(2_000_000));
+    }
+
+    #[test]
+    fn test_payouts_never_exceed_pool() {
+        let bets = vec![
+            stake("a", true, 1, None),
+            stake("b", true, 1, None),
+            stake("c", true, 1, None),
+            stake("d", false, 100, None),
+        ];
+        let settled = settle_bets(&bets, true, &BigDecimal::zero());
+        let paid: BigDecimal = settled.iter().map(|bet| bet.payout.clone()).sum();
+
+        assert!(paid <= 103);
+        assert_eq!(settled[0].payout, BigDecimal::from(34));
+    }
+}
@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    Operator,
+    MarketCurator,
+    Analyst,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    ViewAdminStats,
+    ManageRoles,
+    ViewSyncStatus,
+    TriggerSync,
+    UpdateYields,
+    ManageMarkets,
+    ResolveMarkets,
+    ManageApiKeys,
+    ReadMarkets,
+    PlaceBets,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    ReadMarkets,
+    PlaceBets,
+    AdminSync,
+}
+
+impl Role {
+    pub const ALL: [Role; 4] = [
+        Role::Admin,
+        Role::Operator,
+        Role::MarketCurator,
+        Role::Analyst,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Operator => "operator",
+            Role::MarketCurator => "market_curator",
+            Role::Analyst => "analyst",
+        }
+    }
+
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Role::Admin => &[
+                Permission::ViewAdminStats,
+                Permission::ManageRoles,
+                Permission::ViewSyncStatus,
+                Permission::TriggerSync,
+                Permission::UpdateYields,
+                Permission::ManageMarkets,
+                Permission::ResolveMarkets,
+                Permission::ManageApiKeys,
+                Permission::ReadMarkets,
+                Permission::PlaceBets,
+            ],
+            Role::Operator => &[
+                Permission::ViewAdminStats,
+                Permission::ViewSyncStatus,
+                Permission::TriggerSync,
+                Permission::UpdateYields,
+                Permission::PlaceBets,
+            ],
+            Role::MarketCurator => &[Permission::ManageMarkets],
+            Role::Analyst => &[Permission::ViewAdminStats, Permission::ViewSyncStatus],
+        }
+    }
+
+    pub fn grants(&self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Role::ALL
+            .into_iter()
+            .find(|role| role.as_str() == s)
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown role '{}'", s)))
+    }
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::ViewAdminStats => "view_admin_stats",
+            Permission::ManageRoles => "manage_roles",
+            Permission::ViewSyncStatus => "view_sync_status",
+            Permission::TriggerSync => "trigger_sync",
+            Permission::UpdateYields => "update_yields",
+            Permission::ManageMarkets => "manage_markets",
+            Permission::ResolveMarkets => "resolve_markets",
+            Permission::ManageApiKeys => "manage_api_keys",
+            Permission::ReadMarkets => "read_markets",
+            Permission::PlaceBets => "place_bets",
+        }
+    }
+}
+
+impl ApiKeyScope {
+    pub const ALL: [ApiKeyScope; 3] = [
+        ApiKeyScope::ReadMarkets,
+        ApiKeyScope::PlaceBets,
+        ApiKeyScope::AdminSync,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::ReadMarkets => "read_markets",
+            ApiKeyScope::PlaceBets => "place_bets",
+            ApiKeyScope::AdminSync => "admin_sync",
+        }
+    }
+
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            ApiKeyScope::ReadMarkets => &[Permission::ReadMarkets],
+            ApiKeyScope::PlaceBets => &[Permission::PlaceBets],
+            ApiKeyScope::AdminSync => &[Permission::ViewSyncStatus, Permission::TriggerSync],
+        }
+    }
+
+    pub fn grants(&self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
+
+impl FromStr for ApiKeyScope {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ApiKeyScope::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == s)
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown API key scope '{}'", s)))
+    }
+}
+
+pub fn roles_grant(roles: &[Role], permission: Permission) -> bool {
+    roles.iter().any(|role| role.grants(permission))
+}
+
+pub fn scopes_grant(scopes: &[ApiKeyScope], permission: Permission) -> bool {
+    scopes.iter().any(|scope| scope.grants(permission))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_permissions() {
+        assert!(roles_grant(&[Role::Admin], Permission::ManageRoles));
+        assert!(roles_grant(&[Role::Operator], Permission::TriggerSync));
+        assert!(!roles_grant(&[Role::Operator], Permission::ManageRoles));
+        assert!(roles_grant(
+            &[Role::MarketCurator],
+            Permission::ManageMarkets
+        ));
+        assert!(!roles_grant(
+            &[Role::MarketCurator],
+            Permission::TriggerSync
+        ));
+        assert!(!roles_grant(
+            &[Role::MarketCurator],
+            Permission::ResolveMarkets
+        ));
+        assert!(roles_grant(&[Role::Analyst], Permission::ViewAdminStats));
+        assert!(!roles_grant(&[Role::Analyst], Permission::UpdateYields));
+        assert!(!roles_grant(&[], Permission::ViewSyncStatus));
+    }
+
+    #[test]
+    fn test_scope_permissions() {
+        assert!(scopes_grant(
+            &[ApiKeyScope::AdminSync],
+            Permission::TriggerSync
+        ));
+        assert!(!scopes_grant(
+            &[ApiKeyScope::AdminSync],
+            Permission::UpdateYields
+        ));
+        assert!(scopes_grant(
+            &[ApiKeyScope::PlaceBets],
+            Permission::PlaceBets
+        ));
+        assert!(!scopes_grant(
+            &[ApiKeyScope::ReadMarkets],
+            Permission::PlaceBets
+        ));
+        assert!(!scopes_grant(
+            &[ApiKeyScope::AdminSync],
+            Permission::ManageApiKeys
+        ));
+        assert!("admin_sync".parse::<ApiKeyScope>().is_ok());
+        assert!("write_everything".parse::<ApiKeyScope>().is_err());
+    }
+
+    #[test]
+    fn test_role_round_trip() {
+        for role in Role::ALL {
+            assert_eq!(role.as_str().parse::<Role>().unwrap(), role);
+        }
+        assert!("superuser".parse::<Role>().is_err());
+    }
+}
@@ -0,0 +1,279 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use ethers::types::{Address, Signature};
+use std::str::FromStr;
+
+use crate::error::{AppError, Result};
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiweMessage {
+    pub domain: String,
+    pub address: Address,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: DateTime<FixedOffset>,
+    pub expiration_time: Option<DateTime<FixedOffset>>,
+    pub not_before: Option<DateTime<FixedOffset>>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl FromStr for SiweMessage {
+    type Err = AppError;
+
+    fn from_str(message: &str) -> Result<Self> {
+        let invalid =
+            |reason: &str| AppError::BadRequest(format!("Invalid SIWE message: {}", reason));
+
+        let mut lines = message.lines();
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(HEADER_SUFFIX))
+            .ok_or_else(|| invalid("missing header"))?;
+        let domain = domain
+            .split_once("://")
+            .map(|(_, rest)| rest)
+            .unwrap_or(domain)
+            .to_string();
+
+        let address = lines
+            .next()
+            .ok_or_else(|| invalid("missing address"))?
+            .trim()
+            .parse::<Address>()
+            .map_err(|_| invalid("malformed address"))?;
+
+        if lines.next() != Some("") {
+            return Err(invalid("expected blank line after address"));
+        }
+
+        let mut statement = None;
+        let mut next = lines.next().ok_or_else(|| invalid("missing URI"))?;
+        if !next.starts_with("URI: ") {
+            if !next.is_empty() {
+                statement = Some(next.to_string());
+                if lines.next() != Some("") {
+                    return Err(invalid("expected blank line after statement"));
+                }
+            }
+            next = lines.next().ok_or_else(|| invalid("missing URI"))?;
+        }
+
+        let mut uri = None;
+        let mut version = None;
+        let mut chain_id = None;
+        let mut nonce = None;
+        let mut issued_at = None;
+        let mut expiration_time = None;
+        let mut not_before = None;
+        let mut request_id = None;
+        let mut resources = Vec::new();
+        let mut in_resources = false;
+
+        for line in std::iter::once(next).chain(lines) {
+            if in_resources {
+                if let Some(resource) = line.strip_prefix("- ") {
+                    resources.push(resource.to_string());
+                    continue;
+                }
+                return Err(invalid("unexpected content after resources"));
+            }
+
+            if line == "Resources:" {
+                in_resources = true;
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or_else(|| invalid("malformed field"))?;
+
+            match key {
+                "URI" => uri = Some(value.to_string()),
+                "Version" => version = Some(value.to_string()),
+                "Chain ID" => {
+                    chain_id = Some(
+                        value
+                            .parse::<u64>()
+                            .map_err(|_| invalid("malformed chain ID"))?,
+                    )
+                }
+                "Nonce" => nonce = Some(value.to_string()),
+                "Issued At" => issued_at = Some(parse_timestamp(value)?),
+                "Expiration Time" => expiration_time = Some(parse_timestamp(value)?),
+                "Not Before" => not_before = Some(parse_timestamp(value)?),
+                "Request ID" => request_id = Some(value.to_string()),
+                _ => return Err(invalid(&format!("unknown field '{}'", key))),
+            }
+        }
+
+        let nonce = nonce.ok_or_else(|| invalid("missing nonce"))?;
+        if nonce.len() < 8 || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(invalid("nonce must be at least 8 alphanumeric characters"));
+        }
+
+        Ok(Self {
+            domain,
+            address,
+            statement,
+            uri: uri.ok_or_else(|| invalid("missing URI"))?,
+            version: version.ok_or_else(|| invalid("missing version"))?,
+            chain_id: chain_id.ok_or_else(|| invalid("missing chain ID"))?,
+            nonce,
+            issued_at: issued_at.ok_or_else(|| invalid("missing issued-at"))?,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+}
+
+impl SiweMessage {
+    pub fn validate(
+        &self,
+        expected_domain: &str,
+        expected_chain_id: u64,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        if self.version != "1" {
+            return Err(AppError::Unauthorized(format!(
+                "Unsupported SIWE version {}",
+                self.version
+            )));
+        }
+
+        if self.domain != expected_domain {
+            return Err(AppError::Unauthorized(format!(
+                "SIWE domain mismatch: expected {}, got {}",
+                expected_domain, self.domain
+            )));
+        }
+
+        if self.chain_id != expected_chain_id {
+            return Err(AppError::Unauthorized(format!(
+                "SIWE chain ID mismatch: expected {}, got {}",
+                expected_chain_id, self.chain_id
+            )));
+        }
+
+        if let Some(expiration_time) = self.expiration_time {
+            if now >= expiration_time {
+                return Err(AppError::Unauthorized(
+                    "SIWE message has expired".to_string(),
+                ));
+            }
+        }
+
+        if let Some(not_before) = self.not_before {
+            if now < not_before {
+                return Err(AppError::Unauthorized(
+                    "SIWE message is not yet valid".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn verify_signature(&self, raw_message: &str, signature: &str) -> Result<()> {
+        let signature = Signature::from_str(signature.trim_start_matches("0x"))
+            .map_err(|e| AppError::BadRequest(format!("Invalid signature: {}", e)))?;
+
+        let recovered = signature
+            .recover(raw_message)
+            .map_err(|e| AppError::Unauthorized(format!("Failed to recover signer: {}", e)))?;
+
+        if recovered != self.address {
+            return Err(AppError::Unauthorized(
+                "Signature does not match SIWE address".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_timestamp(value: &str) -> Result<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value)
+        .map_err(|_| AppError::BadRequest(format!("Invalid SIWE timestamp: {}", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use ethers::signers::{LocalWallet, Signer};
+
+    fn build_message(address: Address, statement: Option<&str>, expires_in: Duration) -> String {
+        let now = Utc::now();
+        let mut message = format!(
+            "app.whizy.io{}\n{}\n\n",
+            HEADER_SUFFIX,
+            ethers::utils::to_checksum(&address, None)
+        );
+        if let Some(statement) = statement {
+            message.push_str(&format!("{}\n", statement));
+        }
+        message.push_str(&format!(
+            "\nURI: https://app.whizy.io\nVersion: 1\nChain ID: 296\nNonce: abcd1234efgh\nIssued At: {}\nExpiration Time: {}",
+            now.to_rfc3339(),
+            (now + expires_in).to_rfc3339()
+        ));
+        message
+    }
+
+    #[test]
+    fn test_parse_message() {
+        let address = Address::random();
+        let raw = build_message(address, Some("Sign in to Whizy"), Duration::minutes(5));
+        let message: SiweMessage = raw.parse().unwrap();
+
+        assert_eq!(message.domain, "app.whizy.io");
+        assert_eq!(message.address, address);
+        assert_eq!(message.statement.as_deref(), Some("Sign in to Whizy"));
+        assert_eq!(message.chain_id, 296);
+        assert_eq!(message.nonce, "abcd1234efgh");
+        assert!(message.expiration_time.is_some());
+
+        let without_statement: SiweMessage = build_message(address, None, Duration::minutes(5))
+            .parse()
+            .unwrap();
+        assert!(without_statement.statement.is_none());
+
+        assert!("not a siwe message".parse::<SiweMessage>().is_err());
+    }
+
+    #[test]
+    fn test_validate_message() {
+        let raw = build_message(Address::random(), None, Duration::minutes(5));
+        let message: SiweMessage = raw.parse().unwrap();
+        let now = Utc::now();
+
+        assert!(message.validate("app.whizy.io", 296, now).is_ok());
+        assert!(message.validate("evil.example", 296, now).is_err());
+        assert!(message.validate("app.whizy.io", 1, now).is_err());
+        assert!(message
+            .validate("app.whizy.io", 296, now + Duration::minutes(10))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_signature() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let raw = build_message(wallet.address(), None, Duration::minutes(5));
+        let message: SiweMessage = raw.parse().unwrap();
+
+        let signature = wallet.sign_message(&raw).await.unwrap().to_string();
+        assert!(message.verify_signature(&raw, &signature).is_ok());
+
+        let other = LocalWallet::new(&mut rand::thread_rng());
+        let forged = other.sign_message(&raw).await.unwrap().to_string();
+        assert!(message.verify_signature(&raw, &forged).is_err());
+    }
+}